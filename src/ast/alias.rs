@@ -26,20 +26,26 @@ impl Alias {
             Some(pair) => {
                 let aliases = pair
                     .into_inner()
-                    .map(|pair| match pair.as_rule() {
+                    .filter_map(|pair| match pair.as_rule() {
+                        // Types are imported directly, as they cannot be aliased.
+                        Rule::atom => {
+                            context.declare_type_alias(Atom::new(pair), scope.clone());
+                            None
+                        }
                         Rule::handle => {
-                            let input = Handle::new_in_scope(scope.clone(), pair.clone());
+                            let input = Handle::new_in_scope(scope.clone(), pair.clone(), context);
                             let output = Handle::new(pair, context);
-                            Alias { input, output }
+                            Some(Alias { input, output })
                         }
                         Rule::alias => {
                             let mut pairs = pair.into_inner();
-                            let input = Handle::new_in_scope(scope.clone(), pairs.next().unwrap());
+                            let input =
+                                Handle::new_in_scope(scope.clone(), pairs.next().unwrap(), context);
                             let output = Handle::new(pairs.next().unwrap(), context);
                             if !output.can_alias(&input) {
                                 context.error_invalid_alias_arity(&input, &output);
                             }
-                            Alias { input, output }
+                            Some(Alias { input, output })
                         }
                         _ => unreachable!(),
                     })
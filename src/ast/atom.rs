@@ -47,13 +47,38 @@ impl Atom {
         };
         Self::from(string)
     }
+
+    /// Whether this atom can be written without quotes. Only letters which are certainly allowed
+    /// in a bare atom are accepted, so some atoms which could be written bare are still quoted.
+    fn is_bare(&self) -> bool {
+        let mut chars = self.0.chars();
+        let first = match chars.next() {
+            Some(first) => first,
+            None => return false,
+        };
+        let is_letter =
+            |ch: char| ch.is_ascii_alphanumeric() || (ch.is_alphabetic() && !ch.is_ascii());
+        (first.is_ascii_lowercase()
+            || first.is_alphabetic()
+                && !first.is_ascii()
+                && !first.is_uppercase()
+                && !first.is_lowercase())
+            && chars.all(|ch| ch == '_' || is_letter(ch))
+    }
 }
 
+/// Atoms are written bare if they can be, or else quoted, with as many `#` around the quotes as
+/// are needed for the quotes not to appear within the atom.
 impl Display for Atom {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
-        // TODO: properly quote atoms as necessary
-        //       somehow detect that by un-parsing the atom string?
-        self.0.fmt(f)
+        if self.is_bare() {
+            return f.write_str(&self.0);
+        }
+        let mut hashes = String::new();
+        while self.0.contains(&format!("'{}", hashes)) {
+            hashes.push('#');
+        }
+        write!(f, "{0}'{1}'{0}", hashes, self.0)
     }
 }
 
@@ -27,12 +27,40 @@ impl Body {
         }))
     }
 
+    pub fn new_procession(procession: Procession) -> Self {
+        Self(Some(Disjunction {
+            cases: vec![Conjunction {
+                terms: vec![procession],
+            }],
+        }))
+    }
+
     pub fn handles_mut(&mut self) -> impl Iterator<Item = &mut Handle> {
         self.0
             .iter_mut()
             .flat_map(|disjunction| disjunction.handles_mut())
     }
 
+    /// All queries made by this body, including those nested in expressions.
+    pub fn queries(&self) -> impl Iterator<Item = &Query> {
+        self.0.iter().flat_map(|disjunction| disjunction.queries())
+    }
+
+    /// Queries which must all succeed for this body to succeed. Only bodies without
+    /// alternatives are considered, and nested bodies are skipped, so some such queries may
+    /// be missing.
+    pub fn calls(&self) -> impl Iterator<Item = &Query> {
+        self.0
+            .iter()
+            .filter(|disjunction| disjunction.cases.len() == 1)
+            .flat_map(|disjunction| disjunction.cases[0].terms.iter())
+            .flat_map(|procession| procession.steps.iter())
+            .filter_map(|step| match step {
+                Unification::Query(query) => Some(query),
+                _ => None,
+            })
+    }
+
     pub fn identifiers<'a>(&'a self) -> impl Iterator<Item = Identifier> + 'a {
         self.0
             .iter()
@@ -54,7 +82,7 @@ impl Body {
 
         for (identifier, count) in counts {
             if count <= 1 {
-                context.error_singleton_variable(head.as_ref(), identifier.name());
+                context.error_singleton_variable(head.as_ref(), &identifier);
             }
         }
     }
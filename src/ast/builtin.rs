@@ -15,15 +15,15 @@ op_2!(sub);
 op_2!(mul);
 op_2!(div);
 op_2!(rem);
-// op_2!(exp);
-// op_2!(eq);
-// op_2!(neq);
-// op_2!(lt);
-// op_2!(gt);
-// op_2!(leq);
-// op_2!(geq);
-// op_2!(or);
-// op_2!(and);
+op_2!(pow);
+op_2!(eq);
+op_2!(neq);
+op_2!(lt);
+op_2!(gt);
+op_2!(leq);
+op_2!(geq);
+op_2!(or);
+op_2!(and);
 // op_2!(dif);
 op_2!(bitor);
 op_2!(bitand);
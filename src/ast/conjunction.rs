@@ -22,6 +22,10 @@ impl Conjunction {
         self.terms.iter_mut().flat_map(|term| term.handles_mut())
     }
 
+    pub fn queries(&self) -> impl Iterator<Item = &Query> {
+        self.terms.iter().flat_map(|term| term.queries())
+    }
+
     pub fn identifiers<'a>(&'a self) -> impl Iterator<Item = Identifier> + 'a {
         self.terms.iter().flat_map(|term| term.identifiers())
     }
@@ -1,9 +1,22 @@
 use super::*;
+use crate::parser::Rule;
 use crate::program::*;
-use crate::Lumber;
+use crate::{Analysis, CompileError, Lint, LintLevel, Lumber};
 use pest::Span;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::path::PathBuf;
+use std::rc::Rc;
+
+/// The variables of a lambda which is currently being parsed.
+#[derive(Default)]
+pub(crate) struct LambdaEnvironment {
+    /// Variables local to the lambda.
+    variables: HashMap<String, Identifier>,
+    /// Variables from enclosing environments that are referenced by the lambda.
+    captures: Vec<Identifier>,
+    /// Whether the parameters are being parsed. Parameters are always local to the lambda.
+    parameters: bool,
+}
 
 #[derive(Default)]
 pub struct Context<'p> {
@@ -11,8 +24,25 @@ pub struct Context<'p> {
     pub(crate) root_path: PathBuf,
     pub(crate) current_scope: Scope,
     pub(crate) current_environment: HashMap<String, Identifier>,
+    pub(crate) lambda_environments: Vec<LambdaEnvironment>,
+    pub(crate) lambdas: Vec<(Query, Body)>,
+    pub(crate) lambda_count: usize,
     pub(crate) modules: HashMap<Scope, ModuleHeader>,
     pub(crate) errors: HashMap<Scope, Vec<crate::Error>>,
+    /// Problems reported by lints set to warn, which do not prevent the program from compiling.
+    pub(crate) warnings: Vec<crate::Error>,
+    /// The levels of lints set for the whole program.
+    pub(crate) lints: HashMap<Lint, LintLevel>,
+    /// Imported predicates which have been referenced by their alias.
+    pub(crate) used_aliases: HashSet<Handle>,
+    pub(crate) debug: bool,
+    /// The source of the module currently being parsed.
+    pub(crate) current_source: Option<Rc<Source>>,
+    /// The directive or clause currently being parsed, used to locate errors which do not
+    /// have a more specific location.
+    pub(crate) current_span: SourceSpan,
+    /// Where each variable of the current clause first appears.
+    pub(crate) variable_spans: HashMap<Identifier, SourceSpan>,
 }
 
 impl<'p> Context<'p> {
@@ -33,32 +63,163 @@ impl<'p> Context<'p> {
         source: &str,
         natives: HashMap<Handle, NativeFunction<'p>>,
     ) -> crate::Result<Lumber<'p>> {
+        let mut root_module = self.parse(root_path, source)?;
+        let native_handles: Vec<_> = natives.keys().collect();
+        self.check(&mut root_module, native_handles.as_slice())?;
+        let mut database = self.link(root_module, &natives);
+        self.take_errors()?;
+        database.set_debug(self.debug);
+        Ok(Lumber::build(database, self.warnings))
+    }
+
+    /// Checks a program without compiling it, collecting every problem that is found along with
+    /// the information needed to navigate its source code. All declared native functions are
+    /// assumed to be bound.
+    pub(crate) fn analyze(mut self, root_path: PathBuf, source: &str) -> crate::Result<Analysis> {
+        let mut root_module = self.parse(root_path, source)?;
+        let natives: HashMap<_, _> = self
+            .modules
+            .values()
+            .flat_map(|header| header.natives.iter().cloned())
+            .map(|handle| {
+                let native = NativeFunction::new(|_| Box::new(std::iter::empty()));
+                (handle, native)
+            })
+            .collect();
+        let native_handles: Vec<_> = natives.keys().collect();
+        let mut checked = self.check(&mut root_module, native_handles.as_slice());
+        if checked.is_ok() {
+            self.link(root_module.clone(), &natives);
+            checked = self.take_errors();
+        }
+        let mut diagnostics = match checked {
+            Ok(()) => vec![],
+            Err(error) if error.kind() == crate::ErrorKind::Multiple => error.into_errors(),
+            Err(error) => vec![error],
+        };
+        diagnostics.append(&mut self.warnings);
+        Ok(Analysis::new(&self.modules, &root_module, diagnostics))
+    }
+
+    /// Parses the main module, and all of its submodules.
+    fn parse(&mut self, root_path: PathBuf, source: &str) -> crate::Result<Module> {
         self.root_path = root_path;
+        let mut root_file = None;
         if self.root_path.exists() && std::fs::metadata(&self.root_path)?.is_file() {
+            root_file = Some(self.root_path.clone());
             self.root_path.pop();
         }
-        self.modules
-            .insert(Scope::default(), ModuleHeader::new(Scope::default()));
 
-        let mut root_module = Module::new(source, &mut self)?;
-        let native_handles: Vec<_> = natives.keys().collect();
-        self.validate_headers(native_handles.as_slice());
-        if !self.errors.is_empty() {
-            return Err(crate::Error::multiple_by_module(self.errors));
-        }
-        root_module.resolve_scopes(&mut self);
-        if !self.errors.is_empty() {
-            return Err(crate::Error::multiple_by_module(self.errors));
-        }
-        let mut database: Database = Database::new(root_module.into_definitions());
+        let source = Source::new(root_file, source.to_owned());
+        let mut header = ModuleHeader::new(Scope::default());
+        header.source = Some(source.clone());
+        self.modules.insert(Scope::default(), header);
+        self.current_source = Some(source.clone());
+        let root_module = Module::new(&source, self)?;
+        self.current_source = None;
+        self.current_span = SourceSpan::default();
+        Ok(root_module)
+    }
+
+    /// Checks the parsed program, stopping after the first stage which finds any errors.
+    fn check(&mut self, module: &mut Module, natives: &[&Handle]) -> crate::Result<()> {
+        self.validate_headers(natives);
+        self.take_errors()?;
+        module.resolve_scopes(self);
+        self.take_errors()?;
+        self.check_lints(module);
+        self.check_types(module);
+        self.take_errors()
+    }
+
+    /// Links the definitions of a checked program with its libraries, checking the modes in
+    /// which its predicates are called.
+    fn link(
+        &mut self,
+        module: Module,
+        natives: &HashMap<Handle, NativeFunction<'p>>,
+    ) -> Database<'p> {
+        let mut database: Database = Database::new(module.into_definitions());
         for header in self.modules.values() {
-            database.apply_header(header, &natives);
+            database.apply_header(header, natives);
         }
-        let database = self
-            .libraries
+        let database = std::mem::take(&mut self.libraries)
             .into_iter()
             .fold(database, |database, (_, library)| database.merge(library));
-        Ok(Lumber::build(database))
+        for (scope, error) in database.check_modes() {
+            self.errors.entry(scope).or_default().push(error);
+        }
+        database
+    }
+
+    /// Sorts the problems found so far by the level of the lint that reported them, if any.
+    /// Allowed lints are discarded, and warnings are set aside to be returned with the compiled
+    /// program. If any errors remain, they are returned.
+    fn take_errors(&mut self) -> crate::Result<()> {
+        let mut errors = std::mem::take(&mut self.errors)
+            .into_iter()
+            .collect::<Vec<_>>();
+        errors.sort_by_cached_key(|(scope, _)| scope.to_string());
+        for (scope, errors) in errors {
+            for error in errors {
+                match error.lint().map(|lint| self.lint_level(&scope, lint)) {
+                    Some(LintLevel::Allow) => {}
+                    Some(LintLevel::Warn) => {
+                        let mut warning = error.into_warning();
+                        warning.module = Some(scope.to_string());
+                        self.warnings.push(warning);
+                    }
+                    Some(LintLevel::Deny) | None => {
+                        self.errors.entry(scope.clone()).or_default().push(error)
+                    }
+                }
+            }
+        }
+        if self.errors.is_empty() {
+            return Ok(());
+        }
+        let errors = std::mem::take(&mut self.errors);
+        // A syntax error on its own is reported as it would be if recovery had not been attempted.
+        if let [error] = errors.values().flatten().collect::<Vec<_>>().as_slice() {
+            if matches!(error.compile_error(), Some(CompileError::Syntax { .. })) {
+                return Err(errors.into_values().flatten().next().unwrap());
+            }
+        }
+        Err(crate::Error::multiple_by_module(errors))
+    }
+
+    /// The level of a lint in the module at `scope`. A level set in a module applies to all of
+    /// its submodules, unless they set their own level, and takes precedence over the level set
+    /// for the whole program.
+    fn lint_level(&self, scope: &Scope, lint: Lint) -> LintLevel {
+        let mut scope = Some(scope.clone());
+        while let Some(current) = scope {
+            let level = self
+                .modules
+                .get(&current)
+                .and_then(|module| module.lints.get(&lint));
+            if let Some(level) = level {
+                return *level;
+            }
+            scope = current.parent();
+        }
+        self.lints
+            .get(&lint)
+            .copied()
+            .unwrap_or_else(|| lint.default_level())
+    }
+
+    /// Converts a span of the module currently being parsed into a span that may be kept.
+    pub(crate) fn span(&self, span: Span) -> SourceSpan {
+        match &self.current_source {
+            Some(source) => SourceSpan::new(source, span),
+            None => SourceSpan::default(),
+        }
+    }
+
+    /// Sets the location used for errors found while parsing the current directive or clause.
+    pub(crate) fn locate(&mut self, span: Span) {
+        self.current_span = self.span(span);
     }
 
     fn enter_module(&mut self, module: Atom) {
@@ -69,34 +230,115 @@ impl<'p> Context<'p> {
         self.current_scope.pop();
     }
 
-    pub(crate) fn get_variable(&mut self, name: &str) -> Identifier {
-        if let Some(existing) = self.current_environment.get(name) {
-            existing.clone()
-        } else {
-            let ident = Identifier::new(name.to_owned());
-            self.current_environment
-                .insert(name.to_owned(), ident.clone());
-            ident
+    fn environment(&self, depth: usize) -> &HashMap<String, Identifier> {
+        match depth {
+            0 => &self.current_environment,
+            _ => &self.lambda_environments[depth - 1].variables,
+        }
+    }
+
+    fn innermost_environment_mut(&mut self) -> &mut HashMap<String, Identifier> {
+        match self.lambda_environments.last_mut() {
+            Some(lambda) => &mut lambda.variables,
+            None => &mut self.current_environment,
+        }
+    }
+
+    /// Looks up a variable by name, starting from the innermost lambda. If the variable is found
+    /// in an enclosing environment, it is captured by every lambda in between. Otherwise, a new
+    /// variable is created in the innermost environment.
+    pub(crate) fn get_variable(&mut self, name: &str, span: Span) -> Identifier {
+        let innermost = self.lambda_environments.len();
+        let outermost = match self.lambda_environments.last() {
+            Some(lambda) if lambda.parameters => innermost,
+            _ => 0,
+        };
+        for depth in (outermost..=innermost).rev() {
+            if let Some(existing) = self.environment(depth).get(name).cloned() {
+                for lambda in &mut self.lambda_environments[depth..] {
+                    if !lambda.captures.contains(&existing) {
+                        lambda.captures.push(existing.clone());
+                    }
+                }
+                return existing;
+            }
+        }
+        let ident = Identifier::new(name.to_owned());
+        self.innermost_environment_mut()
+            .insert(name.to_owned(), ident.clone());
+        self.variable_spans.insert(ident.clone(), self.span(span));
+        ident
+    }
+
+    /// Declares every variable which appears in a clause or lambda body, outside of any lambda it
+    /// contains, before it is parsed. A lambda may then capture a variable of its enclosing
+    /// environment even when the variable is first written after the lambda.
+    pub(crate) fn declare_variables(&mut self, pair: crate::Pair) {
+        for pair in pair.into_inner() {
+            match pair.as_rule() {
+                Rule::variable => {
+                    self.get_variable(pair.as_str(), pair.as_span());
+                }
+                Rule::lambda => {}
+                _ => self.declare_variables(pair),
+            }
         }
     }
 
     pub(crate) fn fresh_variable(&mut self) -> Identifier {
-        self.get_variable(&format!("#{}", self.current_environment.len()))
+        let environment = self.innermost_environment_mut();
+        let ident = Identifier::new(format!("#{}", environment.len()));
+        environment.insert(ident.name().to_owned(), ident.clone());
+        ident
     }
 
     pub(crate) fn reset_environment(&mut self) {
         self.current_environment.clear();
+        self.lambda_environments.clear();
+        self.variable_spans.clear();
+    }
+
+    pub(crate) fn enter_lambda(&mut self) {
+        self.lambda_environments.push(LambdaEnvironment {
+            parameters: true,
+            ..LambdaEnvironment::default()
+        });
+    }
+
+    pub(crate) fn enter_lambda_body(&mut self) {
+        self.lambda_environments.last_mut().unwrap().parameters = false;
+    }
+
+    /// Leaves the innermost lambda, returning the variables it captured.
+    pub(crate) fn leave_lambda(&mut self) -> Vec<Identifier> {
+        self.lambda_environments.pop().unwrap().captures
     }
 
-    pub(crate) fn add_module(&mut self, module: Atom) -> crate::Result<Option<Module>> {
+    pub(crate) fn fresh_lambda_name(&mut self) -> Atom {
+        self.lambda_count += 1;
+        Atom::from(format!("#lambda{}", self.lambda_count))
+    }
+
+    pub(crate) fn declare_lambda(&mut self, head: Query, body: Body) {
+        self.lambdas.push((head, body));
+    }
+
+    /// Takes the definitions of all lambdas declared since the last time they were taken.
+    pub(crate) fn take_lambdas(&mut self) -> Vec<(Query, Body)> {
+        std::mem::take(&mut self.lambdas)
+    }
+
+    pub(crate) fn add_module(&mut self, module: Atom, doc: &str) -> crate::Result<Option<Module>> {
         let scope = self.current_scope.join(module.clone());
         if self.modules.contains_key(&scope) {
             self.error_duplicate_module(scope);
             return Ok(None);
         }
         self.enter_module(module.clone());
-        self.modules
-            .insert(scope, ModuleHeader::new(self.current_scope.clone()));
+        let mut header = ModuleHeader::new(self.current_scope.clone());
+        header.span = self.current_span.clone();
+        header.insert_module_doc(doc);
+        self.modules.insert(scope.clone(), header);
         let mut module_path = self
             .current_scope
             .into_iter()
@@ -107,10 +349,18 @@ impl<'p> Context<'p> {
         if !module_path.exists() {
             module_path = module_path.with_file_name(format!("{}/mod.lumber", module.as_ref()));
         }
-        let source = std::fs::read_to_string(&module_path)?;
-        let module = Module::new(&source, self)?;
+        let source = Source::new(
+            Some(module_path.clone()),
+            std::fs::read_to_string(&module_path)?,
+        );
+        self.modules.get_mut(&scope).unwrap().source = Some(source.clone());
+        let parent_source = self.current_source.replace(source.clone());
+        let parent_span = std::mem::take(&mut self.current_span);
+        let module = Module::new(&source, self);
+        self.current_source = parent_source;
+        self.current_span = parent_span;
         self.leave_module();
-        Ok(Some(module))
+        Ok(Some(module?))
     }
 
     fn current_module_mut(&mut self) -> &mut ModuleHeader {
@@ -124,6 +374,20 @@ impl<'p> Context<'p> {
         }
     }
 
+    pub(crate) fn declare_test(&mut self, handle: Handle) {
+        self.current_module_mut().insert_test(handle);
+    }
+
+    pub(crate) fn declare_module_doc(&mut self, doc: &str) {
+        self.current_module_mut().insert_module_doc(doc);
+    }
+
+    pub(crate) fn declare_doc(&mut self, handle: &Handle, doc: &str) {
+        if !doc.is_empty() {
+            self.current_module_mut().insert_doc(handle.clone(), doc);
+        }
+    }
+
     pub(crate) fn declare_mutable(&mut self, handle: Handle) {
         let handle = self.current_module_mut().insert_mutable(handle);
         if let Some(handle) = handle {
@@ -161,6 +425,154 @@ impl<'p> Context<'p> {
         }
     }
 
+    pub(crate) fn declare_operator(&mut self, operator: OperatorDefinition) {
+        let symbol = operator.symbol.clone();
+        let conflict = self.current_module_mut().insert_operator(operator);
+        if let Some(conflict) = conflict {
+            self.error_duplicate_operator(&symbol, &conflict.handle);
+        }
+    }
+
+    /// Imports the operators declared in another module. If a handle is provided, only the
+    /// operators for that predicate are imported. Operators can only be imported from modules
+    /// that have already been parsed.
+    pub(crate) fn import_operators(&mut self, module: &Scope, handle: Option<&Handle>) {
+        let operators = match self.modules.get(module) {
+            Some(module) => module
+                .operators
+                .values()
+                .filter(|operator| handle.map_or(true, |handle| &operator.handle == handle))
+                .cloned()
+                .collect::<Vec<_>>(),
+            None => return,
+        };
+        for operator in operators {
+            self.declare_operator(operator);
+        }
+    }
+
+    /// The operators available in the current module. Questions are parsed outside of any
+    /// module, so they only have access to the built-in operators.
+    pub(crate) fn operators(&self) -> impl Iterator<Item = &OperatorDefinition> {
+        self.modules
+            .get(&self.current_scope)
+            .into_iter()
+            .flat_map(|module| module.operators.values())
+    }
+
+    pub(crate) fn operator(&self, symbol: &str) -> Option<&OperatorDefinition> {
+        self.modules
+            .get(&self.current_scope)
+            .and_then(|module| module.operators.get(symbol))
+    }
+
+    pub(crate) fn declare_type(&mut self, name: Atom, ty: Type) {
+        if Type::is_builtin(&name) {
+            self.error_builtin_type(&name);
+            return;
+        }
+        self.current_module_mut().insert_type(name, ty);
+    }
+
+    pub(crate) fn declare_type_export(&mut self, name: Atom) {
+        let export = self.current_module_mut().insert_type_export(name);
+        if let Some(export) = export {
+            self.error_duplicate_type_export(&export);
+        }
+    }
+
+    pub(crate) fn declare_type_alias(&mut self, name: Atom, source: Scope) {
+        let existing = self
+            .current_module_mut()
+            .insert_type_alias(name.clone(), source);
+        if let Some(existing) = existing {
+            self.error_duplicate_type_import(&name, &existing);
+        }
+    }
+
+    pub(crate) fn declare_signature(&mut self, handle: Handle, types: Vec<Type>) {
+        let existing = self
+            .current_module_mut()
+            .insert_signature(handle.clone(), types);
+        if existing.is_some() {
+            self.error_duplicate_signature(&handle);
+        }
+    }
+
+    pub(crate) fn declare_mode(&mut self, handle: Handle, mode: Mode) {
+        self.current_module_mut().insert_mode(handle, mode);
+    }
+
+    pub(crate) fn declare_lint(&mut self, name: &Atom, level: LintLevel) {
+        match name.as_ref().parse() {
+            Ok(lint) => self.current_module_mut().insert_lint(lint, level),
+            Err(()) => self.error_unknown_lint(name),
+        }
+    }
+
+    pub(crate) fn declare_determinism(&mut self, handle: Handle, determinism: Determinism) {
+        let existing = self
+            .current_module_mut()
+            .insert_determinism(handle.clone(), determinism);
+        if existing.is_some() {
+            self.error_duplicate_determinism(&handle);
+        }
+    }
+
+    /// Finds a type by name, as visible from the module at `scope`.
+    pub(crate) fn resolve_type(&self, name: &Atom, scope: &Scope) -> Option<(Scope, &Vec<Type>)> {
+        let (scope, types) = self.modules.get(scope)?.resolve_type(name, self)?;
+        Some((scope.clone(), types))
+    }
+
+    /// Checks every clause of the program against the declared signatures of the predicates it
+    /// defines and calls.
+    pub(crate) fn check_types(&mut self, module: &Module) {
+        let mut errors = vec![];
+        for (handle, definition) in module.definitions() {
+            for (head, body) in definition.iter() {
+                let scope = handle.module();
+                self.check_query(head, false, &mut |error| {
+                    errors.push((scope.clone(), error))
+                });
+                for query in body.queries() {
+                    self.check_query(query, true, &mut |error| {
+                        errors.push((scope.clone(), error))
+                    });
+                }
+            }
+        }
+        for (scope, error) in errors {
+            self.errors.entry(scope).or_default().push(error);
+        }
+    }
+
+    fn check_query(&self, query: &Query, call: bool, report: &mut dyn FnMut(crate::Error)) {
+        let handle = query.as_ref();
+        let scope = handle.module();
+        let types = match self
+            .modules
+            .get(&scope)
+            .and_then(|module| module.signatures.get(handle))
+        {
+            Some(types) => types,
+            None => return,
+        };
+        for (i, (ty, pattern)) in types.iter().zip(query.patterns.iter()).enumerate() {
+            if !ty.admits(pattern, &scope, self) {
+                report(
+                    crate::Error::compile(CompileError::SignatureMismatch {
+                        handle: handle.clone(),
+                        call,
+                        argument: i + 1,
+                        expected: ty.to_string(),
+                    })
+                    .at(&query.span),
+                );
+            }
+        }
+    }
+
     pub(crate) fn declare_predicate(&mut self, predicate: Handle) {
         self.current_module_mut().insert(predicate);
     }
@@ -197,16 +609,19 @@ impl<'p> Context<'p> {
             }
             return None;
         }
-        let module = handle.module();
+        let module = self.modules.get(&handle.module()).unwrap();
+        if module.aliases.contains_key(handle) {
+            self.used_aliases.insert(handle.clone());
+        }
         let resolved = self
             .modules
-            .get(&module)
+            .get(&handle.module())
             .unwrap()
             .resolve(handle, in_scope, self);
         match resolved {
             Ok(resolved) => return Some(resolved.clone()),
             Err(error) => {
-                self.current_errors_mut().push(error);
+                self.current_errors_mut().push(error.at(&handle.span));
                 None
             }
         }
@@ -218,94 +633,215 @@ impl Context<'_> {
         self.errors.entry(self.current_scope.clone()).or_default()
     }
 
+    /// Reports an error in the current module, located at the current directive or clause if it
+    /// does not already have a location.
+    fn report(&mut self, error: crate::Error) {
+        let error = error.at(&self.current_span);
+        self.current_errors_mut().push(error);
+    }
+
+    pub(crate) fn error_syntax(&mut self, error: crate::Error) {
+        self.report(error);
+    }
+
     pub(crate) fn error_duplicate_module(&mut self, module: Scope) {
-        self.current_errors_mut().push(crate::Error::parse(&format!(
-            "Module {} declared multiple times.",
-            module
-        )));
+        self.report(crate::Error::compile(CompileError::DuplicateModule {
+            module: module.to_string(),
+        }));
     }
 
     pub(crate) fn error_duplicate_export(&mut self, handle: Handle) {
-        self.current_errors_mut().push(crate::Error::parse(&format!(
-            "{} exported multiple times.",
-            handle
-        )));
+        self.report(crate::Error::compile(CompileError::DuplicateExport {
+            handle,
+        }));
     }
 
     pub(crate) fn error_duplicate_incomplete(&mut self, handle: Handle) {
-        self.current_errors_mut().push(crate::Error::parse(&format!(
-            "{} decared as incomplete multiple times.",
-            handle
-        )));
+        self.report(crate::Error::compile(CompileError::DuplicateIncomplete {
+            handle,
+        }));
     }
 
     pub(crate) fn error_duplicate_mutable(&mut self, handle: Handle) {
-        self.current_errors_mut().push(crate::Error::parse(&format!(
-            "{} set as mutable multiple times.",
-            handle
-        )));
+        self.report(crate::Error::compile(CompileError::DuplicateMutable {
+            handle,
+        }));
     }
 
     pub(crate) fn error_negative_scope(&mut self, span: Span) {
-        self.current_errors_mut().push(crate::Error::parse(&format!(
-            "Scope {} goes above the main module.",
-            span.as_str()
-        )));
+        let error = crate::Error::compile(CompileError::NegativeScope {
+            scope: span.as_str().to_owned(),
+        })
+        .at(&self.span(span));
+        self.report(error);
     }
 
     pub(crate) fn error_duplicate_import(&mut self, import: Handle, from: Handle) {
-        self.current_errors_mut().push(crate::Error::parse(&format!(
-            "{} already imported from {}.",
-            import, from
-        )));
+        self.report(crate::Error::compile(CompileError::DuplicateImport {
+            handle: import,
+            from,
+        }));
     }
 
     pub(crate) fn error_duplicate_glob(&mut self, module: Scope) {
-        self.current_errors_mut().push(crate::Error::parse(&format!(
-            "Module {} imported multiple times.",
-            module
-        )));
+        self.report(crate::Error::compile(CompileError::DuplicateGlob {
+            module: module.to_string(),
+        }));
     }
 
     pub(crate) fn error_duplicate_native(&mut self, handle: Handle) {
-        self.current_errors_mut().push(crate::Error::parse(&format!(
-            "Native function {} declared multiple times.",
-            handle
-        )));
+        self.report(crate::Error::compile(CompileError::DuplicateNative {
+            handle,
+        }));
     }
 
     pub(crate) fn error_unrecognized_operator(&mut self, token: &str) {
-        self.current_errors_mut().push(crate::Error::parse(&format!(
-            "Unrecognized operator `{}`.",
-            token
-        )));
+        self.report(crate::Error::compile(CompileError::UnrecognizedOperator {
+            operator: token.to_owned(),
+        }));
+    }
+
+    pub(crate) fn error_invalid_escape(&mut self, escape: &str) {
+        self.report(crate::Error::compile(CompileError::InvalidEscape {
+            escape: escape.to_owned(),
+        }));
+    }
+
+    pub(crate) fn error_unbound_map_key(&mut self, key: &str) {
+        self.report(crate::Error::compile(CompileError::UnboundMapKey {
+            key: key.to_owned(),
+        }));
+    }
+
+    pub(crate) fn error_duplicate_map_key(&mut self, key: &str) {
+        self.report(crate::Error::compile(CompileError::DuplicateMapKey {
+            key: key.to_owned(),
+        }));
+    }
+
+    pub(crate) fn error_invalid_type(&mut self, reason: &str) {
+        self.report(crate::Error::compile(CompileError::InvalidType {
+            reason: reason.to_owned(),
+        }));
+    }
+
+    pub(crate) fn error_builtin_type(&mut self, name: &Atom) {
+        self.report(crate::Error::compile(CompileError::BuiltinType {
+            name: name.to_string(),
+        }));
+    }
+
+    pub(crate) fn error_duplicate_type_export(&mut self, name: &Atom) {
+        self.report(crate::Error::compile(CompileError::DuplicateTypeExport {
+            name: name.to_string(),
+        }));
+    }
+
+    pub(crate) fn error_duplicate_type_import(&mut self, name: &Atom, from: &Scope) {
+        self.report(crate::Error::compile(CompileError::DuplicateTypeImport {
+            name: name.to_string(),
+            from: from.to_string(),
+        }));
+    }
+
+    pub(crate) fn error_duplicate_signature(&mut self, handle: &Handle) {
+        self.report(crate::Error::compile(CompileError::DuplicateSignature {
+            handle: handle.clone(),
+        }));
+    }
+
+    pub(crate) fn error_invalid_mode(&mut self, handle: &Handle) {
+        self.report(crate::Error::compile(CompileError::InvalidMode {
+            handle: handle.clone(),
+        }));
+    }
+
+    pub(crate) fn error_duplicate_determinism(&mut self, handle: &Handle) {
+        self.report(crate::Error::compile(CompileError::DuplicateDeterminism {
+            handle: handle.clone(),
+        }));
+    }
+
+    pub(crate) fn error_invalid_operator_arity(&mut self, symbol: &str, handle: &Handle) {
+        self.report(crate::Error::compile(CompileError::InvalidOperatorArity {
+            operator: symbol.to_owned(),
+            handle: handle.clone(),
+        }));
+    }
+
+    pub(crate) fn error_invalid_operator_precedence(&mut self, symbol: &str, precedence: &str) {
+        self.report(crate::Error::compile(
+            CompileError::InvalidOperatorPrecedence {
+                operator: symbol.to_owned(),
+                precedence: precedence.to_owned(),
+            },
+        ));
+    }
+
+    pub(crate) fn error_builtin_operator(&mut self, symbol: &str) {
+        self.report(crate::Error::compile(CompileError::BuiltinOperator {
+            operator: symbol.to_owned(),
+        }));
+    }
+
+    pub(crate) fn error_duplicate_operator(&mut self, symbol: &str, handle: &Handle) {
+        self.report(crate::Error::compile(CompileError::DuplicateOperator {
+            operator: symbol.to_owned(),
+            handle: handle.clone(),
+        }));
     }
 
     pub(crate) fn error_invalid_alias_arity(&mut self, input: &Handle, output: &Handle) {
-        self.current_errors_mut().push(crate::Error::parse(&format!(
-            "Cannot change arity of {} when aliasing to {}.",
-            input, output,
-        )));
+        self.report(crate::Error::compile(CompileError::AliasArityMismatch {
+            input: input.clone(),
+            output: output.clone(),
+        }));
     }
 
-    pub(crate) fn error_singleton_variable(&mut self, handle: &Handle, variable: &str) {
-        self.current_errors_mut().push(crate::Error::parse(&format!(
-            "Singleton variable {} in predicate {}.",
-            variable, handle,
-        )));
+    pub(crate) fn error_singleton_variable(&mut self, handle: &Handle, variable: &Identifier) {
+        self.error_singleton(variable, Some(handle.clone()));
+    }
+
+    pub(crate) fn error_singleton_lambda_variable(&mut self, variable: &Identifier) {
+        self.error_singleton(variable, None);
+    }
+
+    fn error_singleton(&mut self, variable: &Identifier, predicate: Option<Handle>) {
+        let error = crate::Error::compile(CompileError::SingletonVariable {
+            variable: variable.name().to_owned(),
+            predicate,
+        });
+        let span = self
+            .variable_spans
+            .get(variable)
+            .cloned()
+            .unwrap_or_default();
+        self.report(error.at(&span));
+    }
+
+    pub(crate) fn error_unknown_lint(&mut self, name: &Atom) {
+        self.report(crate::Error::compile(CompileError::UnknownLint {
+            name: name.to_string(),
+        }));
     }
 
     pub(crate) fn error_unlinked_library(&mut self, handle: &Handle, library: &Atom) {
-        self.current_errors_mut().push(crate::Error::parse(&format!(
-            "Referencing predicate {} from unlinked library {}.",
-            handle, library,
-        )));
+        self.report(
+            crate::Error::compile(CompileError::UnlinkedLibrary {
+                handle: handle.clone(),
+                library: library.to_string(),
+            })
+            .at(&handle.span),
+        );
     }
 
     pub(crate) fn error_unresolved_library_predicate(&mut self, handle: &Handle, library: &Atom) {
-        self.current_errors_mut().push(crate::Error::parse(&format!(
-            "No predicate {} is exported by the library {}.",
-            handle, library,
-        )));
+        self.report(
+            crate::Error::compile(CompileError::UnexportedLibraryPredicate {
+                handle: handle.clone(),
+                library: library.to_string(),
+            })
+            .at(&handle.span),
+        );
     }
 }
@@ -11,8 +11,11 @@ impl Definition {
         self.0.push((query, body));
     }
 
-    pub fn bodies_mut(&mut self) -> impl Iterator<Item = &mut Body> {
-        self.0.iter_mut().map(|(_, body)| body)
+    /// All handles referenced by this definition, including those in lambdas in its heads.
+    pub fn handles_mut(&mut self) -> impl Iterator<Item = &mut Handle> {
+        self.0
+            .iter_mut()
+            .flat_map(|(head, body)| head.pattern_handles_mut().chain(body.handles_mut()))
     }
 
     pub fn merge(&mut self, mut other: Definition) {
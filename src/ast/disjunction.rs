@@ -22,6 +22,10 @@ impl Disjunction {
         self.cases.iter_mut().flat_map(|case| case.handles_mut())
     }
 
+    pub fn queries(&self) -> impl Iterator<Item = &Query> {
+        self.cases.iter().flat_map(|case| case.queries())
+    }
+
     pub fn identifiers<'a>(&'a self) -> impl Iterator<Item = Identifier> + 'a {
         self.cases.iter().flat_map(|case| case.identifiers())
     }
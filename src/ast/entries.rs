@@ -0,0 +1,115 @@
+use super::*;
+use crate::parser::Rule;
+use std::hash::{Hash, Hasher};
+
+/// The entries of a map pattern. Keys are always ground patterns, so they are compared for
+/// equality rather than unified.
+#[derive(Clone, Default, Debug)]
+pub(crate) struct Entries {
+    entries: Vec<(Pattern, Pattern)>,
+}
+
+impl Eq for Entries {}
+impl PartialEq for Entries {
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len()
+            && self
+                .iter()
+                .all(|(key, value)| other.get(key) == Some(value))
+    }
+}
+
+impl Hash for Entries {
+    fn hash<H: Hasher>(&self, hasher: &mut H) {
+        // Entries are unordered, so only the length is hashed to stay consistent with `eq`.
+        self.len().hash(hasher);
+    }
+}
+
+impl Entries {
+    pub fn new(pair: crate::Pair, context: &mut Context) -> Self {
+        assert_eq!(pair.as_rule(), Rule::entries);
+        let mut entries = Self::default();
+        for pair in pair.into_inner() {
+            let mut pairs = pair.into_inner();
+            let key_str = pairs.peek().unwrap().as_str();
+            let key = Pattern::new(pairs.next().unwrap(), context);
+            let value = Pattern::new(pairs.next().unwrap(), context);
+            if key.identifiers().next().is_some() {
+                context.error_unbound_map_key(key_str);
+            } else if entries.get(&key).is_some() {
+                context.error_duplicate_map_key(key_str);
+            } else {
+                entries.insert(key, value);
+            }
+        }
+        entries
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn get(&self, key: &Pattern) -> Option<&Pattern> {
+        self.entries
+            .iter()
+            .find(|(existing, _)| existing == key)
+            .map(|(_, value)| value)
+    }
+
+    /// Inserts an entry, replacing the value of any existing entry with the same key.
+    pub fn insert(&mut self, key: Pattern, value: Pattern) {
+        match self
+            .entries
+            .iter_mut()
+            .find(|(existing, _)| *existing == key)
+        {
+            Some(entry) => entry.1 = value,
+            None => self.entries.push((key, value)),
+        }
+    }
+
+    pub fn remove(&mut self, key: &Pattern) -> Option<Pattern> {
+        let index = self
+            .entries
+            .iter()
+            .position(|(existing, _)| existing == key)?;
+        Some(self.entries.remove(index).1)
+    }
+
+    pub fn append(&mut self, other: &mut Self) {
+        for (key, value) in other.entries.drain(..) {
+            self.insert(key, value);
+        }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&Pattern, &Pattern)> {
+        self.entries.iter().map(|(key, value)| (key, value))
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (&Pattern, &mut Pattern)> {
+        self.entries.iter_mut().map(|(key, value)| (&*key, value))
+    }
+}
+
+impl std::iter::FromIterator<(Pattern, Pattern)> for Entries {
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = (Pattern, Pattern)>,
+    {
+        let mut entries = Self::default();
+        for (key, value) in iter {
+            entries.insert(key, value);
+        }
+        entries
+    }
+}
+
+impl IntoIterator for Entries {
+    type Item = (Pattern, Pattern);
+    type IntoIter = std::vec::IntoIter<(Pattern, Pattern)>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.entries.into_iter()
+    }
+}
@@ -7,7 +7,6 @@ const fn left(token: &'static str) -> Operator {
     Operator::new(token, Assoc::Left)
 }
 
-#[allow(dead_code)]
 const fn right(token: &'static str) -> Operator {
     Operator::new(token, Assoc::Right)
 }
@@ -76,13 +75,31 @@ impl Expression {
 
     pub fn handles_mut<'a>(&'a mut self) -> Box<dyn Iterator<Item = &mut Handle> + 'a> {
         match self {
-            Self::Operation(.., unifications) => {
-                Box::new(unifications.iter_mut().flat_map(Unification::handles_mut))
+            Self::Operation(pattern, unifications) => Box::new(
+                pattern
+                    .handles_mut()
+                    .chain(unifications.iter_mut().flat_map(Unification::handles_mut)),
+            ),
+            Self::Value(pattern) => pattern.handles_mut(),
+            #[cfg(feature = "builtin-sets")]
+            Self::SetAggregation(pattern, body) => {
+                Box::new(pattern.handles_mut().chain(body.handles_mut()))
+            }
+            Self::ListAggregation(pattern, body) => {
+                Box::new(pattern.handles_mut().chain(body.handles_mut()))
+            }
+        }
+    }
+
+    pub fn queries<'a>(&'a self) -> Box<dyn Iterator<Item = &Query> + 'a> {
+        match self {
+            Self::Operation(_, unifications) => {
+                Box::new(unifications.iter().flat_map(Unification::queries))
             }
             Self::Value(..) => Box::new(std::iter::empty()),
             #[cfg(feature = "builtin-sets")]
-            Self::SetAggregation(.., body) => Box::new(body.handles_mut()),
-            Self::ListAggregation(.., body) => Box::new(body.handles_mut()),
+            Self::SetAggregation(_, body) => Box::new(body.queries()),
+            Self::ListAggregation(_, body) => Box::new(body.queries()),
         }
     }
 
@@ -120,20 +137,37 @@ fn expression(pair: crate::Pair, context: &mut Context) -> Option<(Pattern, Vec<
     }
 }
 
-fn operation(pair: crate::Pair, context: &mut Context) -> Option<(Pattern, Vec<Unification>)> {
-    let prec_climber = PrecClimber::new(vec![
+fn builtin_operators() -> Vec<Operator<'static>> {
+    vec![
         // left("\\"),
-        // left("||"),
-        // left("&&"),
+        left("||"),
+        left("&&"),
         left("|"),
         left("^"),
         left("&"),
-        // left("==") | left("!="),
-        // left("<") | left(">") | left("<=") | left(">="),
+        left("==") | left("!="),
+        left("<") | left(">") | left("<=") | left(">="),
         left("+") | left("-"),
         left("*") | left("/") | left("%"),
-        // right("**")
-    ]);
+        right("**"),
+    ]
+}
+
+pub(crate) fn is_builtin_operator(symbol: &str) -> bool {
+    PrecClimber::new(builtin_operators()).contains(symbol)
+}
+
+fn operation(pair: crate::Pair, context: &mut Context) -> Option<(Pattern, Vec<Unification>)> {
+    let operators = context
+        .operators()
+        .map(|op| (op.symbol.clone(), op.precedence, op.assoc))
+        .collect::<Vec<_>>();
+    let prec_climber = operators.iter().fold(
+        PrecClimber::new(builtin_operators()),
+        |prec_climber, (symbol, precedence, assoc)| {
+            prec_climber.with_operator(symbol, *precedence, *assoc)
+        },
+    );
     let context = RefCell::new(context);
     prec_climber.climb(
         pair.into_inner(),
@@ -143,7 +177,8 @@ fn operation(pair: crate::Pair, context: &mut Context) -> Option<(Pattern, Vec<U
             let (lhs, mut lwork) = lhs?;
             let (rhs, mut rwork) = rhs?;
             let output = Pattern::Variable(context.fresh_variable());
-            let operation = match op.as_str() {
+            let span = context.span(op.as_span());
+            let mut operation = match op.as_str() {
                 "+" => builtin::add(lhs, rhs, output.clone()),
                 "-" => builtin::sub(lhs, rhs, output.clone()),
                 "*" => builtin::mul(lhs, rhs, output.clone()),
@@ -152,6 +187,15 @@ fn operation(pair: crate::Pair, context: &mut Context) -> Option<(Pattern, Vec<U
                 "|" => builtin::bitor(lhs, rhs, output.clone()),
                 "&" => builtin::bitand(lhs, rhs, output.clone()),
                 "^" => builtin::bitxor(lhs, rhs, output.clone()),
+                "**" => builtin::pow(lhs, rhs, output.clone()),
+                "==" => builtin::eq(lhs, rhs, output.clone()),
+                "!=" => builtin::neq(lhs, rhs, output.clone()),
+                "<" => builtin::lt(lhs, rhs, output.clone()),
+                ">" => builtin::gt(lhs, rhs, output.clone()),
+                "<=" => builtin::leq(lhs, rhs, output.clone()),
+                ">=" => builtin::geq(lhs, rhs, output.clone()),
+                "&&" => builtin::and(lhs, rhs, output.clone()),
+                "||" => builtin::or(lhs, rhs, output.clone()),
                 token => match op.into_inner().next() {
                     Some(pair) => match pair.as_rule() {
                         Rule::named_operator => {
@@ -162,10 +206,16 @@ fn operation(pair: crate::Pair, context: &mut Context) -> Option<(Pattern, Vec<U
                                 vec![lhs, rhs, output.clone()],
                             ))
                         }
-                        Rule::symbolic_operator => {
-                            context.error_unrecognized_operator(token);
-                            return None;
-                        }
+                        Rule::symbolic_operator => match context.operator(token) {
+                            Some(operator) => Unification::Query(Query::new(
+                                operator.handle.clone(),
+                                vec![lhs, rhs, output.clone()],
+                            )),
+                            None => {
+                                context.error_unrecognized_operator(token);
+                                return None;
+                            }
+                        },
                         _ => unreachable!(),
                     },
                     None => {
@@ -174,6 +224,10 @@ fn operation(pair: crate::Pair, context: &mut Context) -> Option<(Pattern, Vec<U
                     }
                 },
             };
+            if let Unification::Query(query) = &mut operation {
+                query.handle.span = span.clone();
+                query.span = span;
+            }
             lwork.append(&mut rwork);
             lwork.push(operation);
             Some((output, lwork))
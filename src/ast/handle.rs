@@ -9,6 +9,8 @@ pub struct Handle {
     pub(crate) scope: Scope,
     /// The arity of the predicate or function being described
     pub(crate) arity: Arity,
+    /// Where this handle was written, if it was written in the source code.
+    pub(crate) span: SourceSpan,
 }
 
 pub trait AsHandle {
@@ -29,11 +31,37 @@ impl AsHandle for &str {
         }
         let arity = Arity::new(pairs.next().unwrap());
         assert_eq!(Rule::EOI, pairs.next().unwrap().as_rule());
-        Ok(Handle { scope, arity })
+        Ok(Handle::from_parts(scope, arity))
     }
 }
 
 impl Handle {
+    /// The name of the predicate, without its path or arity.
+    pub fn name(&self) -> String {
+        self.scope.head().to_string()
+    }
+
+    /// The number of arguments of the predicate, including named arguments.
+    pub fn arity(&self) -> u32 {
+        self.arity.len + self.arity.fields.iter().map(|(_, len)| len).sum::<u32>()
+    }
+
+    /// The number of positional arguments of the predicate, which come before its named
+    /// arguments.
+    pub fn positional_arity(&self) -> u32 {
+        self.arity.len
+    }
+
+    /// The names of the named arguments of the predicate, along with the number of values that
+    /// each of them takes.
+    pub fn fields(&self) -> Vec<(String, u32)> {
+        self.arity
+            .fields
+            .iter()
+            .map(|(name, len)| (name.to_string(), *len))
+            .collect()
+    }
+
     pub(crate) fn library(&self) -> &[Atom] {
         self.scope.library()
     }
@@ -50,6 +78,7 @@ impl Handle {
         Self {
             scope: Scope::default().join(self.scope.head()),
             arity: self.arity.clone(),
+            span: self.span.clone(),
         }
     }
 
@@ -57,6 +86,7 @@ impl Handle {
         Self {
             scope: scope.join(self.scope.head()),
             arity: self.arity.clone(),
+            span: self.span.clone(),
         }
     }
 
@@ -68,8 +98,27 @@ impl Handle {
         self.arity.can_alias(&other.arity)
     }
 
+    /// Whether this is one of the `@core::call` handles, which invoke a lambda.
+    pub(crate) fn is_call(&self) -> bool {
+        self.arity.fields.is_empty() && self.scope == Scope::builtin("call")
+    }
+
+    /// Whether this is the `@core::clause` handle, which inspects the clauses of a definition.
+    pub(crate) fn is_clause(&self) -> bool {
+        self.arity == Arity::new_len(2) && self.scope == Scope::builtin("clause")
+    }
+
+    /// Whether this is the `@core::copy_term` handle, which copies a term with fresh variables.
+    pub(crate) fn is_copy_term(&self) -> bool {
+        self.arity == Arity::new_len(2) && self.scope == Scope::builtin("copy_term")
+    }
+
     pub(crate) fn from_parts(scope: Scope, arity: Arity) -> Self {
-        Handle { scope, arity }
+        Handle {
+            scope,
+            arity,
+            span: SourceSpan::default(),
+        }
     }
 
     pub(crate) fn binop(scope: Scope) -> Self {
@@ -77,16 +126,17 @@ impl Handle {
     }
 
     pub(crate) fn new(pair: crate::Pair, context: &mut Context) -> Self {
-        Self::new_in_scope(context.current_scope.clone(), pair)
+        Self::new_in_scope(context.current_scope.clone(), pair, context)
     }
 
-    pub(crate) fn new_in_scope(mut scope: Scope, pair: crate::Pair) -> Self {
+    pub(crate) fn new_in_scope(mut scope: Scope, pair: crate::Pair, context: &Context) -> Self {
         assert_eq!(pair.as_rule(), Rule::handle);
+        let span = context.span(pair.as_span());
         let mut pairs = pair.into_inner();
         let atom = Atom::new(pairs.next().unwrap());
         scope.push(atom);
         let arity = Arity::new(pairs.next().unwrap());
-        Self { scope, arity }
+        Self { scope, arity, span }
     }
 }
 
@@ -0,0 +1,127 @@
+use super::*;
+use crate::parser::Rule;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::rc::Rc;
+
+/// An anonymous predicate, which can be passed around as a value and later invoked using
+/// `@core::call`.
+///
+/// The body of the lambda is lifted out into a hidden definition in the module it was written
+/// in, so the lambda itself only needs to refer to that definition by handle, along with the
+/// values of any variables it has captured from the surrounding clause.
+#[derive(Clone, Debug)]
+pub(crate) struct Lambda {
+    /// The handle of the lifted definition.
+    pub(crate) handle: Handle,
+    /// Variables captured from the enclosing clause, and the values they were captured with.
+    pub(crate) captures: Vec<(Identifier, Pattern)>,
+    /// The head and body of the lifted definition, as written, which are kept only so that the
+    /// lambda can be printed. They are identified by the handle.
+    pub(crate) definition: Rc<(Query, Procession)>,
+}
+
+impl PartialEq for Lambda {
+    fn eq(&self, other: &Self) -> bool {
+        self.handle == other.handle && self.captures == other.captures
+    }
+}
+
+impl Eq for Lambda {}
+
+impl Hash for Lambda {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.handle.hash(state);
+        self.captures.hash(state);
+    }
+}
+
+impl Lambda {
+    pub fn new(pair: crate::Pair, context: &mut Context) -> Option<Self> {
+        assert_eq!(pair.as_rule(), Rule::lambda);
+        let mut pairs = pair.into_inner().peekable();
+        context.enter_lambda();
+        let params = if pairs.peek().unwrap().as_rule() == Rule::list_entries {
+            pairs
+                .next()
+                .unwrap()
+                .into_inner()
+                .map(|pair| Pattern::new(pair, context))
+                .collect()
+        } else {
+            vec![]
+        };
+        context.enter_lambda_body();
+        let pair = pairs.next().unwrap();
+        context.declare_variables(pair.clone());
+        let procession = Procession::new(pair, context);
+        let captures = context.leave_lambda();
+        let procession = procession?;
+        let body = Body::new_procession(procession.clone());
+
+        let name = context.fresh_lambda_name();
+        let scope = context.current_scope.join(name);
+        let handle = Handle::from_parts(scope, Arity::new_len(params.len() as u32));
+        let head = Query::new(handle.clone(), params);
+        Self::check_variables(&head, &body, &captures, context);
+        let definition = Rc::new((head.clone(), procession));
+        context.declare_lambda(head, body);
+
+        Some(Self {
+            handle,
+            captures: captures
+                .into_iter()
+                .map(|identifier| (identifier.clone(), Pattern::Variable(identifier)))
+                .collect(),
+            definition,
+        })
+    }
+
+    /// This same lambda, having captured different values.
+    pub(crate) fn with_captures(&self, captures: Vec<(Identifier, Pattern)>) -> Self {
+        Self {
+            handle: self.handle.clone(),
+            captures,
+            definition: self.definition.clone(),
+        }
+    }
+
+    /// Variables local to the lambda are checked for singletons here, as the lambda is
+    /// lifted out of its clause. Captured variables are counted as part of the enclosing clause.
+    fn check_variables(head: &Query, body: &Body, captures: &[Identifier], context: &mut Context) {
+        let counts = body
+            .identifiers()
+            .chain(head.identifiers())
+            .filter(|ident| !ident.is_wildcard())
+            .filter(|ident| !captures.contains(ident))
+            .fold(
+                HashMap::<Identifier, usize>::default(),
+                |mut map, identifier| {
+                    *map.entry(identifier).or_default() += 1;
+                    map
+                },
+            );
+
+        for (identifier, count) in counts {
+            if count <= 1 {
+                context.error_singleton_lambda_variable(&identifier);
+            }
+        }
+    }
+
+    pub fn handles_mut<'a>(&'a mut self) -> Box<dyn Iterator<Item = &'a mut Handle> + 'a> {
+        Box::new(
+            std::iter::once(&mut self.handle).chain(
+                self.captures
+                    .iter_mut()
+                    .flat_map(|(_, pattern)| pattern.handles_mut()),
+            ),
+        )
+    }
+
+    pub fn identifiers<'a>(&'a self) -> impl Iterator<Item = Identifier> + 'a {
+        self.captures
+            .iter()
+            .flat_map(|(_, pattern)| pattern.identifiers())
+    }
+}
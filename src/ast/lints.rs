@@ -0,0 +1,124 @@
+use super::*;
+use crate::CompileError;
+use std::collections::{HashMap, HashSet};
+
+impl Context<'_> {
+    /// Checks the resolved program for problems that are reported by lints: unused imports,
+    /// unused private predicates, and calls which can never match any clause.
+    ///
+    /// The main module may be used by questions, so its imports and predicates are never
+    /// considered unused.
+    pub(crate) fn check_lints(&mut self, module: &Module) {
+        let mut errors = vec![];
+
+        let mut clauses = HashMap::<&Handle, Vec<&Query>>::new();
+        let mut referenced = HashSet::<&Handle>::new();
+        for (handle, definition) in module.definitions() {
+            for (head, body) in definition.iter() {
+                clauses.entry(handle).or_default().push(head);
+                referenced.extend(
+                    body.queries()
+                        .map(AsRef::as_ref)
+                        .filter(|&called| called != handle),
+                );
+            }
+        }
+        for header in self.modules.values() {
+            referenced.extend(header.aliases.values());
+        }
+
+        for header in self.modules.values() {
+            if header.scope == Scope::default() {
+                continue;
+            }
+            for (alias, source) in &header.aliases {
+                let used = self.used_aliases.contains(alias)
+                    || header.exports.contains(alias)
+                    || header
+                        .operators
+                        .values()
+                        .any(|operator| &operator.handle == alias || &operator.handle == source);
+                if !used {
+                    errors.push((
+                        header.scope.clone(),
+                        crate::Error::compile(CompileError::UnusedImport {
+                            handle: alias.head(),
+                            from: source.clone(),
+                        })
+                        .at(&alias.span),
+                    ));
+                }
+            }
+            for handle in &header.definitions {
+                let used = referenced.contains(handle)
+                    || handle.scope.head().as_ref().starts_with('#')
+                    || header.exports.contains(handle)
+                    || header.incompletes.contains(handle)
+                    || header.mutables.contains(handle)
+                    || header.tests.contains(handle)
+                    || header
+                        .operators
+                        .values()
+                        .any(|operator| &operator.handle == handle);
+                if !used {
+                    let span = clauses
+                        .get(handle)
+                        .and_then(|heads| heads.first())
+                        .map(|head| head.span.clone())
+                        .unwrap_or_else(|| handle.span.clone());
+                    errors.push((
+                        header.scope.clone(),
+                        crate::Error::compile(CompileError::UnusedPredicate {
+                            handle: handle.head(),
+                        })
+                        .at(&span),
+                    ));
+                }
+            }
+        }
+
+        for (handle, definition) in module.definitions() {
+            for (_, body) in definition.iter() {
+                for call in body.calls() {
+                    let callee = call.as_ref();
+                    let heads = match clauses.get(callee) {
+                        Some(heads) if self.is_closed(callee) => heads,
+                        _ => continue,
+                    };
+                    let reachable = heads.iter().any(|head| {
+                        head.patterns
+                            .iter()
+                            .zip(call.patterns.iter())
+                            .all(|(lhs, rhs)| lhs.may_unify(rhs))
+                    });
+                    if !reachable {
+                        errors.push((
+                            handle.module(),
+                            crate::Error::compile(CompileError::UnreachableClause {
+                                handle: handle.head(),
+                                call: callee.head(),
+                            })
+                            .at(&call.span),
+                        ));
+                    }
+                }
+            }
+        }
+
+        for (scope, error) in errors {
+            self.errors.entry(scope).or_default().push(error);
+        }
+    }
+
+    /// Whether all clauses of a predicate are known at compile time.
+    fn is_closed(&self, handle: &Handle) -> bool {
+        match self.modules.get(&handle.module()) {
+            Some(header) => {
+                !header.natives.contains(handle)
+                    && !header.mutables.contains(handle)
+                    && !header.incompletes.contains(handle)
+            }
+            None => false,
+        }
+    }
+}
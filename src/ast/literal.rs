@@ -1,5 +1,9 @@
+use super::*;
 use crate::parser::Rule;
 use ramp::{int::Int, rational::Rational};
+use std::cmp::Ordering;
+use std::fmt::{self, Formatter, Write};
+use std::hash::{Hash, Hasher};
 
 /// A literal value, which cannot be further pattern matched.
 #[derive(Clone, Hash, Eq, PartialEq, Ord, PartialOrd, Debug)]
@@ -8,43 +12,192 @@ pub(crate) enum Literal {
     Integer(Int),
     /// A fractional number.
     Rational(Rational),
-    /// A string.
+    /// A floating point number.
+    Float(Float),
+    /// A string, which is a sequence of Unicode scalar values.
     String(String),
 }
 
 impl Literal {
-    pub fn new(pair: crate::Pair) -> Self {
+    pub fn new(pair: crate::Pair, context: &mut Context) -> Option<Self> {
         assert_eq!(pair.as_rule(), Rule::literal);
+        let negative = pair.as_str().starts_with('-');
         let pair = just!(pair.into_inner());
-        match pair.as_rule() {
+        let literal = match pair.as_rule() {
             Rule::integer => {
                 let pair = just!(pair.into_inner());
                 match pair.as_rule() {
-                    Rule::integer_10 => {
-                        Self::Integer(Int::from_str_radix(pair.as_str(), 10).unwrap())
-                    }
-                    Rule::integer_2 => {
-                        Self::Integer(Int::from_str_radix(&pair.as_str()[2..], 2).unwrap())
-                    }
-                    Rule::integer_16 => {
-                        Self::Integer(Int::from_str_radix(&pair.as_str()[2..], 10).unwrap())
-                    }
+                    Rule::integer_10 => Some(Self::Integer(
+                        Int::from_str_radix(pair.as_str(), 10).unwrap(),
+                    )),
+                    Rule::integer_2 => Some(Self::Integer(
+                        Int::from_str_radix(&pair.as_str()[2..], 2).unwrap(),
+                    )),
+                    Rule::integer_16 => Some(Self::Integer(
+                        Int::from_str_radix(&pair.as_str()[2..], 10).unwrap(),
+                    )),
                     _ => unreachable!(),
                 }
             }
+            Rule::float => {
+                let float = pair.as_str().trim_end_matches('f').parse().unwrap();
+                Some(Literal::Float(Float(float)))
+            }
             Rule::decimal => {
                 let mut parts = pair.as_str().split('.');
                 let unit = parts.next().unwrap();
                 let fractional = parts.next().unwrap();
                 let denominator = Int::from(10).pow(fractional.len());
                 let numerator = format!("{}{}", unit, fractional).parse().unwrap();
-                Literal::Rational(Rational::new(numerator, denominator))
+                Some(Literal::Rational(Rational::new(numerator, denominator)))
             }
             Rule::string => {
-                let string = pair.as_str().trim_matches('#');
-                Self::String(string[1..string.len() - 1].to_owned())
+                let pair = just!(pair.into_inner());
+                match pair.as_rule() {
+                    Rule::raw_string => {
+                        let string = pair.as_str().trim_matches('#');
+                        Some(Self::String(string[1..string.len() - 1].to_owned()))
+                    }
+                    Rule::escaped_string => {
+                        let mut string = String::new();
+                        for pair in pair.into_inner() {
+                            match pair.as_rule() {
+                                Rule::string_text => string.push_str(pair.as_str()),
+                                Rule::escape => string.push(unescape(pair.as_str(), context)?),
+                                _ => unreachable!(),
+                            }
+                        }
+                        Some(Self::String(string))
+                    }
+                    _ => unreachable!(),
+                }
             }
             _ => unreachable!(),
+        }?;
+        if negative {
+            Some(literal.negate())
+        } else {
+            Some(literal)
+        }
+    }
+
+    fn negate(self) -> Self {
+        match self {
+            Self::Integer(int) => Self::Integer(-int),
+            Self::Rational(rational) => Self::Rational(-rational),
+            Self::Float(float) => Self::Float(Float(-float.0)),
+            Self::String(..) => unreachable!(),
+        }
+    }
+}
+
+/// A floating point number. Floats are compared by their bit patterns so that they can be
+/// unified like any other literal, meaning that `NaN` is equal to itself, and `0.0` is not equal
+/// to `-0.0`.
+#[derive(Copy, Clone, Debug)]
+pub(crate) struct Float(pub f64);
+
+impl PartialEq for Float {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.to_bits() == other.0.to_bits()
+    }
+}
+
+impl Eq for Float {}
+
+impl PartialOrd for Float {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Float {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.total_cmp(&other.0)
+    }
+}
+
+impl Hash for Float {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.to_bits().hash(state);
+    }
+}
+
+/// Writes a float as a Lumber float literal. Infinite and `NaN` values cannot be written as
+/// literals, so they are written as Rust would write them.
+pub(crate) fn fmt_float(float: f64, f: &mut Formatter) -> fmt::Result {
+    if float.is_finite() {
+        write!(f, "{:?}f", float)
+    } else {
+        write!(f, "{}", float)
+    }
+}
+
+/// Writes a rational as a Lumber decimal literal. Rationals with no finite decimal expansion
+/// cannot be written as literals, so they are written as a fraction, which is not valid syntax.
+pub(crate) fn fmt_rational(rational: &Rational, f: &mut Formatter) -> fmt::Result {
+    let (numerator, denominator) = rational.clone().into_parts();
+    let mut rest = denominator.clone();
+    let mut places = 0;
+    for factor in &[2, 5] {
+        let mut count = 0;
+        while &rest % *factor == 0 {
+            rest /= *factor;
+            count += 1;
+        }
+        places = std::cmp::max(places, count);
+    }
+    if rest != 1 {
+        return write!(f, "{}/{}", numerator, denominator);
+    }
+    if numerator < 0 {
+        f.write_char('-')?;
+    }
+    let digits = (numerator.abs() * Int::from(10).pow(places) / denominator).to_string();
+    if places == 0 {
+        return write!(f, "{}.0", digits);
+    }
+    let digits = format!("{:0>width$}", digits, width = places + 1);
+    let (unit, fractional) = digits.split_at(digits.len() - places);
+    write!(f, "{}.{}", unit, fractional)
+}
+
+/// Finds the character described by a single escape sequence. The grammar only allows valid
+/// escape sequences, but a `\u{..}` escape may still describe a value which is not a character.
+fn unescape(escape: &str, context: &mut Context) -> Option<char> {
+    match &escape[1..] {
+        "\"" => Some('"'),
+        "\\" => Some('\\'),
+        "n" => Some('\n'),
+        "t" => Some('\t'),
+        "r" => Some('\r'),
+        "0" => Some('\0'),
+        unicode => {
+            let code = u32::from_str_radix(&unicode[2..unicode.len() - 1], 16).unwrap();
+            let ch = std::char::from_u32(code);
+            if ch.is_none() {
+                context.error_invalid_escape(escape);
+            }
+            ch
+        }
+    }
+}
+
+/// Writes a string as a Lumber string literal, escaping it such that parsing the output gives
+/// back exactly the same string.
+pub(crate) fn fmt_string(string: &str, f: &mut Formatter) -> fmt::Result {
+    f.write_char('"')?;
+    for ch in string.chars() {
+        match ch {
+            '"' => f.write_str("\\\"")?,
+            '\\' => f.write_str("\\\\")?,
+            '\n' => f.write_str("\\n")?,
+            '\t' => f.write_str("\\t")?,
+            '\r' => f.write_str("\\r")?,
+            '\0' => f.write_str("\\0")?,
+            ch if ch.is_control() => write!(f, "\\u{{{:x}}}", ch as u32)?,
+            ch => f.write_char(ch)?,
         }
     }
+    f.write_char('"')
 }
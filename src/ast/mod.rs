@@ -9,17 +9,24 @@ mod body;
 mod conjunction;
 mod definition;
 mod disjunction;
+mod entries;
 mod expression;
 mod fields;
 mod handle;
 mod identifier;
+mod lambda;
 mod literal;
+mod mode;
 mod module;
+mod operator_definition;
 mod pattern;
+mod pretty;
 mod procession;
 mod query;
 mod scope;
+mod source_span;
 mod r#struct;
+mod r#type;
 mod unification;
 
 pub(crate) use alias::Alias;
@@ -29,21 +36,30 @@ pub(crate) use body::Body;
 pub(crate) use conjunction::Conjunction;
 pub(crate) use definition::Definition;
 pub(crate) use disjunction::Disjunction;
-pub(crate) use expression::Expression;
+pub(crate) use entries::Entries;
+pub(crate) use expression::{is_builtin_operator, Expression};
 pub(crate) use fields::Fields;
-pub(crate) use handle::{AsHandle, Handle};
+pub(crate) use handle::AsHandle;
+pub use handle::Handle;
 pub(crate) use identifier::Identifier;
-pub(crate) use literal::Literal;
+pub(crate) use lambda::Lambda;
+pub(crate) use literal::{fmt_float, fmt_rational, fmt_string, Float, Literal};
+pub(crate) use mode::{Determinism, Instantiation, Mode};
 pub(crate) use module::Module;
+pub(crate) use operator_definition::OperatorDefinition;
 pub(crate) use pattern::Pattern;
+pub(crate) use pretty::{collection, display, structure, text, Doc, Pretty};
 pub(crate) use procession::Procession;
 pub(crate) use query::Query;
 pub(crate) use r#struct::Struct;
+pub(crate) use r#type::Type;
 pub(crate) use scope::Scope;
+pub(crate) use source_span::{Source, SourceSpan};
 pub(crate) use unification::Unification;
 
 mod builtin;
 mod context;
+mod lints;
 mod module_header;
 mod prec_climber;
 
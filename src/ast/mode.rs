@@ -0,0 +1,164 @@
+use super::*;
+use crate::parser::Rule;
+use std::fmt::{self, Display, Formatter};
+
+/// The instantiation that a predicate requires of one of its arguments.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub(crate) enum Instantiation {
+    /// The argument must be bound, written `!`.
+    Bound,
+    /// The argument must not be bound, written `?`.
+    Unbound,
+    /// The argument may be bound or not, written `_`.
+    Any,
+}
+
+impl Instantiation {
+    /// The instantiation of an argument, as far as can be determined. A variable that may have
+    /// been bound earlier is `Any`.
+    pub fn of(pattern: &Pattern, unbound: &dyn Fn(&Identifier) -> bool) -> Self {
+        match pattern {
+            Pattern::Bound(inner) | Pattern::Unbound(inner) => Self::of(inner, unbound),
+            Pattern::Wildcard => Self::Unbound,
+            Pattern::Variable(identifier) if unbound(identifier) => Self::Unbound,
+            Pattern::Variable(..) => Self::Any,
+            _ => Self::Bound,
+        }
+    }
+
+    /// Whether an argument of the `other` instantiation could ever satisfy this requirement.
+    pub fn admits(self, other: Self) -> bool {
+        !matches!(
+            (self, other),
+            (Self::Bound, Self::Unbound) | (Self::Unbound, Self::Bound)
+        )
+    }
+}
+
+impl Display for Instantiation {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::Bound => write!(f, "!"),
+            Self::Unbound => write!(f, "?"),
+            Self::Any => write!(f, "_"),
+        }
+    }
+}
+
+/// A mode of a predicate, describing one way in which it may be called.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub(crate) struct Mode {
+    pub(crate) params: Vec<Instantiation>,
+}
+
+impl Mode {
+    /// Parses a mode from the head in a `:- mode` directive. Each parameter must be one of
+    /// `!`, `?` or `_`.
+    pub fn new(pair: crate::Pair, context: &mut Context) -> Option<(Handle, Self)> {
+        assert_eq!(pair.as_rule(), Rule::head);
+        let query = Query::from_head(pair, context);
+        let params = query
+            .patterns
+            .iter()
+            .map(|pattern| match pattern {
+                Pattern::Bound(inner) if matches!(**inner, Pattern::Wildcard) => {
+                    Some(Instantiation::Bound)
+                }
+                Pattern::Unbound(inner) if matches!(**inner, Pattern::Wildcard) => {
+                    Some(Instantiation::Unbound)
+                }
+                Pattern::Wildcard => Some(Instantiation::Any),
+                _ => None,
+            })
+            .collect::<Option<Vec<_>>>();
+        match params {
+            Some(params) => Some((query.handle, Self { params })),
+            None => {
+                context.error_invalid_mode(&query.handle);
+                None
+            }
+        }
+    }
+
+    /// Whether a call with arguments of exactly these instantiations satisfies this mode.
+    pub fn matches(&self, arguments: &[Instantiation]) -> bool {
+        self.params
+            .iter()
+            .zip(arguments)
+            .all(|(param, argument)| *param == Instantiation::Any || param == argument)
+    }
+
+    /// Whether a call with arguments of these instantiations could ever satisfy this mode.
+    pub fn admits(&self, arguments: &[Instantiation]) -> bool {
+        self.params
+            .iter()
+            .zip(arguments)
+            .all(|(param, argument)| param.admits(*argument))
+    }
+}
+
+impl Display for Mode {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "(")?;
+        for (i, param) in self.params.iter().enumerate() {
+            if i != 0 {
+                write!(f, ", ")?;
+            }
+            param.fmt(f)?;
+        }
+        write!(f, ")")
+    }
+}
+
+/// The number of answers a predicate claims to produce, when called in one of its modes.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub(crate) enum Determinism {
+    /// Exactly one answer.
+    Det,
+    /// At most one answer.
+    Semidet,
+    /// At least one answer.
+    Multi,
+    /// Any number of answers.
+    Nondet,
+}
+
+impl Determinism {
+    pub fn new(pair: crate::Pair) -> Self {
+        assert_eq!(pair.as_rule(), Rule::determinism);
+        match pair.as_str() {
+            "det" => Self::Det,
+            "semidet" => Self::Semidet,
+            "multi" => Self::Multi,
+            "nondet" => Self::Nondet,
+            _ => unreachable!(),
+        }
+    }
+
+    /// The fewest answers allowed.
+    pub fn min(self) -> usize {
+        match self {
+            Self::Det | Self::Multi => 1,
+            Self::Semidet | Self::Nondet => 0,
+        }
+    }
+
+    /// The most answers allowed, if limited.
+    pub fn max(self) -> Option<usize> {
+        match self {
+            Self::Det | Self::Semidet => Some(1),
+            Self::Multi | Self::Nondet => None,
+        }
+    }
+}
+
+impl Display for Determinism {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::Det => write!(f, "det"),
+            Self::Semidet => write!(f, "semidet"),
+            Self::Multi => write!(f, "multi"),
+            Self::Nondet => write!(f, "nondet"),
+        }
+    }
+}
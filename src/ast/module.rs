@@ -1,5 +1,6 @@
 use super::*;
 use crate::parser::{Parser, Rule};
+use crate::LintLevel;
 use std::collections::HashMap;
 
 /// A module within a Lumber program.
@@ -12,62 +13,165 @@ pub(crate) struct Module {
 }
 
 impl Module {
-    pub fn new(source_str: &str, context: &mut Context) -> crate::Result<Self> {
-        let pairs = Parser::parse_module(source_str)?;
+    pub fn new(source: &Source, context: &mut Context) -> crate::Result<Self> {
+        let path = source.path.as_deref();
+        let recovered;
+        let pairs = match Parser::parse_module(&source.code, path) {
+            Ok(pairs) => pairs,
+            Err(..) => {
+                let (code, errors) = Parser::recover_module(&source.code, path);
+                for error in errors {
+                    context.error_syntax(error);
+                }
+                recovered = code;
+                Parser::parse_module(&recovered, path)?
+            }
+        };
         let pairs = just!(Rule::module, pairs).into_inner();
 
         let mut submodules = HashMap::new();
         let mut definitions = HashMap::<Handle, Definition>::new();
+        let mut module_doc = vec![];
+        let mut doc = vec![];
 
         for pair in pairs {
             match pair.as_rule() {
+                Rule::module_doc => module_doc.push(doc_line(pair)),
+                Rule::doc_comment => doc.push(doc_line(pair)),
                 Rule::directive => {
+                    context.locate(pair.as_span());
+                    let doc = std::mem::take(&mut doc).join("\n");
                     let pair = just!(Rule::instruction, pair.into_inner());
                     let pair = just!(pair.into_inner());
                     match pair.as_rule() {
                         Rule::mod_ => {
                             let atom = just!(Rule::atom, pair.into_inner());
                             let atom = Atom::new(atom);
-                            if let Some(module) = context.add_module(atom.clone())? {
+                            if let Some(module) = context.add_module(atom.clone(), &doc)? {
                                 submodules.insert(atom, module);
                             }
                         }
                         Rule::pub_ => {
-                            let handle = just!(Rule::handle, pair.into_inner());
-                            let handle = Handle::new(handle, context);
-                            context.declare_export(handle);
+                            let pair = just!(pair.into_inner());
+                            match pair.as_rule() {
+                                Rule::handle => {
+                                    let handle = Handle::new(pair, context);
+                                    context.declare_doc(&handle, &doc);
+                                    context.declare_export(handle);
+                                }
+                                Rule::atom => context.declare_type_export(Atom::new(pair)),
+                                _ => unreachable!(),
+                            }
                         }
                         Rule::use_ => {
                             let handle = just!(Rule::multi_handle, pair.into_inner());
                             match Alias::unpack_multiple(handle, context) {
                                 Ok(unpacked) => {
                                     for Alias { input, output } in unpacked {
+                                        context.import_operators(&input.module(), Some(&input));
                                         context.declare_alias(output.clone(), input.clone());
                                     }
                                 }
-                                Err(module) => context.import_glob(module),
+                                Err(module) => {
+                                    context.import_operators(&module, None);
+                                    context.import_glob(module);
+                                }
                             }
                         }
                         Rule::mut_ => {
                             let handle = just!(Rule::handle, pair.into_inner());
                             let handle = Handle::new(handle, context);
+                            context.declare_doc(&handle, &doc);
                             context.declare_mutable(handle);
                         }
                         Rule::inc => {
                             let handle = just!(Rule::handle, pair.into_inner());
                             let handle = Handle::new(handle, context);
+                            context.declare_doc(&handle, &doc);
                             context.declare_incomplete(handle);
                         }
                         Rule::nat => {
                             let pair = just!(Rule::handle, pair.into_inner());
                             let handle = Handle::new(pair, context);
+                            context.declare_doc(&handle, &doc);
                             context.declare_native(handle.clone());
                         }
+                        Rule::type_ => {
+                            let mut pairs = pair.into_inner();
+                            let name = pairs.next().unwrap();
+                            let pattern = Pattern::new(pairs.next().unwrap(), context);
+                            match name.as_rule() {
+                                Rule::atom => {
+                                    if let Some(ty) = Type::new(pattern, context) {
+                                        context.declare_type(Atom::new(name), ty);
+                                    }
+                                }
+                                Rule::handle => {
+                                    let handle = Handle::new(name, context);
+                                    let types = match pattern {
+                                        Pattern::List(types, None) => types
+                                            .into_iter()
+                                            .map(|ty| Type::new(ty, context))
+                                            .collect::<Option<Vec<_>>>(),
+                                        _ => {
+                                            context.error_invalid_type(
+                                                "a signature must be a list of types",
+                                            );
+                                            None
+                                        }
+                                    };
+                                    if let Some(types) = types {
+                                        context.declare_signature(handle, types);
+                                    }
+                                }
+                                _ => unreachable!(),
+                            }
+                        }
+                        Rule::mode => {
+                            let head = just!(Rule::head, pair.into_inner());
+                            if let Some((handle, mode)) = Mode::new(head, context) {
+                                context.declare_mode(handle, mode);
+                            }
+                        }
+                        Rule::det => {
+                            let mut pairs = pair.into_inner();
+                            let handle = Handle::new(pairs.next().unwrap(), context);
+                            let determinism =
+                                pairs.next().map_or(Determinism::Det, Determinism::new);
+                            context.declare_determinism(handle, determinism);
+                        }
+                        Rule::op => {
+                            if let Some(operator) = OperatorDefinition::new(pair, context) {
+                                context.declare_operator(operator);
+                            }
+                        }
+                        Rule::lint => {
+                            let mut pairs = pair.into_inner();
+                            let level = match pairs.next().unwrap().as_str() {
+                                "allow" => LintLevel::Allow,
+                                "warn" => LintLevel::Warn,
+                                "deny" => LintLevel::Deny,
+                                _ => unreachable!(),
+                            };
+                            let name = Atom::new(just!(Rule::atom, pairs));
+                            context.declare_lint(&name, level);
+                        }
+                        Rule::test => {
+                            let pair = just!(Rule::atom, pair.into_inner());
+                            let scope = context.current_scope.join(Atom::new(pair.clone()));
+                            let mut handle = Handle::from_parts(scope, Arity::default());
+                            handle.span = context.span(pair.as_span());
+                            context.declare_doc(&handle, &doc);
+                            context.declare_test(handle);
+                        }
                         _ => unreachable!(),
                     }
                 }
                 Rule::clause => {
+                    context.locate(pair.as_span());
                     context.reset_environment();
+                    context.declare_variables(pair.clone());
+                    let doc = std::mem::take(&mut doc).join("\n");
                     let pair = just!(pair.into_inner());
                     let (head, body) = match pair.as_rule() {
                         Rule::fact => {
@@ -115,16 +219,25 @@ impl Module {
                         _ => unreachable!(),
                     };
                     body.check_variables(&head, context);
+                    context.declare_doc(head.as_ref(), &doc);
                     context.declare_predicate(head.as_ref().clone());
                     definitions
                         .entry(head.as_ref().clone())
                         .or_default()
                         .insert(head, body);
+                    for (head, body) in context.take_lambdas() {
+                        context.declare_predicate(head.as_ref().clone());
+                        definitions
+                            .entry(head.as_ref().clone())
+                            .or_default()
+                            .insert(head, body);
+                    }
                 }
                 Rule::EOI => {}
                 _ => unreachable!(),
             }
         }
+        context.declare_module_doc(&module_doc.join("\n"));
 
         Ok(Self {
             submodules,
@@ -137,11 +250,9 @@ impl Module {
             context.resolve_scopes(module, name.clone());
         }
         for definition in self.definitions.values_mut() {
-            for body in definition.bodies_mut() {
-                for handle in body.handles_mut() {
-                    if let Some(resolved) = context.resolve_handle(handle) {
-                        *handle = resolved.clone();
-                    }
+            for handle in definition.handles_mut() {
+                if let Some(resolved) = context.resolve_handle(handle) {
+                    *handle = resolved.clone();
                 }
             }
         }
@@ -157,6 +268,15 @@ impl Module {
             .collect();
     }
 
+    /// All definitions in this module and its submodules.
+    pub fn definitions(&self) -> Box<dyn Iterator<Item = (&Handle, &Definition)> + '_> {
+        Box::new(
+            self.definitions
+                .iter()
+                .chain(self.submodules.values().flat_map(Self::definitions)),
+        )
+    }
+
     pub fn into_definitions(self) -> Box<dyn Iterator<Item = (Handle, Definition)>> {
         Box::new(
             self.definitions.into_iter().chain(
@@ -168,3 +288,9 @@ impl Module {
         )
     }
 }
+
+/// The text of a doc comment, without the space which usually follows its `///` or `//!`.
+fn doc_line<'i>(pair: crate::Pair<'i>) -> &'i str {
+    let text = just!(Rule::doc_text, pair.into_inner()).as_str().trim_end();
+    text.strip_prefix(' ').unwrap_or(text)
+}
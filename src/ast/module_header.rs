@@ -1,5 +1,7 @@
 use super::*;
+use crate::{CompileError, Lint, LintLevel};
 use std::collections::{HashMap, HashSet};
+use std::rc::Rc;
 
 /// Lists the predicates and exports of the module, but does not bind them to any
 /// actual definitions.
@@ -7,6 +9,10 @@ use std::collections::{HashMap, HashSet};
 pub(crate) struct ModuleHeader {
     /// The path to this module.
     pub scope: Scope,
+    /// The source code of this module, once it has been read.
+    pub source: Option<Rc<Source>>,
+    /// The `:- mod` directive which declared this module.
+    pub span: SourceSpan,
     /// Modules from which imports are globbed.
     pub globs: HashSet<Scope>,
     /// Native functions bound to this module.
@@ -21,12 +27,38 @@ pub(crate) struct ModuleHeader {
     pub definitions: HashSet<Handle>,
     /// Imported predicates, and their alises.
     pub aliases: HashMap<Handle, Handle>,
+    /// Operators declared in or imported into this module, by symbol.
+    pub operators: HashMap<String, OperatorDefinition>,
+    /// Types declared in this module. A type declared multiple times accepts any of the
+    /// declared alternatives.
+    pub types: HashMap<Atom, Vec<Type>>,
+    /// Publicly available types.
+    pub type_exports: HashSet<Atom>,
+    /// Imported types, and the modules they are imported from.
+    pub type_aliases: HashMap<Atom, Scope>,
+    /// The declared types of the arguments of predicates in this module.
+    pub signatures: HashMap<Handle, Vec<Type>>,
+    /// The modes in which predicates in this module may be called.
+    pub modes: HashMap<Handle, Vec<Mode>>,
+    /// The declared determinism of predicates in this module.
+    pub determinisms: HashMap<Handle, Determinism>,
+    /// The levels of lints set in this module, which also apply to its submodules.
+    pub lints: HashMap<Lint, LintLevel>,
+    /// Predicates which are run as tests.
+    pub tests: HashSet<Handle>,
+    /// The documentation of this module, from its `//!` comments and the `///` comments on the
+    /// `:- mod` directive which declared it.
+    pub doc: String,
+    /// The documentation of predicates in this module, from their `///` comments.
+    pub docs: HashMap<Handle, String>,
 }
 
 impl ModuleHeader {
     pub fn new(scope: Scope) -> Self {
         Self {
             scope,
+            source: None,
+            span: SourceSpan::default(),
             globs: Default::default(),
             natives: Default::default(),
             exports: Default::default(),
@@ -34,6 +66,17 @@ impl ModuleHeader {
             incompletes: Default::default(),
             definitions: Default::default(),
             aliases: Default::default(),
+            operators: Default::default(),
+            types: Default::default(),
+            type_exports: Default::default(),
+            type_aliases: Default::default(),
+            signatures: Default::default(),
+            modes: Default::default(),
+            determinisms: Default::default(),
+            lints: Default::default(),
+            tests: Default::default(),
+            doc: String::new(),
+            docs: Default::default(),
         }
     }
 
@@ -72,6 +115,103 @@ impl ModuleHeader {
             .map(|source| (alias, source))
     }
 
+    /// Adds an operator to this module, returning the conflicting operator if the symbol was
+    /// already used for a different predicate.
+    pub fn insert_operator(&mut self, operator: OperatorDefinition) -> Option<OperatorDefinition> {
+        match self.operators.get(&operator.symbol) {
+            Some(existing) if existing.handle == operator.handle => None,
+            Some(existing) => Some(existing.clone()),
+            None => {
+                self.operators.insert(operator.symbol.clone(), operator);
+                None
+            }
+        }
+    }
+
+    pub fn insert_type(&mut self, name: Atom, ty: Type) {
+        self.types.entry(name).or_default().push(ty);
+    }
+
+    pub fn insert_type_export(&mut self, name: Atom) -> Option<Atom> {
+        self.type_exports.replace(name)
+    }
+
+    pub fn insert_type_alias(&mut self, name: Atom, source: Scope) -> Option<Scope> {
+        self.type_aliases.insert(name, source)
+    }
+
+    /// Adds a predicate signature, returning the existing signature if there already was one.
+    pub fn insert_signature(&mut self, handle: Handle, types: Vec<Type>) -> Option<Vec<Type>> {
+        match self.signatures.get(&handle) {
+            Some(existing) => Some(existing.clone()),
+            None => {
+                self.signatures.insert(handle, types);
+                None
+            }
+        }
+    }
+
+    pub fn insert_mode(&mut self, handle: Handle, mode: Mode) {
+        self.modes.entry(handle).or_default().push(mode);
+    }
+
+    /// Sets the determinism of a predicate, returning the existing determinism if it was already
+    /// declared.
+    pub fn insert_determinism(
+        &mut self,
+        handle: Handle,
+        determinism: Determinism,
+    ) -> Option<Determinism> {
+        match self.determinisms.get(&handle) {
+            Some(existing) => Some(*existing),
+            None => {
+                self.determinisms.insert(handle, determinism);
+                None
+            }
+        }
+    }
+
+    pub fn insert_lint(&mut self, lint: Lint, level: LintLevel) {
+        self.lints.insert(lint, level);
+    }
+
+    pub fn insert_test(&mut self, handle: Handle) {
+        self.tests.insert(handle);
+    }
+
+    pub fn insert_module_doc(&mut self, doc: &str) {
+        push_paragraph(&mut self.doc, doc);
+    }
+
+    /// Adds documentation to a predicate. A predicate documented in more than one place has each
+    /// part of its documentation as a separate paragraph.
+    pub fn insert_doc(&mut self, handle: Handle, doc: &str) {
+        push_paragraph(self.docs.entry(handle).or_default(), doc);
+    }
+
+    /// Finds a type by name, as visible from within this module. The scope of the module in
+    /// which the type was declared is returned along with its alternatives.
+    pub fn resolve_type<'a>(
+        &'a self,
+        name: &Atom,
+        context: &'a Context,
+    ) -> Option<(&'a Scope, &'a Vec<Type>)> {
+        if let Some(types) = self.types.get(name) {
+            return Some((&self.scope, types));
+        }
+        let exported = |scope: &Scope| {
+            context
+                .modules
+                .get(scope)
+                .filter(|module| module.type_exports.contains(name))
+                .and_then(|module| Some((&module.scope, module.types.get(name)?)))
+        };
+        match self.type_aliases.get(name) {
+            Some(scope) => exported(scope),
+            None => self.globbed_modules().find_map(exported),
+        }
+    }
+
     pub fn resolve<'a>(
         &'a self,
         handle: &'a Handle,
@@ -79,10 +219,10 @@ impl ModuleHeader {
         context: &'a Context,
     ) -> crate::Result<&'a Handle> {
         match self.resolve_inner(handle, from_scope, context, &mut vec![])? {
-            None => Err(crate::Error::parse(&format!(
-                "Unresolved predicate {} in scope {}.",
-                handle, from_scope
-            ))),
+            None => Err(crate::Error::compile(CompileError::UnresolvedPredicate {
+                handle: handle.clone(),
+                scope: from_scope.to_string(),
+            })),
             Some(resolved) => Ok(resolved),
         }
     }
@@ -96,13 +236,9 @@ impl ModuleHeader {
     ) -> crate::Result<Option<&'a Handle>> {
         if path.contains(&handle) {
             path.push(handle);
-            return Err(crate::Error::parse(&format!(
-                "Alias loop detected: {}",
-                path.into_iter()
-                    .map(|handle| handle.to_string())
-                    .collect::<Vec<_>>()
-                    .join(" -> "),
-            )));
+            return Err(crate::Error::compile(CompileError::AliasLoop {
+                path: path.iter().map(|&handle| handle.clone()).collect(),
+            }));
         }
         path.push(handle);
         let resolved = if let Some(resolved) = self.definitions.get(handle) {
@@ -139,15 +275,10 @@ impl ModuleHeader {
                 &[] => return Ok(None),
                 &[handle] => handle,
                 _ => {
-                    return Err(crate::Error::parse(&format!(
-                        "Ambiguous reference {}. Could be referring to any of:\n{}",
-                        handle,
-                        candidates
-                            .iter()
-                            .map(|candidate| format!("\t{}", candidate))
-                            .collect::<Vec<_>>()
-                            .join("\n"),
-                    )))
+                    return Err(crate::Error::compile(CompileError::AmbiguousReference {
+                        handle: handle.clone(),
+                        candidates: candidates.iter().map(|&handle| handle.clone()).collect(),
+                    }))
                 }
             }
         };
@@ -155,10 +286,10 @@ impl ModuleHeader {
         if self.scope >= *from_scope || self.exports.contains(handle) {
             Ok(Some(resolved))
         } else {
-            Err(crate::Error::parse(&format!(
-                "Predicate {} is not visible from scope {}.",
-                handle, from_scope
-            )))
+            Err(crate::Error::compile(CompileError::InvisiblePredicate {
+                handle: handle.clone(),
+                scope: from_scope.to_string(),
+            }))
         }
     }
 
@@ -181,63 +312,78 @@ impl ModuleHeader {
         let mut errors = vec![];
         for module in &self.globs {
             if !context.modules.contains_key(module) {
-                errors.push(crate::Error::parse(&format!(
-                    "Unresolved module {} in glob import.",
-                    module,
-                )));
+                errors.push(crate::Error::compile(CompileError::UnresolvedGlob {
+                    module: module.to_string(),
+                }));
             }
         }
         for native in &self.natives {
             if !native_handles.contains(&native) {
-                errors.push(crate::Error::parse(&format!(
-                    "Native function {} is not bound.",
-                    native,
-                )));
+                errors.push(
+                    crate::Error::compile(CompileError::UnboundNative {
+                        handle: native.clone(),
+                    })
+                    .at(&native.span),
+                );
             }
             if self.definitions.contains(native) {
-                errors.push(crate::Error::parse(&format!(
-                    "Native function {} cannot also be implemented.",
-                    native,
-                )));
+                errors.push(
+                    crate::Error::compile(CompileError::ImplementedNative {
+                        handle: native.clone(),
+                    })
+                    .at(&native.span),
+                );
             } else if self.aliases.contains_key(native) {
-                errors.push(crate::Error::parse(&format!(
-                    "Native function {} cannot also be imported.",
-                    native,
-                )));
+                errors.push(
+                    crate::Error::compile(CompileError::ImportedNative {
+                        handle: native.clone(),
+                    })
+                    .at(&native.span),
+                );
             } else if self.mutables.contains(native) {
-                errors.push(crate::Error::parse(&format!(
-                    "Native function {} cannot be set as mutable.",
-                    native,
-                )));
+                errors.push(
+                    crate::Error::compile(CompileError::MutableNative {
+                        handle: native.clone(),
+                    })
+                    .at(&native.span),
+                );
             } else if self.incompletes.contains(native) {
-                errors.push(crate::Error::parse(&format!(
-                    "Native function {} cannot be set as incomplete.",
-                    native,
-                )));
+                errors.push(
+                    crate::Error::compile(CompileError::IncompleteNative {
+                        handle: native.clone(),
+                    })
+                    .at(&native.span),
+                );
             }
         }
         for export in &self.exports {
             if !self.resolve(export, &self.scope, context).is_ok() {
-                errors.push(crate::Error::parse(&format!(
-                    "Exported predicate {} cannot be found.",
-                    export.head(),
-                )));
+                errors.push(
+                    crate::Error::compile(CompileError::UnresolvedExport {
+                        handle: export.head(),
+                    })
+                    .at(&export.span),
+                );
             }
         }
         for mutable in &self.mutables {
             if self.aliases.contains_key(mutable) {
-                errors.push(crate::Error::parse(&format!(
-                    "Cannot set alias {} as mutable.",
-                    mutable.head(),
-                )));
+                errors.push(
+                    crate::Error::compile(CompileError::MutableAlias {
+                        handle: mutable.head(),
+                    })
+                    .at(&mutable.span),
+                );
             }
         }
         for incomplete in &self.incompletes {
             if self.aliases.contains_key(incomplete) {
-                errors.push(crate::Error::parse(&format!(
-                    "Cannot set alias {} as incomplete.",
-                    incomplete.head(),
-                )));
+                errors.push(
+                    crate::Error::compile(CompileError::IncompleteAlias {
+                        handle: incomplete.head(),
+                    })
+                    .at(&incomplete.span),
+                );
             }
         }
         for definition in &self.definitions {
@@ -248,16 +394,127 @@ impl ModuleHeader {
                     .map(|module| module.incompletes.contains(value))
                     .unwrap_or(false);
                 if !incomplete {
-                    let mut message = format!(
-                        "Definition of {} conflicts with imported {}",
-                        definition.head(),
-                        value,
+                    errors.push(
+                        crate::Error::compile(CompileError::ConflictingDefinition {
+                            handle: definition.head(),
+                            import: value.clone(),
+                            alias: if key.like(value) {
+                                None
+                            } else {
+                                Some(key.head())
+                            },
+                        })
+                        .at(&definition.span),
+                    );
+                }
+            }
+        }
+        for (name, scope) in &self.type_aliases {
+            match context.modules.get(scope) {
+                Some(module) if module.type_exports.contains(name) => {}
+                Some(module) if module.types.contains_key(name) => {
+                    errors.push(crate::Error::compile(CompileError::InvisibleType {
+                        name: name.to_string(),
+                        scope: self.scope.to_string(),
+                    }));
+                }
+                _ => errors.push(crate::Error::compile(
+                    CompileError::UnresolvedImportedType {
+                        name: name.to_string(),
+                        module: scope.to_string(),
+                    },
+                )),
+            }
+        }
+        for export in &self.type_exports {
+            if !self.types.contains_key(export) {
+                errors.push(crate::Error::compile(
+                    CompileError::UnresolvedExportedType {
+                        name: export.to_string(),
+                    },
+                ));
+            }
+        }
+        for (handle, types) in &self.signatures {
+            let len =
+                handle.arity.len + handle.arity.fields.iter().map(|(_, len)| len).sum::<u32>();
+            if types.len() != len as usize {
+                errors.push(
+                    crate::Error::compile(CompileError::SignatureArityMismatch {
+                        handle: handle.head(),
+                        types: types.len(),
+                        arguments: len as usize,
+                    })
+                    .at(&handle.span),
+                );
+            }
+            if !self.definitions.contains(handle) && !self.natives.contains(handle) {
+                errors.push(
+                    crate::Error::compile(CompileError::UndefinedSignature {
+                        handle: handle.head(),
+                    })
+                    .at(&handle.span),
+                );
+            }
+        }
+        for test in &self.tests {
+            if !self.definitions.contains(test) {
+                errors.push(
+                    crate::Error::compile(CompileError::UndefinedTest {
+                        handle: test.head(),
+                    })
+                    .at(&test.span),
+                );
+            }
+            if let Some(mutable) = self.mutables.get(test) {
+                errors.push(
+                    crate::Error::compile(CompileError::MutableTest {
+                        handle: mutable.head(),
+                    })
+                    .at(&mutable.span),
+                );
+            }
+        }
+        for handle in self.modes.keys().chain(self.determinisms.keys()) {
+            if !self.definitions.contains(handle) && !self.natives.contains(handle) {
+                errors.push(
+                    crate::Error::compile(CompileError::UndefinedMode {
+                        handle: handle.head(),
+                    })
+                    .at(&handle.span),
+                );
+            }
+        }
+        let references = self
+            .types
+            .values()
+            .flatten()
+            .chain(self.signatures.values().flatten())
+            .flat_map(Type::references)
+            .collect::<HashSet<_>>();
+        for name in references {
+            if self.resolve_type(name, context).is_none() {
+                errors.push(crate::Error::compile(CompileError::UnresolvedType {
+                    name: name.to_string(),
+                    scope: self.scope.to_string(),
+                }));
+            }
+        }
+        for glob in &self.globs {
+            let module = match context.modules.get(glob) {
+                Some(module) => module,
+                None => continue,
+            };
+            for handle in self.definitions.iter().chain(self.aliases.keys()) {
+                let shadowed = handle.relocate(glob);
+                if module.exports.contains(&shadowed) && !module.incompletes.contains(&shadowed) {
+                    errors.push(
+                        crate::Error::compile(CompileError::ShadowedGlob {
+                            handle: handle.head(),
+                            module: glob.to_string(),
+                        })
+                        .at(&handle.span),
                     );
-                    if !key.like(value) {
-                        message.push_str(&format!(" (aliased as {})", key.head()));
-                    }
-                    message.push('.');
-                    errors.push(crate::Error::parse(&message));
                 }
             }
         }
@@ -269,17 +526,23 @@ impl ModuleHeader {
             match alias.library().first() {
                 Some(library) => match context.libraries.get(&library) {
                     None => {
-                        errors.push(crate::Error::parse(&format!(
-                            "Referencing predicate {} from unlinked library {}.",
-                            alias, library,
-                        )));
+                        errors.push(
+                            crate::Error::compile(CompileError::UnlinkedLibrary {
+                                handle: alias.clone(),
+                                library: library.to_string(),
+                            })
+                            .at(&alias.span),
+                        );
                     }
                     Some(lib) if lib.exports(alias) => continue,
                     Some(..) => {
-                        errors.push(crate::Error::parse(&format!(
-                            "No predicate {} is exported by the library {}.",
-                            alias, library,
-                        )));
+                        errors.push(
+                            crate::Error::compile(CompileError::UnexportedLibraryPredicate {
+                                handle: alias.clone(),
+                                library: library.to_string(),
+                            })
+                            .at(&alias.span),
+                        );
                     }
                 },
                 None => {
@@ -289,7 +552,7 @@ impl ModuleHeader {
                         context,
                     ) {
                         Ok(..) => {}
-                        Err(error) => errors.push(error),
+                        Err(error) => errors.push(error.at(&alias.span)),
                     }
                     let aliases = self
                         .aliases
@@ -299,15 +562,13 @@ impl ModuleHeader {
                         .collect::<Vec<_>>();
                     if aliases.len() != 1 {
                         reported.insert(alias.clone());
-                        errors.push(crate::Error::parse(&format!(
-                            "{} is aliased multiple times, as:\n\t{}",
-                            alias,
-                            aliases
-                                .into_iter()
-                                .map(|alias| format!("\t{}", alias))
-                                .collect::<Vec<_>>()
-                                .join("\n"),
-                        )));
+                        errors.push(
+                            crate::Error::compile(CompileError::MultipleAliases {
+                                handle: alias.clone(),
+                                aliases: aliases.into_iter().cloned().collect(),
+                            })
+                            .at(&alias.span),
+                        );
                     }
                 }
             }
@@ -315,3 +576,13 @@ impl ModuleHeader {
         errors
     }
 }
+
+fn push_paragraph(doc: &mut String, paragraph: &str) {
+    if paragraph.is_empty() {
+        return;
+    }
+    if !doc.is_empty() {
+        doc.push_str("\n\n");
+    }
+    doc.push_str(paragraph);
+}
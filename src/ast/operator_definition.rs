@@ -0,0 +1,53 @@
+use super::*;
+use crate::parser::Rule;
+use pest::prec_climber::Assoc;
+
+/// A user-defined operator, declared using the `:- op` directive.
+#[derive(Clone, Debug)]
+pub(crate) struct OperatorDefinition {
+    /// The symbol used to write this operator.
+    pub symbol: String,
+    /// The precedence of the operator. This is on the same scale as the built-in operators,
+    /// from `||` at 1 up to `**` at 10, where higher precedence binds more tightly.
+    pub precedence: u32,
+    /// The associativity of the operator.
+    pub assoc: Assoc,
+    /// The predicate this operator stands for, which takes the two operands and the output.
+    pub handle: Handle,
+}
+
+impl OperatorDefinition {
+    pub fn new(pair: crate::Pair, context: &mut Context) -> Option<Self> {
+        assert_eq!(pair.as_rule(), Rule::op);
+        let mut pairs = pair.into_inner();
+        let symbol = pairs.next().unwrap().as_str().to_owned();
+        let precedence = pairs.next().unwrap().as_str();
+        let precedence = match precedence.parse() {
+            Ok(precedence) if (1..=10).contains(&precedence) => precedence,
+            _ => {
+                context.error_invalid_operator_precedence(&symbol, precedence);
+                return None;
+            }
+        };
+        let assoc = match pairs.next().unwrap().as_str() {
+            "left" => Assoc::Left,
+            "right" => Assoc::Right,
+            _ => unreachable!(),
+        };
+        let handle = Handle::new(pairs.next().unwrap(), context);
+        if handle.arity != Arity::new_len(3) {
+            context.error_invalid_operator_arity(&symbol, &handle);
+            return None;
+        }
+        if is_builtin_operator(&symbol) {
+            context.error_builtin_operator(&symbol);
+            return None;
+        }
+        Some(Self {
+            symbol,
+            precedence,
+            assoc,
+            handle,
+        })
+    }
+}
@@ -22,6 +22,10 @@ pub(crate) enum Pattern {
     Set(Vec<Pattern>, Option<Box<Pattern>>),
     /// A record, containing a set of fields.
     Record(Fields, Option<Box<Pattern>>),
+    /// A map, containing a set of entries with ground keys.
+    Map(Entries, Option<Box<Pattern>>),
+    /// An anonymous predicate (unifies only with the same lambda, having captured the same values).
+    Lambda(Box<Lambda>),
     /// A wildcard (unifies with anything).
     Wildcard,
     /// An unknown Rust value.
@@ -51,7 +55,9 @@ impl PartialEq for Pattern {
             (Pattern::Record(lhs, ltail), Pattern::Record(rhs, rtail)) => {
                 lhs == rhs && ltail == rtail
             }
+            (Pattern::Map(lhs, ltail), Pattern::Map(rhs, rtail)) => lhs == rhs && ltail == rtail,
             (Pattern::Any(lhs), Pattern::Any(rhs)) => Rc::ptr_eq(lhs, rhs),
+            (Pattern::Lambda(lhs), Pattern::Lambda(rhs)) => lhs == rhs,
             (Pattern::Wildcard, Pattern::Wildcard) => true,
             (Pattern::Bound(lhs), Pattern::Bound(rhs)) => lhs.eq(rhs),
             (Pattern::Unbound(lhs), Pattern::Unbound(rhs)) => lhs.eq(rhs),
@@ -70,7 +76,9 @@ impl Hash for Pattern {
             Pattern::Set(value, tail) => ("set", value, tail).hash(hasher),
             Pattern::List(value, tail) => ("list", value, tail).hash(hasher),
             Pattern::Record(value, tail) => ("record", value, tail).hash(hasher),
+            Pattern::Map(value, tail) => ("map", value, tail).hash(hasher),
             Pattern::Any(value) => ("any", Rc::as_ptr(value)).hash(hasher),
+            Pattern::Lambda(value) => ("lambda", value).hash(hasher),
             Pattern::Wildcard => "wildcard".hash(hasher),
             Pattern::Bound(pattern) => ("bound", pattern).hash(hasher),
             Pattern::Unbound(pattern) => ("unbound", pattern).hash(hasher),
@@ -102,8 +110,16 @@ impl Pattern {
                     .unwrap_or(Self::Wildcard),
             )),
             Rule::struct_ => Self::Struct(Struct::new(pair, context)),
-            Rule::literal => Self::Literal(Literal::new(pair)),
-            Rule::variable => Self::Variable(context.get_variable(pair.as_str())),
+            // If the lambda's body is malformed, the error has already been reported, so this
+            // placeholder will never be used.
+            Rule::lambda => Lambda::new(pair, context)
+                .map(|lambda| Self::Lambda(Box::new(lambda)))
+                .unwrap_or(Self::Wildcard),
+            // As with lambdas, an invalid literal has already been reported.
+            Rule::literal => Literal::new(pair, context)
+                .map(Self::Literal)
+                .unwrap_or(Self::Wildcard),
+            Rule::variable => Self::Variable(context.get_variable(pair.as_str(), pair.as_span())),
             Rule::list => {
                 let mut pairs = pair.into_inner();
                 let head = match pairs.next() {
@@ -151,6 +167,18 @@ impl Pattern {
                 });
                 Self::Record(head, tail)
             }
+            Rule::map => {
+                let mut pairs = pair.into_inner();
+                let head = match pairs.next() {
+                    Some(head) => Entries::new(head, context),
+                    None => return Self::Map(Entries::default(), None),
+                };
+                let tail = pairs.next().map(|pair| match pair.into_inner().next() {
+                    Some(pair) => Box::new(Pattern::new_inner(pair, context)),
+                    None => Box::new(Pattern::Wildcard),
+                });
+                Self::Map(head, tail)
+            }
             Rule::wildcard => Self::Wildcard,
             _ => unreachable!(),
         }
@@ -173,15 +201,137 @@ impl Pattern {
                     .flat_map(|(_, pattern)| pattern.identifiers())
                     .chain(tail.iter().flat_map(|pattern| pattern.identifiers())),
             ),
+            Self::Map(head, tail) => Box::new(
+                head.iter()
+                    .flat_map(|(_, pattern)| pattern.identifiers())
+                    .chain(tail.iter().flat_map(|pattern| pattern.identifiers())),
+            ),
             #[cfg(feature = "builtin-sets")]
             Self::Set(head, tail) => Box::new(
                 head.iter()
                     .flat_map(|pattern| pattern.identifiers())
                     .chain(tail.iter().flat_map(|pattern| pattern.identifiers())),
             ),
+            Self::Lambda(lambda) => Box::new(lambda.identifiers()),
             // TODO: give these unique names, and allow user to specify wildcard names.
             Self::Wildcard => Box::new(std::iter::once(Identifier::wildcard("_".to_owned()))),
             _ => Box::new(std::iter::empty()),
         }
     }
+
+    /// Handles referenced by this pattern, which are only found within lambdas.
+    pub fn handles_mut<'a>(&'a mut self) -> Box<dyn Iterator<Item = &'a mut Handle> + 'a> {
+        match self {
+            Self::Struct(s) => Box::new(
+                s.contents
+                    .iter_mut()
+                    .flat_map(|pattern| pattern.handles_mut()),
+            ),
+            Self::Unbound(inner) | Self::Bound(inner) => inner.handles_mut(),
+            Self::List(head, tail) => Box::new(
+                head.iter_mut()
+                    .flat_map(|pattern| pattern.handles_mut())
+                    .chain(tail.iter_mut().flat_map(|pattern| pattern.handles_mut())),
+            ),
+            Self::Record(head, tail) => Box::new(
+                head.iter_mut()
+                    .flat_map(|(_, pattern)| pattern.handles_mut())
+                    .chain(tail.iter_mut().flat_map(|pattern| pattern.handles_mut())),
+            ),
+            Self::Map(head, tail) => Box::new(
+                head.iter_mut()
+                    .flat_map(|(_, pattern)| pattern.handles_mut())
+                    .chain(tail.iter_mut().flat_map(|pattern| pattern.handles_mut())),
+            ),
+            #[cfg(feature = "builtin-sets")]
+            Self::Set(head, tail) => Box::new(
+                head.iter_mut()
+                    .flat_map(|pattern| pattern.handles_mut())
+                    .chain(tail.iter_mut().flat_map(|pattern| pattern.handles_mut())),
+            ),
+            Self::Lambda(lambda) => lambda.handles_mut(),
+            _ => Box::new(std::iter::empty()),
+        }
+    }
+
+    /// The variables of this pattern, not including wildcards.
+    pub fn variables_mut<'a>(&'a mut self) -> Box<dyn Iterator<Item = &'a mut Identifier> + 'a> {
+        match self {
+            Self::Struct(s) => Box::new(
+                s.contents
+                    .iter_mut()
+                    .flat_map(|pattern| pattern.variables_mut()),
+            ),
+            Self::Unbound(inner) | Self::Bound(inner) => inner.variables_mut(),
+            Self::Variable(identifier) => Box::new(std::iter::once(identifier)),
+            Self::List(head, tail) => Box::new(
+                head.iter_mut()
+                    .flat_map(|pattern| pattern.variables_mut())
+                    .chain(tail.iter_mut().flat_map(|pattern| pattern.variables_mut())),
+            ),
+            Self::Record(head, tail) => Box::new(
+                head.iter_mut()
+                    .flat_map(|(_, pattern)| pattern.variables_mut())
+                    .chain(tail.iter_mut().flat_map(|pattern| pattern.variables_mut())),
+            ),
+            Self::Map(head, tail) => Box::new(
+                head.iter_mut()
+                    .flat_map(|(_, pattern)| pattern.variables_mut())
+                    .chain(tail.iter_mut().flat_map(|pattern| pattern.variables_mut())),
+            ),
+            #[cfg(feature = "builtin-sets")]
+            Self::Set(head, tail) => Box::new(
+                head.iter_mut()
+                    .flat_map(|pattern| pattern.variables_mut())
+                    .chain(tail.iter_mut().flat_map(|pattern| pattern.variables_mut())),
+            ),
+            Self::Lambda(lambda) => Box::new(
+                lambda
+                    .captures
+                    .iter_mut()
+                    .flat_map(|(_, pattern)| pattern.variables_mut()),
+            ),
+            _ => Box::new(std::iter::empty()),
+        }
+    }
+
+    /// Whether this pattern might unify with another. This is a conservative check, used only
+    /// to find clauses that can never match, so it returns `true` whenever it is not certain.
+    pub fn may_unify(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Bound(lhs), rhs) | (Self::Unbound(lhs), rhs) => lhs.may_unify(rhs),
+            (lhs, Self::Bound(rhs)) | (lhs, Self::Unbound(rhs)) => lhs.may_unify(rhs),
+            (Self::Literal(lhs), Self::Literal(rhs)) => {
+                std::mem::discriminant(lhs) != std::mem::discriminant(rhs) || lhs == rhs
+            }
+            (Self::Struct(lhs), Self::Struct(rhs)) => {
+                lhs.name == rhs.name
+                    && match (&lhs.contents, &rhs.contents) {
+                        (Some(lhs), Some(rhs)) => lhs.may_unify(rhs),
+                        (None, None) => true,
+                        _ => false,
+                    }
+            }
+            (Self::List(lhs, ltail), Self::List(rhs, rtail)) => {
+                let lengths = match (ltail, rtail) {
+                    (None, None) => lhs.len() == rhs.len(),
+                    (Some(..), None) => lhs.len() <= rhs.len(),
+                    (None, Some(..)) => lhs.len() >= rhs.len(),
+                    (Some(..), Some(..)) => true,
+                };
+                lengths
+                    && lhs
+                        .iter()
+                        .zip(rhs.iter())
+                        .all(|(lhs, rhs)| lhs.may_unify(rhs))
+            }
+            (Self::Struct(..), Self::Literal(..))
+            | (Self::Struct(..), Self::List(..))
+            | (Self::Literal(..), Self::Struct(..))
+            | (Self::Literal(..), Self::List(..))
+            | (Self::List(..), Self::Struct(..))
+            | (Self::List(..), Self::Literal(..)) => false,
+            _ => true,
+        }
+    }
 }
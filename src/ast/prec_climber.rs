@@ -70,6 +70,16 @@ impl<'s> PrecClimber<'s> {
         PrecClimber { ops }
     }
 
+    /// Adds an operator with an explicit precedence, such as those declared by the user.
+    pub fn with_operator(mut self, symbol: &'s str, prec: u32, assoc: Assoc) -> Self {
+        self.ops.insert(symbol, (prec, assoc));
+        self
+    }
+
+    pub fn contains(&self, symbol: &str) -> bool {
+        self.ops.contains_key(symbol)
+    }
+
     pub fn climb<'i, R, P, F, G, T>(&self, mut pairs: P, mut primary: F, mut infix: G) -> T
     where
         R: RuleType,
@@ -0,0 +1,563 @@
+//! Prints values and the internal representation of a program as Lumber source code.
+//!
+//! Everything is first described as a [`Doc`][], which is then laid out either entirely on one
+//! line, or else broken across lines to fit within a given width.
+use super::*;
+use std::collections::HashSet;
+use std::fmt::{self, Display, Formatter};
+
+/// The width which the alternate (`{:#}`) form of a printed value or program is laid out to fit.
+pub(crate) const WIDTH: usize = 80;
+
+const INDENT: usize = 4;
+
+/// A description of the layouts in which some source code may be printed.
+#[derive(Clone, Debug)]
+pub(crate) enum Doc {
+    /// Text which is always printed as is.
+    Text(String),
+    /// The name of a variable. Variables introduced by the compiler have names which cannot be
+    /// written, so they are renamed by [`Doc::name_variables`][] before being printed.
+    Variable(String),
+    /// A line break, if its group is broken, or else the given text.
+    Line(&'static str),
+    /// Text which is only printed if its group is broken, such as a trailing comma.
+    Broken(&'static str),
+    /// A document whose line breaks are indented one level further.
+    Nest(Box<Doc>),
+    /// A document which is printed on one line if it fits, or else has its line breaks broken.
+    /// Groups within it are then each laid out on their own.
+    Group(Box<Doc>),
+    /// Documents printed one after the other.
+    Concat(Vec<Doc>),
+}
+
+impl From<&str> for Doc {
+    fn from(text: &str) -> Self {
+        Self::Text(text.to_owned())
+    }
+}
+
+impl From<String> for Doc {
+    fn from(text: String) -> Self {
+        Self::Text(text)
+    }
+}
+
+impl Doc {
+    fn nest(doc: Doc) -> Self {
+        Self::Nest(Box::new(doc))
+    }
+
+    fn group(doc: Doc) -> Self {
+        Self::Group(Box::new(doc))
+    }
+
+    fn is_empty(&self) -> bool {
+        match self {
+            Self::Text(text) => text.is_empty(),
+            Self::Concat(docs) => docs.iter().all(Self::is_empty),
+            _ => false,
+        }
+    }
+
+    /// Renames the variables introduced by the compiler, which are named `#N`, to `TmpN`. If this
+    /// document already has a variable by that name, underscores are added until it does not.
+    fn name_variables(&mut self) {
+        let mut names = HashSet::new();
+        self.visit_variables(&mut |name| {
+            names.insert(name.clone());
+        });
+        self.visit_variables(&mut |name| {
+            if let Some(index) = name.strip_prefix('#') {
+                let mut renamed = format!("Tmp{}", index);
+                while names.contains(&renamed) {
+                    renamed.push('_');
+                }
+                *name = renamed;
+            }
+        });
+    }
+
+    /// Replaces each variable for which a replacement is given.
+    fn replace_variables<F: FnMut(&str) -> Option<Doc>>(&mut self, replace: &mut F) {
+        match self {
+            Self::Variable(name) => {
+                if let Some(doc) = replace(name) {
+                    *self = doc;
+                }
+            }
+            Self::Nest(doc) | Self::Group(doc) => doc.replace_variables(replace),
+            Self::Concat(docs) => {
+                for doc in docs {
+                    doc.replace_variables(replace);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn visit_variables<F: FnMut(&mut String)>(&mut self, visit: &mut F) {
+        match self {
+            Self::Variable(name) => visit(name),
+            Self::Nest(doc) | Self::Group(doc) => doc.visit_variables(visit),
+            Self::Concat(docs) => {
+                for doc in docs {
+                    doc.visit_variables(visit);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Lays out this document. With no width, it is printed entirely on one line.
+    pub fn render(&self, width: Option<usize>) -> String {
+        let mut output = String::new();
+        let mut column = 0;
+        let flat = match width {
+            Some(width) => fits(width as isize, self, &[]),
+            None => true,
+        };
+        let mut stack = vec![(0, flat, self)];
+        while let Some((indent, flat, doc)) = stack.pop() {
+            match doc {
+                Self::Text(text) | Self::Variable(text) => {
+                    output.push_str(text);
+                    column += text.chars().count();
+                }
+                Self::Line(text) if flat => {
+                    output.push_str(text);
+                    column += text.chars().count();
+                }
+                Self::Line(..) => {
+                    output.push('\n');
+                    output.push_str(&" ".repeat(indent));
+                    column = indent;
+                }
+                Self::Broken(..) if flat => {}
+                Self::Broken(text) => {
+                    output.push_str(text);
+                    column += text.chars().count();
+                }
+                Self::Nest(doc) => stack.push((indent + INDENT, flat, doc)),
+                Self::Group(doc) => {
+                    let remaining = width.unwrap_or(0) as isize - column as isize;
+                    let flat = flat || fits(remaining, doc, &stack);
+                    stack.push((indent, flat, doc));
+                }
+                Self::Concat(docs) => {
+                    stack.extend(docs.iter().rev().map(|doc| (indent, flat, doc)))
+                }
+            }
+        }
+        output
+    }
+}
+
+/// Whether a document fits on one line in the remaining width, along with whatever follows it up
+/// to the next line break.
+fn fits(mut remaining: isize, doc: &Doc, rest: &[(usize, bool, &Doc)]) -> bool {
+    let mut rest = rest.iter().rev().map(|&(_, flat, doc)| (flat, doc));
+    let mut stack = vec![(true, doc)];
+    while remaining >= 0 {
+        let (flat, doc) = match stack.pop().or_else(|| rest.next()) {
+            Some(next) => next,
+            None => return true,
+        };
+        match doc {
+            Doc::Text(text) | Doc::Variable(text) => remaining -= text.chars().count() as isize,
+            Doc::Line(text) if flat => remaining -= text.chars().count() as isize,
+            Doc::Line(..) => return true,
+            Doc::Broken(..) if flat => {}
+            Doc::Broken(text) => remaining -= text.chars().count() as isize,
+            Doc::Nest(doc) | Doc::Group(doc) => stack.push((flat, doc)),
+            Doc::Concat(docs) => stack.extend(docs.iter().rev().map(|doc| (flat, doc))),
+        }
+    }
+    false
+}
+
+/// Types which can be printed as Lumber source code.
+pub(crate) trait Pretty {
+    fn doc(&self) -> Doc;
+}
+
+/// Writes something as Lumber source code, on one line, or laid out to fit within [`WIDTH`][]
+/// columns when the alternate flag (`{:#}`) is used.
+pub(crate) fn display<T: Pretty + ?Sized>(value: &T, f: &mut Formatter) -> fmt::Result {
+    let width = if f.alternate() { Some(WIDTH) } else { None };
+    let mut doc = value.doc();
+    doc.name_variables();
+    f.pad(&doc.render(width))
+}
+
+/// Text written by a formatting function.
+pub(crate) fn text<F: Fn(&mut Formatter) -> fmt::Result>(write: F) -> Doc {
+    struct Text<F>(F);
+
+    impl<F: Fn(&mut Formatter) -> fmt::Result> Display for Text<F> {
+        fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+            (self.0)(f)
+        }
+    }
+
+    Doc::from(Text(write).to_string())
+}
+
+fn join(docs: Vec<Doc>, separator: &'static str) -> Vec<Doc> {
+    let mut joined = vec![];
+    for (i, doc) in docs.into_iter().enumerate() {
+        if i != 0 {
+            joined.push(Doc::from(separator));
+            joined.push(Doc::Line(" "));
+        }
+        joined.push(doc);
+    }
+    joined
+}
+
+/// Wraps a document in brackets, placing it on its own line between them if it does not fit.
+pub(crate) fn wrap(open: &str, doc: Doc, close: &str) -> Doc {
+    Doc::group(Doc::Concat(vec![
+        Doc::from(open),
+        Doc::nest(Doc::Concat(vec![Doc::Line(""), doc])),
+        Doc::Line(""),
+        Doc::from(close),
+    ]))
+}
+
+/// A bracketed, comma separated collection such as a list or record, which may be followed by
+/// the tail of the collection (an empty tail being written as a bare `..`). When broken across
+/// lines, each item is placed on its own line.
+///
+/// A collection with no items is written as `empty`. A collection with no items but a tail is
+/// written as just the tail, as it cannot be written in brackets, but would unify the same way.
+pub(crate) fn collection(
+    open: &str,
+    items: Vec<Doc>,
+    tail: Option<Doc>,
+    close: &str,
+    empty: &str,
+) -> Doc {
+    if items.is_empty() {
+        return match tail {
+            None => Doc::from(empty),
+            Some(tail) if tail.is_empty() => Doc::from("_"),
+            Some(tail) => tail,
+        };
+    }
+    let mut contents = join(items, ",");
+    match tail {
+        Some(tail) => contents.extend(vec![Doc::from(","), Doc::Line(" "), Doc::from(".."), tail]),
+        None => contents.push(Doc::Broken(",")),
+    }
+    wrap(open, Doc::Concat(contents), close)
+}
+
+/// A struct, given its contents and whether they are written as a collection. The brackets of a
+/// list, record or set may follow the name directly, while other contents are parenthesized.
+pub(crate) fn structure(name: &Atom, contents: Option<(Doc, bool)>) -> Doc {
+    match contents {
+        None => Doc::from(name.to_string()),
+        Some((contents, true)) => Doc::Concat(vec![Doc::from(name.to_string()), contents]),
+        Some((contents, false)) => {
+            Doc::Concat(vec![Doc::from(name.to_string()), wrap("(", contents, ")")])
+        }
+    }
+}
+
+/// The arguments of a query, with the arguments for its named fields labelled.
+fn arguments(name: Doc, arity: &Arity, patterns: &[Pattern]) -> Doc {
+    if patterns.is_empty() {
+        return name;
+    }
+    let mut patterns = patterns.iter().map(Pretty::doc);
+    let mut args: Vec<_> = patterns.by_ref().take(arity.len as usize).collect();
+    for (field, len) in &arity.fields {
+        for i in 0..*len {
+            let pattern = patterns.next().unwrap_or_else(|| Doc::from("_"));
+            if i == 0 {
+                args.push(Doc::Concat(vec![
+                    Doc::from(format!("{}: ", field)),
+                    pattern,
+                ]));
+            } else {
+                args.push(pattern);
+            }
+        }
+    }
+    Doc::Concat(vec![name, collection("(", args, None, ")", "")])
+}
+
+/// Whether a pattern is written as a bracketed collection.
+fn is_collection(pattern: &Pattern) -> bool {
+    match pattern {
+        Pattern::List(items, tail) => !items.is_empty() || tail.is_none(),
+        #[cfg(feature = "builtin-sets")]
+        Pattern::Set(items, tail) => !items.is_empty() || tail.is_none(),
+        Pattern::Record(fields, tail) => fields.len() != 0 || tail.is_none(),
+        _ => false,
+    }
+}
+
+impl Pretty for Pattern {
+    fn doc(&self) -> Doc {
+        let tail = |tail: &Option<Box<Pattern>>| {
+            tail.as_ref().map(|tail| match tail.as_ref() {
+                Pattern::Wildcard => Doc::from(""),
+                tail => tail.doc(),
+            })
+        };
+        match self {
+            Pattern::Struct(Struct { name, contents }) => structure(
+                name,
+                contents
+                    .as_ref()
+                    .map(|contents| (contents.doc(), is_collection(contents))),
+            ),
+            Pattern::Variable(identifier) => Doc::Variable(identifier.name().to_owned()),
+            Pattern::Literal(literal) => literal.doc(),
+            Pattern::List(items, rest) => collection(
+                "[",
+                items.iter().map(Pretty::doc).collect(),
+                tail(rest),
+                "]",
+                "[]",
+            ),
+            #[cfg(feature = "builtin-sets")]
+            Pattern::Set(items, rest) => collection(
+                "{",
+                items.iter().map(Pretty::doc).collect(),
+                tail(rest),
+                "}",
+                "{}",
+            ),
+            Pattern::Record(fields, rest) => collection(
+                "{",
+                fields
+                    .iter()
+                    .map(|(key, value)| {
+                        Doc::Concat(vec![Doc::from(format!("{}: ", key)), value.doc()])
+                    })
+                    .collect(),
+                tail(rest),
+                "}",
+                "{:}",
+            ),
+            Pattern::Map(entries, rest) => collection(
+                "{",
+                entries
+                    .iter()
+                    .map(|(key, value)| {
+                        Doc::Concat(vec![key.doc(), Doc::from(" => "), value.doc()])
+                    })
+                    .collect(),
+                tail(rest),
+                "}",
+                "{=>}",
+            ),
+            Pattern::Lambda(lambda) => lambda.doc(),
+            Pattern::Wildcard | Pattern::Any(..) => Doc::from("_"),
+            Pattern::Bound(pattern) => match pattern.as_ref() {
+                Pattern::Wildcard => Doc::from("!"),
+                pattern => Doc::Concat(vec![Doc::from("!"), pattern.doc()]),
+            },
+            Pattern::Unbound(pattern) => match pattern.as_ref() {
+                Pattern::Wildcard => Doc::from("?"),
+                pattern => Doc::Concat(vec![Doc::from("?"), pattern.doc()]),
+            },
+        }
+    }
+}
+
+impl Pretty for Literal {
+    fn doc(&self) -> Doc {
+        match self {
+            Literal::Integer(int) => Doc::from(int.to_string()),
+            Literal::Rational(rational) => text(|f| fmt_rational(rational, f)),
+            Literal::Float(float) => text(|f| fmt_float(float.0, f)),
+            Literal::String(string) => text(|f| fmt_string(string, f)),
+        }
+    }
+}
+
+/// A lambda is written with the parameters and body of the definition it was lifted out to, in
+/// which its captured variables are replaced by the values they were captured with. Variables
+/// introduced by the compiler within the lambda are given the number of the lambda as well, so
+/// that they cannot clash with those of the enclosing clause.
+impl Pretty for Lambda {
+    fn doc(&self) -> Doc {
+        let (head, body) = self.definition.as_ref();
+        let params = head.patterns.iter().map(Pretty::doc).collect();
+        let mut doc = Doc::group(Doc::Concat(vec![
+            collection("[", params, None, "]", "[]"),
+            Doc::from(" :-"),
+            Doc::nest(Doc::Concat(vec![
+                Doc::Line(" "),
+                procession_steps(&body.steps),
+            ])),
+        ]));
+        let name = self.handle.scope.head();
+        let number = name.as_ref().trim_start_matches("#lambda");
+        doc.replace_variables(&mut |name| match self
+            .captures
+            .iter()
+            .find(|(identifier, _)| identifier.name() == name)
+        {
+            Some((_, pattern)) => Some(pattern.doc()),
+            None => name
+                .strip_prefix('#')
+                .map(|index| Doc::Variable(format!("#{}_{}", number, index))),
+        });
+        doc
+    }
+}
+
+impl Pretty for Query {
+    fn doc(&self) -> Doc {
+        arguments(
+            Doc::from(self.handle.scope.to_string()),
+            &self.handle.arity,
+            &self.patterns,
+        )
+    }
+}
+
+/// The goals of a body, separated such that they are each placed on their own line when their
+/// group is broken. An empty body is written as a goal that always succeeds.
+impl Pretty for Body {
+    fn doc(&self) -> Doc {
+        let disjunction = match &self.0 {
+            Some(disjunction) => disjunction,
+            None => return Doc::from("@core::true"),
+        };
+        let cases = disjunction
+            .cases
+            .iter()
+            .map(|conjunction| {
+                let mut terms = vec![];
+                for procession in &conjunction.terms {
+                    match procession.steps.as_slice() {
+                        // The work of an operation is written as separate goals, rather than
+                        // parenthesized, when it is not part of a procession.
+                        [Unification::Assumption(pattern, Expression::Operation(output, work))] => {
+                            terms.extend(work.iter().map(Pretty::doc));
+                            terms.push(assumption(pattern, output.doc()));
+                        }
+                        steps => terms.push(procession_steps(steps)),
+                    }
+                }
+                Doc::Concat(join(terms, ","))
+            })
+            .collect();
+        Doc::Concat(join(cases, ";"))
+    }
+}
+
+/// The steps of a procession, each of which is parenthesized if it has more than one goal.
+fn procession_steps(steps: &[Unification]) -> Doc {
+    Doc::Concat(
+        steps
+            .iter()
+            .enumerate()
+            .flat_map(|(i, step)| {
+                let arrow = if i == 0 { "" } else { " -> " };
+                vec![Doc::from(arrow), step.doc()]
+            })
+            .collect(),
+    )
+}
+
+fn assumption(pattern: &Pattern, value: Doc) -> Doc {
+    Doc::Concat(vec![pattern.doc(), Doc::from(" <- "), value])
+}
+
+impl Pretty for Unification {
+    fn doc(&self) -> Doc {
+        match self {
+            Unification::Query(query) => query.doc(),
+            Unification::Body(body) => wrap("(", body.doc(), ")"),
+            Unification::Assumption(pattern, expression) => match expression {
+                Expression::Value(value) => assumption(pattern, value.doc()),
+                Expression::Operation(output, work) if work.is_empty() => {
+                    assumption(pattern, output.doc())
+                }
+                Expression::Operation(output, work) => {
+                    let mut goals: Vec<_> = work.iter().map(Pretty::doc).collect();
+                    goals.push(assumption(pattern, output.doc()));
+                    wrap("(", Doc::Concat(join(goals, ",")), ")")
+                }
+                #[cfg(feature = "builtin-sets")]
+                Expression::SetAggregation(output, body) => {
+                    assumption(pattern, aggregation("{", output, body, "}"))
+                }
+                Expression::ListAggregation(output, body) => {
+                    assumption(pattern, aggregation("[", output, body, "]"))
+                }
+            },
+        }
+    }
+}
+
+fn aggregation(open: &str, output: &Pattern, body: &Body, close: &str) -> Doc {
+    wrap(
+        open,
+        Doc::Concat(vec![output.doc(), Doc::from(" : "), body.doc()]),
+        close,
+    )
+}
+
+/// A clause of a definition, with the body of a rule placed beneath its head if it does not fit
+/// beside it.
+fn clause(head: &Query, body: &Body) -> Doc {
+    let head = arguments(
+        Doc::from(head.handle.scope.head().to_string()),
+        &head.handle.arity,
+        &head.patterns,
+    );
+    match body.0 {
+        None => Doc::Concat(vec![head, Doc::from(".")]),
+        Some(..) => Doc::group(Doc::Concat(vec![
+            head,
+            Doc::from(" :-"),
+            Doc::nest(Doc::Concat(vec![Doc::Line(" "), body.doc()])),
+            Doc::from("."),
+        ])),
+    }
+}
+
+impl Display for Pattern {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        display(self, f)
+    }
+}
+
+impl Display for Query {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        display(self, f)
+    }
+}
+
+impl Display for Body {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        display(self, f)
+    }
+}
+
+/// Each clause of a definition is written on its own line. Queries are written with paths
+/// relative to the root module, but clauses are written with only the name of the predicate they
+/// define, as they would be written in the module that defines them.
+impl Display for Definition {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let width = if f.alternate() { Some(WIDTH) } else { None };
+        for (i, (head, body)) in self.iter().enumerate() {
+            if i != 0 {
+                writeln!(f)?;
+            }
+            let mut doc = clause(head, body);
+            doc.name_variables();
+            f.write_str(&doc.render(width))?;
+        }
+        Ok(())
+    }
+}
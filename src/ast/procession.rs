@@ -22,6 +22,10 @@ impl Procession {
         self.steps.iter_mut().flat_map(|step| step.handles_mut())
     }
 
+    pub fn queries(&self) -> impl Iterator<Item = &Query> {
+        self.steps.iter().flat_map(|step| step.queries())
+    }
+
     pub fn identifiers<'a>(&'a self) -> impl Iterator<Item = Identifier> + 'a {
         self.steps.iter().flat_map(|step| step.identifiers())
     }
@@ -7,6 +7,8 @@ pub(crate) struct Query {
     pub(crate) handle: Handle,
     /// The patterns in each field.
     pub(crate) patterns: Vec<Pattern>,
+    /// Where this query was written, if it was written in the source code.
+    pub(crate) span: SourceSpan,
 }
 
 impl AsRef<Handle> for Query {
@@ -23,7 +25,11 @@ impl AsMut<Handle> for Query {
 
 impl Query {
     pub fn new(handle: Handle, patterns: Vec<Pattern>) -> Self {
-        Self { handle, patterns }
+        Self {
+            handle,
+            patterns,
+            span: SourceSpan::default(),
+        }
     }
 }
 
@@ -42,6 +48,7 @@ impl Query {
     }
 
     fn new_unscoped(pair: crate::Pair, context: &mut Context) -> Self {
+        let span = context.span(pair.as_span());
         let mut pairs = pair.into_inner();
         let atom = Atom::new(pairs.next().unwrap());
         let scope = context.current_scope.join(atom);
@@ -49,8 +56,13 @@ impl Query {
             .next()
             .map(|pair| params(pair, context))
             .unwrap_or((Arity::default(), vec![]));
-        let handle = Handle::from_parts(scope, arity);
-        Query { handle, patterns }
+        let mut handle = Handle::from_parts(scope, arity);
+        handle.span = span.clone();
+        Query {
+            handle,
+            patterns,
+            span,
+        }
     }
 
     pub fn from_predicate(pair: crate::Pair, context: &mut Context) -> Option<Self> {
@@ -67,14 +79,36 @@ impl Query {
     }
 
     fn new_scoped(pair: crate::Pair, context: &mut Context) -> Option<Self> {
+        let span = context.span(pair.as_span());
         let mut pairs = pair.into_inner();
         let scope = Scope::new(pairs.next().unwrap(), context)?;
         let (arity, patterns) = pairs
             .next()
             .map(|pair| params(pair, context))
             .unwrap_or((Arity::default(), vec![]));
-        let handle = Handle::from_parts(scope, arity);
-        Some(Query { handle, patterns })
+        let mut handle = Handle::from_parts(scope, arity);
+        handle.span = span.clone();
+        Some(Query {
+            handle,
+            patterns,
+            span,
+        })
+    }
+
+    pub fn handles_mut(&mut self) -> impl Iterator<Item = &mut Handle> {
+        std::iter::once(&mut self.handle).chain(
+            self.patterns
+                .iter_mut()
+                .flat_map(|pattern| pattern.handles_mut()),
+        )
+    }
+
+    /// Handles referenced by lambdas within the patterns of this query, not including
+    /// the handle of the query itself.
+    pub fn pattern_handles_mut(&mut self) -> impl Iterator<Item = &mut Handle> {
+        self.patterns
+            .iter_mut()
+            .flat_map(|pattern| pattern.handles_mut())
     }
 
     pub fn identifiers<'a>(&'a self) -> impl Iterator<Item = Identifier> + 'a {
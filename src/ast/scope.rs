@@ -96,6 +96,18 @@ impl Scope {
         }
     }
 
+    /// Parses a scope from the way it is displayed, such as `@core::list::contains`.
+    pub fn parse_path(path: &str) -> Self {
+        let mut scope = Self::default();
+        for part in path.split("::") {
+            match part.strip_prefix('@') {
+                Some(lib) if scope.path.is_empty() => scope.lib.push(Atom::from(lib)),
+                _ => scope.path.push(Atom::from(part)),
+            }
+        }
+        scope
+    }
+
     pub fn join(&self, atom: Atom) -> Self {
         let mut path = self.path.clone();
         path.push(atom);
@@ -120,6 +132,16 @@ impl Scope {
         scope
     }
 
+    /// The scope of the module containing this one, or `None` if this is the root of a program
+    /// or library.
+    pub fn parent(&self) -> Option<Self> {
+        if self.path.is_empty() {
+            None
+        } else {
+            Some(self.drop())
+        }
+    }
+
     pub fn add_lib(&mut self, lib: Atom) {
         self.lib.insert(0, lib);
     }
@@ -0,0 +1,73 @@
+use crate::Location;
+use std::fmt::{self, Debug, Formatter};
+use std::hash::{Hash, Hasher};
+use std::path::PathBuf;
+use std::rc::Rc;
+
+/// The source code of a module, kept so that errors may refer back to it.
+#[derive(Debug)]
+pub(crate) struct Source {
+    /// The file the source code was read from, if it was read from a file.
+    pub(crate) path: Option<PathBuf>,
+    pub(crate) code: String,
+}
+
+impl Source {
+    pub fn new(path: Option<PathBuf>, code: String) -> Rc<Self> {
+        Rc::new(Self { path, code })
+    }
+}
+
+/// A region of the source code of a module.
+///
+/// Spans are attached to parts of the program only so that errors can point back at them, so
+/// they are ignored when comparing or hashing. Only the byte offsets are stored; the line and
+/// column are only computed when a [`Location`][] is requested.
+#[derive(Clone, Default)]
+pub(crate) struct SourceSpan(Option<(Rc<Source>, usize, usize)>);
+
+impl SourceSpan {
+    pub fn new(source: &Rc<Source>, span: pest::Span) -> Self {
+        Self(Some((source.clone(), span.start(), span.end())))
+    }
+
+    pub fn location(&self) -> Option<Location> {
+        let (source, start, end) = self.0.as_ref()?;
+        let start = pest::Position::new(&source.code, *start)?;
+        let end = pest::Position::new(&source.code, *end)?;
+        let (line, column) = start.line_col();
+        let (end_line, end_column) = end.line_col();
+        Some(Location {
+            file: source.path.clone(),
+            line,
+            column,
+            end_line,
+            end_column,
+            snippet: start
+                .line_of()
+                .trim_end_matches(&['\r', '\n'][..])
+                .to_owned(),
+        })
+    }
+}
+
+impl PartialEq for SourceSpan {
+    fn eq(&self, _: &Self) -> bool {
+        true
+    }
+}
+
+impl Eq for SourceSpan {}
+
+impl Hash for SourceSpan {
+    fn hash<H: Hasher>(&self, _: &mut H) {}
+}
+
+impl Debug for SourceSpan {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match &self.0 {
+            Some((_, start, end)) => write!(f, "SourceSpan({}..{})", start, end),
+            None => write!(f, "SourceSpan(None)"),
+        }
+    }
+}
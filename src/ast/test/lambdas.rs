@@ -0,0 +1,56 @@
+use super::*;
+
+yes! {
+    lambda_basic => r#"
+    check(yes).
+    test(P) :- @core::equal(P, [X] :- check(X)).
+    "#
+}
+
+yes! {
+    lambda_capture => r#"
+    check(yes, yes).
+    test(A, P) :- @core::equal(P, [X] :- check(A, X)).
+    "#
+}
+
+yes! {
+    lambda_capture_only => r#"
+    check(yes).
+    test(A, P) :- @core::equal(P, [] :- check(A)).
+    "#
+}
+
+yes! {
+    lambda_local_variables => r#"
+    check(yes, yes).
+    test(P) :- @core::equal(P, [X] :- check(X, Y) -> check(Y, X)).
+    "#
+}
+
+yes! {
+    lambda_nested => r#"
+    check(yes, yes).
+    test(P) :- @core::equal(P, [X] :- @core::equal(X, [Y] :- check(X, Y))).
+    "#
+}
+
+no! {
+    lambda_singleton => r#"
+    check(yes).
+    test(P) :- @core::equal(P, [X] :- check(Y)).
+    "#
+}
+
+no! {
+    lambda_undefined => r#"
+    test(P) :- @core::equal(P, [X] :- check(X)).
+    "#
+}
+
+no! {
+    lambda_wrong_arity => r#"
+    check(yes).
+    test(P) :- @core::equal(P, [X] :- check(X, X)).
+    "#
+}
@@ -39,6 +39,7 @@ mod functions;
 mod globs;
 mod imports;
 mod incompletes;
+mod lambdas;
 mod libraries;
 mod modules;
 mod mutables;
@@ -15,12 +15,24 @@ yes! {
     "#
 }
 
-no! {
+yes! {
     operation_boolean => r#"
     test!(A, B, C, D) <- A == B && C < D.
     "#
 }
 
+yes! {
+    operation_comparison_goal => r#"
+    test(A, B) :- A < B, A + 1 != B.
+    "#
+}
+
+no! {
+    operation_condition_unrecognized => r#"
+    test(A, B) :- A <> B.
+    "#
+}
+
 yes! {
     operation_named_operator => r#"
     in!(A, [A, ..]) <- [_].
@@ -58,3 +70,50 @@ no! {
     test!(A, B) <- A `in` B.
     "#
 }
+
+yes! {
+    operation_user_defined => r#"
+    :- op(<>, 8, left, concat/3).
+    concat!(A, B) <- A + B.
+    test!(A, B) <- A <> B <> A.
+    "#
+}
+
+no! {
+    operation_user_defined_wrong_arity => r#"
+    :- op(<>, 8, left, concat/2).
+    concat(A, B).
+    "#
+}
+
+no! {
+    operation_user_defined_builtin => r#"
+    :- op(+, 8, left, concat/3).
+    concat!(A, B) <- A + B.
+    "#
+}
+
+no! {
+    operation_user_defined_duplicate => r#"
+    :- op(<>, 8, left, concat/3).
+    :- op(<>, 8, left, append/3).
+    concat!(A, B) <- A + B.
+    append!(A, B) <- A + B.
+    "#
+}
+
+yes! {
+    operator_import => r#"
+    :- mod(a).
+    :- use(a).
+    test!(A, B) <- A <> B.
+    "#
+}
+
+yes! {
+    operator_import_alias => r#"
+    :- mod(a).
+    :- use(a(concat/3)).
+    test!(A, B) <- A <> B.
+    "#
+}
@@ -18,6 +18,18 @@ yes! {
     "###
 }
 
+yes! {
+    value_string_escapes => r#"
+    test(A) :- A <- "tab\t quote\" tree\u{1F332}".
+    "#
+}
+
+no! {
+    value_string_invalid_unicode => r#"
+    test(A) :- A <- "\u{D800}".
+    "#
+}
+
 yes! {
     value_atom => r#"
     test(A) :- A <- hello.
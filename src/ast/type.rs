@@ -0,0 +1,262 @@
+use super::*;
+use std::collections::BTreeMap;
+use std::fmt::{self, Display, Formatter};
+
+/// The names of the built-in types, which cannot be redefined.
+const BUILTIN_TYPES: &[&str] = &[
+    "any", "integer", "rational", "float", "number", "string", "atom", "bool", "list", "map",
+];
+
+/// After this many named types are expanded without checking any part of the pattern, the type
+/// is assumed to be recursive without any structure, and the pattern is accepted.
+const MAX_EXPANSIONS: usize = 32;
+
+/// A type, as written in a type declaration or predicate signature. Types are written using
+/// pattern syntax, which is interpreted as follows:
+///
+/// *   `_` and `any` accept any value.
+/// *   `integer`, `rational`, `float`, `number`, `string`, `atom` and `bool` accept values of the
+///     corresponding kind. `list` and `map` accept any list or map.
+/// *   `list(T)` accepts a list where each element is of type `T`.
+/// *   `atom(a)` accepts only the atom `a`.
+/// *   `[A, B]` accepts a list of exactly two elements, of types `A` and `B` respectively.
+/// *   `{ a: A, b: B }` accepts a record with exactly the fields `a` and `b`. If the record type
+///     has a tail, as in `{ a: A, .. }`, other fields are also accepted.
+/// *   `name(T)` accepts a struct called `name` containing a value of type `T`.
+/// *   Any other atom refers to a declared type, by name.
+#[derive(Clone, Debug)]
+pub(crate) enum Type {
+    Any,
+    Integer,
+    Rational,
+    Float,
+    Number,
+    String,
+    Atom(Option<Atom>),
+    Bool,
+    List(Option<Box<Type>>),
+    Map,
+    Tuple(Vec<Type>),
+    Record(BTreeMap<Atom, Type>, bool),
+    Struct(Atom, Box<Type>),
+    Named(Atom),
+}
+
+impl Type {
+    /// Interprets a pattern as a type. If the pattern does not describe a type, the error is
+    /// reported and `None` is returned.
+    pub fn new(pattern: Pattern, context: &mut Context) -> Option<Self> {
+        let ty = match pattern {
+            Pattern::Wildcard => Self::Any,
+            Pattern::Struct(Struct {
+                name,
+                contents: None,
+            }) => match name.as_ref() {
+                "any" => Self::Any,
+                "integer" => Self::Integer,
+                "rational" => Self::Rational,
+                "float" => Self::Float,
+                "number" => Self::Number,
+                "string" => Self::String,
+                "atom" => Self::Atom(None),
+                "bool" => Self::Bool,
+                "list" => Self::List(None),
+                "map" => Self::Map,
+                _ => Self::Named(name),
+            },
+            Pattern::Struct(Struct {
+                name,
+                contents: Some(contents),
+            }) => match (name.as_ref(), *contents) {
+                (
+                    "atom",
+                    Pattern::Struct(Struct {
+                        name,
+                        contents: None,
+                    }),
+                ) => Self::Atom(Some(name)),
+                ("atom", _) => {
+                    context.error_invalid_type("the contents of `atom` must be an atom");
+                    return None;
+                }
+                ("list", contents) => Self::List(Some(Box::new(Self::new(contents, context)?))),
+                (_, contents) => Self::Struct(name, Box::new(Self::new(contents, context)?)),
+            },
+            Pattern::List(elements, None) => Self::Tuple(
+                elements
+                    .into_iter()
+                    .map(|element| Self::new(element, context))
+                    .collect::<Option<_>>()?,
+            ),
+            Pattern::Record(fields, tail) => {
+                let fields = fields
+                    .into_iter()
+                    .map(|(key, field)| Some((key, Self::new(field, context)?)))
+                    .collect::<Option<_>>()?;
+                Self::Record(fields, tail.is_none())
+            }
+            _ => {
+                context.error_invalid_type(
+                    "types may only be written using atoms, structs, lists and records",
+                );
+                return None;
+            }
+        };
+        Some(ty)
+    }
+
+    pub fn is_builtin(name: &Atom) -> bool {
+        BUILTIN_TYPES.contains(&name.as_ref())
+    }
+
+    /// The names of all declared types referenced by this type.
+    pub fn references(&self) -> Box<dyn Iterator<Item = &Atom> + '_> {
+        match self {
+            Self::List(Some(ty)) | Self::Struct(_, ty) => ty.references(),
+            Self::Tuple(types) => Box::new(types.iter().flat_map(Self::references)),
+            Self::Record(fields, _) => Box::new(fields.values().flat_map(Self::references)),
+            Self::Named(name) => Box::new(std::iter::once(name)),
+            _ => Box::new(std::iter::empty()),
+        }
+    }
+
+    /// Checks whether a pattern could match some value of this type. Named types are resolved
+    /// relative to the module in which this type was declared. Variables may hold values of any
+    /// type, so only the shape of the pattern itself is checked.
+    pub fn admits(&self, pattern: &Pattern, scope: &Scope, context: &Context) -> bool {
+        self.admits_inner(pattern, scope, context, 0)
+    }
+
+    fn admits_inner(
+        &self,
+        pattern: &Pattern,
+        scope: &Scope,
+        context: &Context,
+        expansions: usize,
+    ) -> bool {
+        let admits = |ty: &Type, pattern: &Pattern| ty.admits_inner(pattern, scope, context, 0);
+        match (self, pattern) {
+            (_, Pattern::Bound(inner)) | (_, Pattern::Unbound(inner)) => {
+                self.admits_inner(inner, scope, context, expansions)
+            }
+            (_, Pattern::Variable(..))
+            | (_, Pattern::Wildcard)
+            | (_, Pattern::Any(..))
+            | (_, Pattern::Lambda(..))
+            | (Self::Any, _) => true,
+            (Self::Named(name), _) => {
+                if expansions > MAX_EXPANSIONS {
+                    return true;
+                }
+                match context.resolve_type(name, scope) {
+                    Some((scope, alternatives)) => alternatives
+                        .iter()
+                        .any(|ty| ty.admits_inner(pattern, &scope, context, expansions + 1)),
+                    // Unresolved types have already been reported.
+                    None => true,
+                }
+            }
+            (Self::Integer, Pattern::Literal(Literal::Integer(..)))
+            | (Self::Rational, Pattern::Literal(Literal::Rational(..)))
+            | (Self::Float, Pattern::Literal(Literal::Float(..)))
+            | (Self::Number, Pattern::Literal(Literal::Integer(..)))
+            | (Self::Number, Pattern::Literal(Literal::Rational(..)))
+            | (Self::Number, Pattern::Literal(Literal::Float(..)))
+            | (Self::String, Pattern::Literal(Literal::String(..)))
+            | (Self::Map, Pattern::Map(..)) => true,
+            (Self::Atom(name), Pattern::Struct(structure)) => {
+                structure.contents.is_none()
+                    && name.as_ref().map_or(true, |name| name == &structure.name)
+            }
+            (Self::Bool, Pattern::Struct(structure)) => {
+                structure.contents.is_none()
+                    && (structure.name.as_ref() == "true" || structure.name.as_ref() == "false")
+            }
+            (Self::List(element), Pattern::List(elements, tail)) => match element {
+                None => true,
+                Some(element) => {
+                    elements.iter().all(|pattern| admits(element, pattern))
+                        && tail.iter().all(|tail| admits(self, tail))
+                }
+            },
+            (Self::Tuple(types), Pattern::List(elements, tail)) => {
+                let lengths_match = match tail {
+                    None => types.len() == elements.len(),
+                    Some(..) => types.len() >= elements.len(),
+                };
+                lengths_match
+                    && types
+                        .iter()
+                        .zip(elements.iter())
+                        .all(|(ty, pattern)| admits(ty, pattern))
+            }
+            (Self::Record(types, complete), Pattern::Record(fields, tail)) => {
+                let fields_match = fields.iter().all(|(key, pattern)| match types.get(key) {
+                    Some(ty) => admits(ty, pattern),
+                    None => !complete,
+                });
+                let fields_present = tail.is_some()
+                    || !complete
+                    || types
+                        .keys()
+                        .all(|key| fields.iter().any(|(field, _)| field == key));
+                fields_match && fields_present
+            }
+            (Self::Struct(name, ty), Pattern::Struct(structure)) => {
+                name == &structure.name
+                    && structure
+                        .contents
+                        .as_ref()
+                        .map_or(false, |contents| admits(ty, contents))
+            }
+            _ => false,
+        }
+    }
+}
+
+impl Display for Type {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::Any => write!(f, "any"),
+            Self::Integer => write!(f, "integer"),
+            Self::Rational => write!(f, "rational"),
+            Self::Float => write!(f, "float"),
+            Self::Number => write!(f, "number"),
+            Self::String => write!(f, "string"),
+            Self::Atom(None) => write!(f, "atom"),
+            Self::Atom(Some(name)) => write!(f, "atom({})", name),
+            Self::Bool => write!(f, "bool"),
+            Self::List(None) => write!(f, "list"),
+            Self::List(Some(ty)) => write!(f, "list({})", ty),
+            Self::Map => write!(f, "map"),
+            Self::Tuple(types) => {
+                write!(f, "[")?;
+                for (i, ty) in types.iter().enumerate() {
+                    if i != 0 {
+                        write!(f, ", ")?;
+                    }
+                    ty.fmt(f)?;
+                }
+                write!(f, "]")
+            }
+            Self::Record(fields, complete) => {
+                write!(f, "{{")?;
+                for (i, (key, ty)) in fields.iter().enumerate() {
+                    if i != 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{}: {}", key, ty)?;
+                }
+                match (fields.is_empty(), complete) {
+                    (true, true) => write!(f, ":")?,
+                    (true, false) => write!(f, "..")?,
+                    (false, false) => write!(f, ", ..")?,
+                    (false, true) => {}
+                }
+                write!(f, "}}")
+            }
+            Self::Struct(name, ty) => write!(f, "{}({})", name, ty),
+            Self::Named(name) => name.fmt(f),
+        }
+    }
+}
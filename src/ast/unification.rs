@@ -18,6 +18,7 @@ impl Unification {
         let pair = just!(pair.into_inner());
         let unification = match pair.as_rule() {
             Rule::assumption => Self::from_assumption(pair, context)?,
+            Rule::condition => Self::from_condition(pair, context)?,
             Rule::predicate => Self::Query(Query::from_predicate(pair, context)?),
             Rule::disjunction => Self::Body(Body::new_inner(pair, context)?),
             _ => unreachable!(),
@@ -35,11 +36,31 @@ impl Unification {
         ))
     }
 
+    /// A condition is an operation used as a goal, which succeeds when it evaluates to `true`.
+    pub fn from_condition(pair: crate::Pair, context: &mut Context) -> Option<Self> {
+        assert_eq!(pair.as_rule(), Rule::condition);
+        let pair = just!(Rule::operation, pair.into_inner());
+        Some(Self::Assumption(
+            Pattern::Struct(Struct::from_parts(Atom::from("true"), None)),
+            Expression::new_operation(pair, context)?,
+        ))
+    }
+
     pub fn handles_mut<'a>(&'a mut self) -> Box<dyn Iterator<Item = &mut Handle> + 'a> {
         match self {
-            Self::Query(query) => Box::new(std::iter::once(query.as_mut())),
+            Self::Query(query) => Box::new(query.handles_mut()),
             Self::Body(body) => Box::new(body.handles_mut()),
-            Self::Assumption(_, expression) => expression.handles_mut(),
+            Self::Assumption(pattern, expression) => {
+                Box::new(pattern.handles_mut().chain(expression.handles_mut()))
+            }
+        }
+    }
+
+    pub fn queries<'a>(&'a self) -> Box<dyn Iterator<Item = &Query> + 'a> {
+        match self {
+            Self::Query(query) => Box::new(std::iter::once(query)),
+            Self::Body(body) => Box::new(body.queries()),
+            Self::Assumption(_, expression) => expression.queries(),
         }
     }
 
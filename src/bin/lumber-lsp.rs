@@ -0,0 +1,440 @@
+use lumber::{Analysis, Error, ErrorKind, Location, Lumber};
+use serde_json::{json, Value};
+use std::collections::HashSet;
+use std::io::{self, BufRead, Write};
+use std::path::{Path, PathBuf};
+
+/// Lumber language server, speaking the Language Server Protocol over stdin and stdout.
+///
+/// The program is checked from its main module, which is given by the `main` initialization
+/// option, or else is `main.lumber` in the root of the workspace. Files which are not part of
+/// that program are checked on their own.
+#[derive(Default)]
+struct Server {
+    main: Option<PathBuf>,
+    analysis: Option<Analysis>,
+    published: HashSet<PathBuf>,
+    shutdown: bool,
+}
+
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_REQUEST: i64 = -32600;
+
+pub fn main() {
+    let stdin = io::stdin();
+    let stdout = io::stdout();
+    let mut input = stdin.lock();
+    let mut output = stdout.lock();
+    let mut server = Server::default();
+    while let Some(message) = read_message(&mut input) {
+        let method = message["method"].as_str().unwrap_or_default();
+        let params = &message["params"];
+        match message.get("id") {
+            Some(id) => {
+                let response = match server.request(method, params) {
+                    Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+                    Err((code, message)) => json!({
+                        "jsonrpc": "2.0",
+                        "id": id,
+                        "error": { "code": code, "message": message },
+                    }),
+                };
+                write_message(&mut output, &response);
+            }
+            None if method == "exit" => std::process::exit(if server.shutdown { 0 } else { 1 }),
+            None => {
+                for notification in server.notify(method, params) {
+                    write_message(&mut output, &notification);
+                }
+            }
+        }
+    }
+}
+
+impl Server {
+    fn request(&mut self, method: &str, params: &Value) -> Result<Value, (i64, String)> {
+        if self.shutdown {
+            return Err((INVALID_REQUEST, "The server has been shut down".to_owned()));
+        }
+        match method {
+            "initialize" => {
+                self.initialize(params);
+                Ok(json!({
+                    "capabilities": {
+                        "textDocumentSync": { "openClose": true, "change": 0, "save": true },
+                        "definitionProvider": true,
+                        "referencesProvider": true,
+                        "hoverProvider": true,
+                        "completionProvider": {},
+                    },
+                    "serverInfo": { "name": "lumber-lsp", "version": env!("CARGO_PKG_VERSION") },
+                }))
+            }
+            "shutdown" => {
+                self.shutdown = true;
+                Ok(Value::Null)
+            }
+            "textDocument/definition" => Ok(self.definition(params)),
+            "textDocument/references" => Ok(self.references(params)),
+            "textDocument/hover" => Ok(self.hover(params)),
+            "textDocument/completion" => Ok(self.completion(params)),
+            _ => Err((METHOD_NOT_FOUND, format!("Unsupported method {}", method))),
+        }
+    }
+
+    fn notify(&mut self, method: &str, params: &Value) -> Vec<Value> {
+        match method {
+            "textDocument/didOpen" | "textDocument/didSave" => match document(params) {
+                Some(file) => self.check(&file),
+                None => vec![],
+            },
+            _ => vec![],
+        }
+    }
+
+    fn initialize(&mut self, params: &Value) {
+        let root = params["rootUri"].as_str().and_then(uri_to_path);
+        self.main = match params["initializationOptions"]["main"].as_str() {
+            Some(main) => Some(match &root {
+                Some(root) => root.join(main),
+                None => PathBuf::from(main),
+            }),
+            None => root
+                .map(|root| root.join("main.lumber"))
+                .filter(|main| main.is_file()),
+        };
+    }
+
+    /// Checks the program which includes a file, returning the diagnostics to publish for each
+    /// of its files. Files which had diagnostics before but are no longer checked are cleared.
+    fn check(&mut self, file: &Path) -> Vec<Value> {
+        self.analysis = None;
+        let mut diagnostics = Vec::<(PathBuf, Vec<Value>)>::new();
+        match self.analyze(file) {
+            Ok(analysis) => {
+                diagnostics.extend(analysis.files().map(|file| (file.to_owned(), vec![])));
+                for error in analysis.diagnostics() {
+                    let file = error.location().and_then(Location::file).unwrap_or(file);
+                    match diagnostics.iter_mut().find(|(path, _)| path == file) {
+                        Some((_, list)) => list.push(diagnostic(error)),
+                        None => diagnostics.push((file.to_owned(), vec![diagnostic(error)])),
+                    }
+                }
+            }
+            Err(error) => {
+                let file = error.location().and_then(Location::file).unwrap_or(file);
+                diagnostics.push((file.to_owned(), vec![diagnostic(&error)]));
+            }
+        }
+
+        let files: HashSet<_> = diagnostics.iter().map(|(file, _)| file.clone()).collect();
+        for file in self.published.difference(&files) {
+            diagnostics.push((file.clone(), vec![]));
+        }
+        self.published = diagnostics
+            .iter()
+            .filter(|(_, list)| !list.is_empty())
+            .map(|(file, _)| file.clone())
+            .collect();
+        diagnostics
+            .into_iter()
+            .map(|(file, list)| {
+                json!({
+                    "jsonrpc": "2.0",
+                    "method": "textDocument/publishDiagnostics",
+                    "params": { "uri": path_to_uri(&file), "diagnostics": list },
+                })
+            })
+            .collect()
+    }
+
+    /// Analyzes the program which includes a file. The analysis is reused until the next time a
+    /// file is saved.
+    fn analyze(&mut self, file: &Path) -> lumber::Result<&Analysis> {
+        let includes = |analysis: &Analysis| analysis.files().any(|path| path == file);
+        if !self.analysis.as_ref().map(includes).unwrap_or(false) {
+            let mut analysis = None;
+            if let Some(main) = &self.main {
+                analysis = Some(Lumber::builder().analyze_file(main)?).filter(includes);
+            }
+            self.analysis = match analysis {
+                Some(analysis) => Some(analysis),
+                None => Some(Lumber::builder().analyze_file(file)?),
+            };
+        }
+        Ok(self.analysis.as_ref().unwrap())
+    }
+
+    fn definition(&mut self, params: &Value) -> Value {
+        let (file, line, column) = match position(params) {
+            Some(position) => position,
+            None => return Value::Null,
+        };
+        let analysis = match self.analyze(&file) {
+            Ok(analysis) => analysis,
+            Err(..) => return Value::Null,
+        };
+        if let Some(module) = analysis.module_at(&file, line, column) {
+            return json!({
+                "uri": path_to_uri(module),
+                "range": { "start": { "line": 0, "character": 0 }, "end": { "line": 0, "character": 0 } },
+            });
+        }
+        match analysis.handle_at(&file, line, column) {
+            Some(handle) => locations(analysis.definitions(&handle)),
+            None => Value::Null,
+        }
+    }
+
+    fn references(&mut self, params: &Value) -> Value {
+        let (file, line, column) = match position(params) {
+            Some(position) => position,
+            None => return Value::Null,
+        };
+        let analysis = match self.analyze(&file) {
+            Ok(analysis) => analysis,
+            Err(..) => return Value::Null,
+        };
+        let handle = match analysis.handle_at(&file, line, column) {
+            Some(handle) => handle,
+            None => return Value::Null,
+        };
+        let mut references = analysis.references(&handle);
+        if params["context"]["includeDeclaration"]
+            .as_bool()
+            .unwrap_or(false)
+        {
+            references.extend(analysis.definitions(&handle));
+        }
+        locations(references)
+    }
+
+    fn hover(&mut self, params: &Value) -> Value {
+        let (file, line, column) = match position(params) {
+            Some(position) => position,
+            None => return Value::Null,
+        };
+        let analysis = match self.analyze(&file) {
+            Ok(analysis) => analysis,
+            Err(..) => return Value::Null,
+        };
+        let handle = match analysis.handle_at(&file, line, column) {
+            Some(handle) => handle,
+            None => return Value::Null,
+        };
+        let visibility = if analysis.is_exported(&handle) {
+            "exported"
+        } else {
+            "private"
+        };
+        let mut value = format!("```lumber\n{}\n```\n\n", handle);
+        if let Some(doc) = analysis.doc(&handle) {
+            value.push_str(doc);
+            value.push_str("\n\n");
+        }
+        value.push_str(&format!("Arity {}, {}.", handle.arity(), visibility));
+        json!({ "contents": { "kind": "markdown", "value": value } })
+    }
+
+    fn completion(&mut self, params: &Value) -> Value {
+        let file = match document(params) {
+            Some(file) => file,
+            None => return Value::Null,
+        };
+        let analysis = match self.analyze(&file) {
+            Ok(analysis) => analysis,
+            Err(..) => return Value::Null,
+        };
+        analysis
+            .completions(&file)
+            .into_iter()
+            .map(|handle| {
+                json!({
+                    "label": handle.name(),
+                    "kind": 3,
+                    "detail": handle.to_string(),
+                })
+            })
+            .collect()
+    }
+}
+
+/// Reads one message, framed by its `Content-Length` header. Returns `None` once the input
+/// has ended.
+fn read_message(input: &mut impl BufRead) -> Option<Value> {
+    loop {
+        let mut length = None;
+        let mut header = String::new();
+        loop {
+            header.clear();
+            if input.read_line(&mut header).ok()? == 0 {
+                return None;
+            }
+            let header = header.trim_end();
+            if header.is_empty() {
+                break;
+            }
+            if let Some(value) = header.strip_prefix("Content-Length:") {
+                length = value.trim().parse::<usize>().ok();
+            }
+        }
+        let mut content = vec![0; length?];
+        input.read_exact(&mut content).ok()?;
+        if let Ok(message) = serde_json::from_slice(&content) {
+            return Some(message);
+        }
+    }
+}
+
+fn write_message(output: &mut impl Write, message: &Value) {
+    let content = message.to_string();
+    write!(
+        output,
+        "Content-Length: {}\r\n\r\n{}",
+        content.len(),
+        content
+    )
+    .unwrap();
+    output.flush().unwrap();
+}
+
+/// The file named by the `textDocument` parameter of a request or notification.
+fn document(params: &Value) -> Option<PathBuf> {
+    uri_to_path(params["textDocument"]["uri"].as_str()?)
+}
+
+/// The file, line and column named by the parameters of a request, with the line and column
+/// counting from 1 rather than from 0, and the column counting characters rather than UTF-16
+/// code units.
+fn position(params: &Value) -> Option<(PathBuf, usize, usize)> {
+    let file = document(params)?;
+    let line = params["position"]["line"].as_u64()? as usize;
+    let column = params["position"]["character"].as_u64()? as usize;
+    let column = match line_of(&file, line + 1) {
+        Some(text) => char_column(&text, column),
+        None => column,
+    };
+    Some((file, line + 1, column + 1))
+}
+
+fn range(location: &Location) -> Value {
+    let end_line = match location.file() {
+        Some(file) if location.end_line() != location.line() => {
+            line_of(file, location.end_line()).unwrap_or_default()
+        }
+        _ => location.snippet().to_owned(),
+    };
+    json!({
+        "start": {
+            "line": location.line() - 1,
+            "character": utf16_column(location.snippet(), location.column() - 1),
+        },
+        "end": {
+            "line": location.end_line() - 1,
+            "character": utf16_column(&end_line, location.end_column() - 1),
+        },
+    })
+}
+
+/// The text of a line of a file, counting from 1.
+fn line_of(file: &Path, line: usize) -> Option<String> {
+    let source = std::fs::read_to_string(file).ok()?;
+    source.lines().nth(line - 1).map(str::to_owned)
+}
+
+/// Converts a column counting characters, as Lumber counts them, to one counting UTF-16 code
+/// units, as the protocol counts them. Columns past the end of the line are left as they are.
+fn utf16_column(line: &str, column: usize) -> usize {
+    let chars = line.chars().count();
+    line.chars()
+        .take(column)
+        .map(char::len_utf16)
+        .sum::<usize>()
+        + column.saturating_sub(chars)
+}
+
+/// Converts a column counting UTF-16 code units to one counting characters.
+fn char_column(line: &str, column: usize) -> usize {
+    let mut units = 0;
+    let mut chars = 0;
+    for ch in line.chars() {
+        if units >= column {
+            return chars;
+        }
+        units += ch.len_utf16();
+        chars += 1;
+    }
+    chars + column.saturating_sub(units)
+}
+
+fn locations(locations: Vec<Location>) -> Value {
+    locations
+        .iter()
+        .filter_map(|location| {
+            Some(json!({
+                "uri": path_to_uri(location.file()?),
+                "range": range(location),
+            }))
+        })
+        .collect()
+}
+
+fn diagnostic(error: &Error) -> Value {
+    let range = match error.location() {
+        Some(location) => range(location),
+        None => json!({
+            "start": { "line": 0, "character": 0 },
+            "end": { "line": 0, "character": 0 },
+        }),
+    };
+    let severity = match error.kind() {
+        ErrorKind::Warning => 2,
+        _ => 1,
+    };
+    let mut message = error.message().to_owned();
+    for note in error.notes() {
+        message.push_str("\nnote: ");
+        message.push_str(note);
+    }
+    if let Some(help) = error.help() {
+        message.push_str("\nhelp: ");
+        message.push_str(help);
+    }
+    json!({
+        "range": range,
+        "severity": severity,
+        "code": error.code(),
+        "source": "lumber",
+        "message": message,
+    })
+}
+
+fn uri_to_path(uri: &str) -> Option<PathBuf> {
+    let path = uri.strip_prefix("file://")?.as_bytes();
+    let mut decoded = Vec::with_capacity(path.len());
+    let mut i = 0;
+    while i < path.len() {
+        if path[i] == b'%' {
+            let hex = std::str::from_utf8(path.get(i + 1..i + 3)?).ok()?;
+            decoded.push(u8::from_str_radix(hex, 16).ok()?);
+            i += 3;
+        } else {
+            decoded.push(path[i]);
+            i += 1;
+        }
+    }
+    String::from_utf8(decoded).ok().map(PathBuf::from)
+}
+
+fn path_to_uri(path: &Path) -> String {
+    let mut uri = String::from("file://");
+    for byte in path.to_string_lossy().bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' | b'/' => {
+                uri.push(byte as char)
+            }
+            _ => uri.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    uri
+}
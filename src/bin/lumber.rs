@@ -0,0 +1,146 @@
+use lumber::{DocFormat, ErrorKind, Lumber};
+use std::path::{Path, PathBuf};
+
+/// Lumber command line tools
+#[derive(structopt::StructOpt)]
+enum Opts {
+    /// Generates documentation for each module of a program, listing the predicates it exports
+    /// along with their doc comments.
+    Doc {
+        /// The format of the documentation: `markdown` or `html`.
+        #[structopt(short, long, default_value = "markdown", parse(try_from_str = doc_format))]
+        format: DocFormat,
+        /// The directory to write the documentation to. Each module is written to `index.md` or
+        /// `index.html`, in a directory named after its path from the main module.
+        #[structopt(short, long, default_value = "doc")]
+        output: PathBuf,
+        /// The main module of your program.
+        module: PathBuf,
+    },
+    /// Runs the tests declared with `:- test` in every module of a program, reporting the goal
+    /// at which each failing test ran out of answers. Exits with an error if any test fails.
+    Test {
+        /// Only runs the tests whose names contain this string.
+        #[structopt(long)]
+        filter: Option<String>,
+        /// The main module of your program.
+        module: PathBuf,
+    },
+}
+
+fn doc_format(format: &str) -> Result<DocFormat, String> {
+    match format {
+        "markdown" | "md" => Ok(DocFormat::Markdown),
+        "html" => Ok(DocFormat::Html),
+        _ => Err(format!(
+            "unknown format {:?}, expected markdown or html",
+            format
+        )),
+    }
+}
+
+#[paw::main]
+pub fn main(opts: Opts) {
+    let success = match opts {
+        Opts::Doc {
+            format,
+            output,
+            module,
+        } => doc(format, &output, &module),
+        Opts::Test { filter, module } => test(&module, filter.as_deref()),
+    };
+    if !success {
+        std::process::exit(1);
+    }
+}
+
+fn doc(format: DocFormat, output: &Path, module: &Path) -> bool {
+    let analysis = match Lumber::builder().analyze_file(module) {
+        Ok(analysis) => analysis,
+        Err(error) => {
+            eprintln!("{}", error);
+            return false;
+        }
+    };
+    let errors: Vec<_> = analysis
+        .diagnostics()
+        .iter()
+        .filter(|error| error.kind() != ErrorKind::Warning)
+        .collect();
+    if !errors.is_empty() {
+        for error in errors {
+            eprintln!("{}", error);
+        }
+        return false;
+    }
+
+    for module in analysis.modules() {
+        let path = module
+            .path()
+            .iter()
+            .fold(output.to_owned(), |path, name| path.join(name))
+            .join(format!("index.{}", format.extension()));
+        let contents = analysis.document(module, format);
+        let written = path
+            .parent()
+            .map_or(Ok(()), std::fs::create_dir_all)
+            .and_then(|_| std::fs::write(&path, contents));
+        if let Err(error) = written {
+            eprintln!("{}: {}", path.display(), error);
+            return false;
+        }
+    }
+    true
+}
+
+fn test(module: &Path, filter: Option<&str>) -> bool {
+    let program = match Lumber::from_file(module) {
+        Ok(program) => program,
+        Err(error) => {
+            eprintln!("{}", error);
+            return false;
+        }
+    };
+    for warning in program.warnings() {
+        eprintln!("{}", warning);
+    }
+
+    let tests: Vec<_> = program
+        .tests()
+        .into_iter()
+        .map(|test| (test.to_string().trim_end_matches("/0").to_owned(), test))
+        .filter(|(name, _)| match filter {
+            Some(filter) => name.contains(filter),
+            None => true,
+        })
+        .collect();
+    println!(
+        "running {} test{}",
+        tests.len(),
+        if tests.len() == 1 { "" } else { "s" },
+    );
+    let mut failures = vec![];
+    for (name, test) in &tests {
+        match program.run_test(test) {
+            Ok(()) => println!("test {} ... ok", name),
+            Err(failure) => {
+                println!("test {} ... FAILED", name);
+                failures.push((name, failure));
+            }
+        }
+    }
+
+    if !failures.is_empty() {
+        println!("\nfailures:");
+        for (name, failure) in &failures {
+            println!("\n---- {} ----\n{}", name, failure);
+        }
+    }
+    println!(
+        "\ntest result: {}. {} passed; {} failed",
+        if failures.is_empty() { "ok" } else { "FAILED" },
+        tests.len() - failures.len(),
+        failures.len(),
+    );
+    failures.is_empty()
+}
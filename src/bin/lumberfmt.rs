@@ -0,0 +1,64 @@
+use std::io::Read;
+use std::path::PathBuf;
+
+/// Lumber source formatter
+#[derive(structopt::StructOpt)]
+struct Opts {
+    /// Check whether the files are formatted, without changing them. Exits with an error if any
+    /// file would be changed.
+    #[structopt(long)]
+    check: bool,
+    /// The files to format in place. Otherwise, formats standard input to standard output.
+    files: Vec<PathBuf>,
+}
+
+#[paw::main]
+pub fn main(opts: Opts) {
+    let mut success = true;
+    if opts.files.is_empty() {
+        let mut source = String::new();
+        if let Err(error) = std::io::stdin().read_to_string(&mut source) {
+            eprintln!("{}", error);
+            std::process::exit(1);
+        }
+        match lumber::format(&source) {
+            Ok(formatted) if opts.check => success = formatted == source,
+            Ok(formatted) => print!("{}", formatted),
+            Err(error) => {
+                eprintln!("{}", error);
+                success = false;
+            }
+        }
+    }
+    for path in &opts.files {
+        let source = match std::fs::read_to_string(path) {
+            Ok(source) => source,
+            Err(error) => {
+                eprintln!("{}: {}", path.display(), error);
+                success = false;
+                continue;
+            }
+        };
+        let formatted = match lumber::format(&source) {
+            Ok(formatted) => formatted,
+            Err(error) => {
+                eprintln!("{}: {}", path.display(), error);
+                success = false;
+                continue;
+            }
+        };
+        if formatted == source {
+            continue;
+        }
+        if opts.check {
+            println!("{} is not formatted", path.display());
+            success = false;
+        } else if let Err(error) = std::fs::write(path, formatted) {
+            eprintln!("{}: {}", path.display(), error);
+            success = false;
+        }
+    }
+    if !success {
+        std::process::exit(1);
+    }
+}
@@ -1,18 +1,51 @@
 //! Implementation of the Lumber @core library, containing important built-in functions required
 //! for the language to operate.
 
-use crate::Lumber;
+use crate::{Lumber, Value};
+use std::cmp::Ordering;
 use std::path::PathBuf;
 
+/// Reads a number as a float.
+fn as_float(value: &Value) -> Option<f64> {
+    match value {
+        Value::Integer(int) => Some(int.to_f64()),
+        Value::Rational(rat) => Some(rat.to_f64()),
+        Value::Float(float) => Some(*float),
+        _ => None,
+    }
+}
+
+/// Reads two numbers as floats, if at least one of them is a float. Arithmetic on integers and
+/// rationals alone remains exact.
+fn floats(lhs: &Value, rhs: &Value) -> Option<(f64, f64)> {
+    if !matches!(lhs, Value::Float(..)) && !matches!(rhs, Value::Float(..)) {
+        return None;
+    }
+    Some((as_float(lhs)?, as_float(rhs)?))
+}
+
+/// Converts a whole float to an integer.
+fn float_integer(float: f64) -> Option<ramp::int::Int> {
+    if !float.is_finite() || float.fract() != 0.0 {
+        return None;
+    }
+    format!("{:.0}", float).parse().ok()
+}
+
 native_function! {
     fn add(lhs, rhs, out) {
         use crate::Value::*;
         match (lhs, rhs, out) {
-            (Some(Integer(lhs)), Some(Integer(rhs)), None)   => answer![lhs, rhs, lhs + rhs],
-            (Some(Integer(lhs)), Some(Rational(rhs)), None)  => answer![lhs, rhs, lhs + rhs],
-            (Some(Rational(lhs)), Some(Integer(rhs)), None)  => answer![lhs, rhs, lhs + rhs],
-            (Some(Rational(lhs)), Some(Rational(rhs)), None) => answer![lhs, rhs, lhs + rhs],
-            (Some(String(lhs)), Some(String(rhs)), None)     => answer![lhs, rhs, lhs + &rhs],
+            (Some(Integer(lhs)), Some(Integer(rhs)), _)   => answer![lhs, rhs, lhs + rhs],
+            (Some(Integer(lhs)), Some(Rational(rhs)), _)  => answer![lhs, rhs, lhs + rhs],
+            (Some(Rational(lhs)), Some(Integer(rhs)), _)  => answer![lhs, rhs, lhs + rhs],
+            (Some(Rational(lhs)), Some(Rational(rhs)), _) => answer![lhs, rhs, lhs + rhs],
+            (Some(String(lhs)), Some(String(rhs)), _)     => answer![lhs, rhs, lhs + &rhs],
+            (Some(lhs), Some(rhs), _) => {
+                if let Some((l, r)) = floats(&lhs, &rhs) {
+                    answer![lhs, rhs, l + r];
+                }
+            }
             _ => {}
         }
     }
@@ -22,10 +55,15 @@ native_function! {
     fn sub(lhs, rhs, out) {
         use crate::Value::*;
         match (lhs, rhs, out) {
-            (Some(Integer(lhs)), Some(Integer(rhs)), None)   => answer![lhs, rhs, lhs - rhs],
-            (Some(Integer(lhs)), Some(Rational(rhs)), None)  => answer![lhs, rhs, ramp::rational::Rational::from(lhs) - rhs],
-            (Some(Rational(lhs)), Some(Integer(rhs)), None)  => answer![lhs, rhs, lhs - ramp::rational::Rational::from(rhs)],
-            (Some(Rational(lhs)), Some(Rational(rhs)), None) => answer![lhs, rhs, lhs - rhs],
+            (Some(Integer(lhs)), Some(Integer(rhs)), _)   => answer![lhs, rhs, lhs - rhs],
+            (Some(Integer(lhs)), Some(Rational(rhs)), _)  => answer![lhs, rhs, ramp::rational::Rational::from(lhs) - rhs],
+            (Some(Rational(lhs)), Some(Integer(rhs)), _)  => answer![lhs, rhs, lhs - ramp::rational::Rational::from(rhs)],
+            (Some(Rational(lhs)), Some(Rational(rhs)), _) => answer![lhs, rhs, lhs - rhs],
+            (Some(lhs), Some(rhs), _) => {
+                if let Some((l, r)) = floats(&lhs, &rhs) {
+                    answer![lhs, rhs, l - r];
+                }
+            }
             _ => {}
         }
     }
@@ -35,10 +73,15 @@ native_function! {
     fn mul(lhs, rhs, out) {
         use crate::Value::*;
         match (lhs, rhs, out) {
-            (Some(Integer(lhs)), Some(Integer(rhs)), None)   => answer![lhs, rhs, lhs * rhs],
-            (Some(Integer(lhs)), Some(Rational(rhs)), None)  => answer![lhs, rhs, lhs * rhs],
-            (Some(Rational(lhs)), Some(Integer(rhs)), None)  => answer![lhs, rhs, lhs * rhs],
-            (Some(Rational(lhs)), Some(Rational(rhs)), None) => answer![lhs, rhs, lhs * rhs],
+            (Some(Integer(lhs)), Some(Integer(rhs)), _)   => answer![lhs, rhs, lhs * rhs],
+            (Some(Integer(lhs)), Some(Rational(rhs)), _)  => answer![lhs, rhs, lhs * rhs],
+            (Some(Rational(lhs)), Some(Integer(rhs)), _)  => answer![lhs, rhs, lhs * rhs],
+            (Some(Rational(lhs)), Some(Rational(rhs)), _) => answer![lhs, rhs, lhs * rhs],
+            (Some(lhs), Some(rhs), _) => {
+                if let Some((l, r)) = floats(&lhs, &rhs) {
+                    answer![lhs, rhs, l * r];
+                }
+            }
             _ => {}
         }
     }
@@ -48,10 +91,15 @@ native_function! {
     fn div(lhs, rhs, out) {
         use crate::Value::*;
         match (lhs, rhs, out) {
-            (Some(Integer(lhs)), Some(Integer(rhs)), None)   => answer![lhs, rhs, lhs / rhs],
-            (Some(Integer(lhs)), Some(Rational(rhs)), None)  => answer![lhs, rhs, lhs / rhs],
-            (Some(Rational(lhs)), Some(Integer(rhs)), None)  => answer![lhs, rhs, lhs / rhs],
-            (Some(Rational(lhs)), Some(Rational(rhs)), None) => answer![lhs, rhs, lhs / rhs],
+            (Some(Integer(lhs)), Some(Integer(rhs)), _)   => answer![lhs, rhs, lhs / rhs],
+            (Some(Integer(lhs)), Some(Rational(rhs)), _)  => answer![lhs, rhs, lhs / rhs],
+            (Some(Rational(lhs)), Some(Integer(rhs)), _)  => answer![lhs, rhs, lhs / rhs],
+            (Some(Rational(lhs)), Some(Rational(rhs)), _) => answer![lhs, rhs, lhs / rhs],
+            (Some(lhs), Some(rhs), _) => {
+                if let Some((l, r)) = floats(&lhs, &rhs) {
+                    answer![lhs, rhs, l / r];
+                }
+            }
             _ => {}
         }
     }
@@ -61,7 +109,12 @@ native_function! {
     fn rem(lhs, rhs, out) {
         use crate::Value::*;
         match (lhs, rhs, out) {
-            (Some(Integer(lhs)), Some(Integer(rhs)), None) => answer![lhs, rhs, lhs % rhs],
+            (Some(Integer(lhs)), Some(Integer(rhs)), _) => answer![lhs, rhs, lhs % rhs],
+            (Some(lhs), Some(rhs), _) => {
+                if let Some((l, r)) = floats(&lhs, &rhs) {
+                    answer![lhs, rhs, l % r];
+                }
+            }
             _ => {}
         }
     }
@@ -71,7 +124,7 @@ native_function! {
     fn bitor(lhs, rhs, out) {
         use crate::Value::*;
         match (lhs, rhs, out) {
-            (Some(Integer(lhs)), Some(Integer(rhs)), None) => answer![lhs, rhs, lhs | rhs],
+            (Some(Integer(lhs)), Some(Integer(rhs)), _) => answer![lhs, rhs, lhs | rhs],
             _ => {}
         }
     }
@@ -81,7 +134,7 @@ native_function! {
     fn bitand(lhs, rhs, out) {
         use crate::Value::*;
         match (lhs, rhs, out) {
-            (Some(Integer(lhs)), Some(Integer(rhs)), None) => answer![lhs, rhs, lhs & rhs],
+            (Some(Integer(lhs)), Some(Integer(rhs)), _) => answer![lhs, rhs, lhs & rhs],
             _ => {}
         }
     }
@@ -91,7 +144,334 @@ native_function! {
     fn bitxor(lhs, rhs, out) {
         use crate::Value::*;
         match (lhs, rhs, out) {
-            (Some(Integer(lhs)), Some(Integer(rhs)), None) => answer![lhs, rhs, lhs ^ rhs],
+            (Some(Integer(lhs)), Some(Integer(rhs)), _) => answer![lhs, rhs, lhs ^ rhs],
+            _ => {}
+        }
+    }
+}
+
+native_function! {
+    fn pow(lhs, rhs, out) {
+        use crate::Value::*;
+        match (lhs, rhs, out) {
+            (Some(Integer(lhs)), Some(Integer(rhs)), _) => {
+                if let Some(exp) = as_exponent(&[&lhs], &rhs) {
+                    if rhs >= 0 {
+                        answer![lhs, rhs, lhs.pow(exp)];
+                    } else if lhs != 0 {
+                        answer![lhs, rhs, ramp::rational::Rational::new(1.into(), lhs.pow(exp))];
+                    }
+                }
+            }
+            (Some(Rational(lhs)), Some(Integer(rhs)), _) => {
+                let (numer, denom) = lhs.clone().into_parts();
+                if let Some(exp) = as_exponent(&[&numer, &denom], &rhs) {
+                    if rhs >= 0 {
+                        answer![lhs, rhs, ramp::rational::Rational::new(numer.pow(exp), denom.pow(exp))];
+                    } else if numer != 0 {
+                        answer![lhs, rhs, ramp::rational::Rational::new(denom.pow(exp), numer.pow(exp))];
+                    }
+                }
+            }
+            (Some(lhs), Some(rhs), _) => {
+                if let Some((l, r)) = floats(&lhs, &rhs) {
+                    answer![lhs, rhs, l.powf(r)];
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+native_function! {
+    fn abs(value, out) {
+        use crate::Value::*;
+        match (value, out) {
+            (Some(Integer(value)), _) => answer![value, value.clone().abs()],
+            (Some(Rational(value)), _) => answer![value, value.clone().abs()],
+            (Some(Float(value)), _) => answer![value, value.abs()],
+            _ => {}
+        }
+    }
+}
+
+native_function! {
+    fn neg(value, out) {
+        use crate::Value::*;
+        match (value, out) {
+            (Some(Integer(value)), _) => answer![value, -value.clone()],
+            (Some(Rational(value)), _) => answer![value, -value.clone()],
+            (Some(Float(value)), _) => answer![value, -value],
+            _ => {}
+        }
+    }
+}
+
+native_function! {
+    fn min(lhs, rhs, out) {
+        match (lhs, rhs, out) {
+            (Some(lhs), Some(rhs), _) => match compare(&lhs, &rhs) {
+                Some(Ordering::Greater) => answer![lhs, rhs, rhs],
+                Some(..) => answer![lhs, rhs, lhs],
+                None => {}
+            },
+            _ => {}
+        }
+    }
+}
+
+native_function! {
+    fn max(lhs, rhs, out) {
+        match (lhs, rhs, out) {
+            (Some(lhs), Some(rhs), _) => match compare(&lhs, &rhs) {
+                Some(Ordering::Less) => answer![lhs, rhs, rhs],
+                Some(..) => answer![lhs, rhs, lhs],
+                None => {}
+            },
+            _ => {}
+        }
+    }
+}
+
+native_function! {
+    fn gcd(lhs, rhs, out) {
+        use crate::Value::*;
+        match (lhs, rhs, out) {
+            (Some(Integer(lhs)), Some(Integer(rhs)), _) => answer![lhs, rhs, lhs.gcd(&rhs).abs()],
+            _ => {}
+        }
+    }
+}
+
+native_function! {
+    fn lcm(lhs, rhs, out) {
+        use crate::Value::*;
+        match (lhs, rhs, out) {
+            (Some(Integer(lhs)), Some(Integer(rhs)), _) if lhs == 0 || rhs == 0 => answer![lhs, rhs, ramp::int::Int::zero()],
+            (Some(Integer(lhs)), Some(Integer(rhs)), _) => answer![lhs, rhs, lhs.lcm(&rhs).abs()],
+            _ => {}
+        }
+    }
+}
+
+/// Divides a rational into its integer part, rounded towards zero, and whether there is a
+/// remainder.
+fn truncated(value: &ramp::rational::Rational) -> (ramp::int::Int, bool) {
+    let (numer, denom) = value.clone().into_parts();
+    let remainder = &numer % &denom != 0;
+    (numer / denom, remainder)
+}
+
+macro_rules! rounding {
+    ($name:ident, $float:path, |$value:ident| $round:expr) => {
+        native_function! {
+            fn $name(value, out) {
+                use crate::Value::*;
+                match (value, out) {
+                    (Some(Integer(value)), _) => answer![value, value],
+                    (Some(Rational($value)), _) => answer![$value, $round],
+                    (Some(Float(value)), _) => {
+                        if let Some(int) = float_integer($float(value)) {
+                            answer![value, int];
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+    };
+}
+
+rounding!(floor, f64::floor, |value| match truncated(&value) {
+    (int, true) if value.sign() < 0 => int - 1,
+    (int, _) => int,
+});
+rounding!(ceil, f64::ceil, |value| match truncated(&value) {
+    (int, true) if value.sign() > 0 => int + 1,
+    (int, _) => int,
+});
+rounding!(round, f64::round, |value| value.clone().round());
+rounding!(truncate, f64::trunc, |value| truncated(&value).0);
+
+native_function! {
+    fn numerator(value, out) {
+        use crate::Value::*;
+        match (value, out) {
+            (Some(Integer(value)), _) => answer![value, value],
+            (Some(Rational(value)), _) => answer![value, value.clone().into_parts().0],
+            _ => {}
+        }
+    }
+}
+
+native_function! {
+    fn denominator(value, out) {
+        use crate::Value::*;
+        match (value, out) {
+            (Some(Integer(value)), _) => answer![value, ramp::int::Int::one()],
+            (Some(Rational(value)), _) => answer![value, value.clone().into_parts().1],
+            _ => {}
+        }
+    }
+}
+
+native_function! {
+    // Only rationals which are whole numbers can be converted to integers. Use one of the
+    // rounding functions for the others.
+    fn integer(value, out) {
+        use crate::Value::*;
+        match (value, out) {
+            (Some(Integer(value)), _) => answer![value, value],
+            (Some(Rational(value)), _) => {
+                if let (int, false) = truncated(&value) {
+                    answer![value, int];
+                }
+            }
+            (Some(Float(value)), _) => {
+                if let Some(int) = float_integer(value) {
+                    answer![value, int];
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+native_function! {
+    fn float(value, out) {
+        match (value, out) {
+            (Some(value), _) => {
+                if let Some(float) = as_float(&value) {
+                    answer![value, float];
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+native_function! {
+    fn rational(value, out) {
+        use crate::Value::*;
+        match (value, out) {
+            (Some(Integer(value)), _) => answer![value, ramp::rational::Rational::from(value.clone())],
+            (Some(Rational(value)), _) => answer![value, value],
+            _ => {}
+        }
+    }
+}
+
+/// Compares two values, if they are of comparable types. Integers and rationals may be
+/// compared with each other.
+fn compare(lhs: &Value, rhs: &Value) -> Option<Ordering> {
+    use crate::Value::*;
+    match (lhs, rhs) {
+        (Integer(lhs), Integer(rhs)) => lhs.partial_cmp(rhs),
+        (Integer(lhs), Rational(rhs)) => {
+            ramp::rational::Rational::from(lhs.clone()).partial_cmp(rhs)
+        }
+        (Rational(lhs), Integer(rhs)) => {
+            lhs.partial_cmp(&ramp::rational::Rational::from(rhs.clone()))
+        }
+        (Rational(lhs), Rational(rhs)) => lhs.partial_cmp(rhs),
+        (String(lhs), String(rhs)) => lhs.partial_cmp(rhs),
+        _ => {
+            let (lhs, rhs) = floats(lhs, rhs)?;
+            lhs.partial_cmp(&rhs)
+        }
+    }
+}
+
+/// Checks whether two values are equal. Numbers are equal if they have the same value, even if
+/// they are of different types. As usual, `NaN` is not equal to anything.
+fn equal(lhs: &Value, rhs: &Value) -> bool {
+    match compare(lhs, rhs) {
+        Some(ordering) => ordering == Ordering::Equal,
+        None if floats(lhs, rhs).is_some() => false,
+        None => lhs == rhs,
+    }
+}
+
+native_function! {
+    fn eq(lhs, rhs, out) {
+        match (lhs, rhs, out) {
+            (Some(lhs), Some(rhs), None) => {
+                let equal = equal(&lhs, &rhs);
+                answer![lhs, rhs, Value::bool(equal)];
+            }
+            _ => {}
+        }
+    }
+}
+
+native_function! {
+    fn neq(lhs, rhs, out) {
+        match (lhs, rhs, out) {
+            (Some(lhs), Some(rhs), None) => {
+                let equal = equal(&lhs, &rhs);
+                answer![lhs, rhs, Value::bool(!equal)];
+            }
+            _ => {}
+        }
+    }
+}
+
+macro_rules! comparison {
+    ($name:ident, $($ordering:ident)|+) => {
+        native_function! {
+            fn $name(lhs, rhs, out) {
+                match (lhs, rhs, out) {
+                    (Some(lhs), Some(rhs), None) => {
+                        if let Some(ordering) = compare(&lhs, &rhs) {
+                            answer![lhs, rhs, Value::bool(matches!(ordering, $(Ordering::$ordering)|+))];
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+    };
+}
+
+comparison!(lt_op, Less);
+comparison!(gt_op, Greater);
+comparison!(leq_op, Less | Equal);
+comparison!(geq_op, Greater | Equal);
+
+native_function! {
+    fn and(lhs, rhs, out) {
+        match (lhs, rhs, out) {
+            (Some(lhs), Some(rhs), _) => {
+                if let (Some(l), Some(r)) = (lhs.as_bool(), rhs.as_bool()) {
+                    answer![lhs, rhs, Value::bool(l && r)];
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+native_function! {
+    fn or(lhs, rhs, out) {
+        match (lhs, rhs, out) {
+            (Some(lhs), Some(rhs), _) => {
+                if let (Some(l), Some(r)) = (lhs.as_bool(), rhs.as_bool()) {
+                    answer![lhs, rhs, Value::bool(l || r)];
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+native_function! {
+    fn not(value, out) {
+        match (value, out) {
+            (Some(value), _) => {
+                if let Some(b) = value.as_bool() {
+                    answer![value, Value::bool(!b)];
+                }
+            }
             _ => {}
         }
     }
@@ -160,6 +540,700 @@ native_function! {
     }
 }
 
+native_function! {
+    fn var(value) {
+        match value {
+            Some(_value) => {}
+            None => answer![_],
+        }
+    }
+}
+
+native_function! {
+    fn ground(value) {
+        match value {
+            Some(value) if is_ground(&value) => answer![value],
+            _ => {}
+        }
+    }
+}
+
+/// Checks whether a value is fully bound, including all of its contents.
+fn is_ground(value: &crate::Value) -> bool {
+    use crate::Value::*;
+    match value {
+        List(list) => list.complete && list.values.iter().all(is_ground_field),
+        Record(record) => record.complete && record.fields.values().all(is_ground_field),
+        Map(map) => map.complete && map.entries.iter().all(|(_, value)| is_ground_field(value)),
+        Struct(structure) => match &structure.contents {
+            Some(contents) => is_ground_field(contents),
+            None => true,
+        },
+        _ => true,
+    }
+}
+
+fn is_ground_field(value: &Option<crate::Value>) -> bool {
+    value.as_ref().map(is_ground).unwrap_or(false)
+}
+
+native_function! {
+    fn struct_name(value, name) {
+        use crate::Value::*;
+        match value {
+            Some(Struct(structure)) => answer![Struct(structure.clone()), Struct(crate::Struct::atom(structure.name()))],
+            _ => {}
+        }
+    }
+}
+
+native_function! {
+    fn r#struct(value, name, contents) {
+        use crate::Value::*;
+        match (value, name) {
+            (Some(Struct(structure)), _) if !structure.is_atom() => {
+                let contents = structure.contents.clone().unwrap();
+                yield vec![
+                    Some(Struct(structure.clone())),
+                    Some(Struct(crate::Struct::atom(structure.name()))),
+                    *contents,
+                ];
+            }
+            (None, Some(Struct(name))) if name.is_atom() => {
+                let structure = crate::Struct::new(name.name(), contents.clone());
+                yield vec![Some(Struct(structure)), Some(Struct(name)), contents];
+            }
+            _ => {}
+        }
+    }
+}
+
+/// The fields of a record, sorted by key.
+fn sorted_fields(record: &crate::Record) -> Vec<(&crate::ast::Atom, &Option<Value>)> {
+    let mut fields = record.fields.iter().collect::<Vec<_>>();
+    fields.sort_by(|(lhs, _), (rhs, _)| lhs.as_ref().cmp(rhs.as_ref()));
+    fields
+}
+
+/// Reads a record key, which may be written as either an atom or a string.
+fn as_key(value: &Value) -> Option<crate::ast::Atom> {
+    match value {
+        Value::Struct(key) if key.is_atom() => Some(key.name.clone()),
+        Value::String(key) => Some(crate::ast::Atom::from(key.as_str())),
+        _ => None,
+    }
+}
+
+fn key_value(key: &crate::ast::Atom) -> Value {
+    Value::Struct(crate::Struct::atom(key.as_ref()))
+}
+
+native_function! {
+    fn record_pairs(record, pairs) {
+        use crate::Value::*;
+        match (record, pairs) {
+            (Some(Record(record)), _) => {
+                let pairs = sorted_fields(&record)
+                    .into_iter()
+                    .map(|(key, value)| {
+                        let key = Some(Struct(crate::Struct::atom(key.as_ref())));
+                        Some(List(crate::List::new(vec![key, value.clone()], true)))
+                    })
+                    .collect();
+                let pairs = crate::List::new(pairs, record.complete);
+                answer![Record(record), List(pairs)];
+            }
+            (None, Some(List(pairs))) if pairs.complete => {
+                let fields = pairs
+                    .values
+                    .iter()
+                    .map(|pair| match pair {
+                        Some(List(pair)) if pair.complete && pair.len() == 2 => match &pair[0] {
+                            Some(Struct(key)) if key.is_atom() => {
+                                Some((key.name.clone(), pair[1].clone()))
+                            }
+                            _ => None,
+                        },
+                        _ => None,
+                    })
+                    .collect::<Option<_>>();
+                if let Some(fields) = fields {
+                    answer![Record(crate::Record::new(fields, true)), List(pairs)];
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// The largest number of bits that the result of raising an integer to a power may have, as
+/// estimated from the sizes of the base and exponent. Larger powers are not computed.
+const MAX_POW_BITS: usize = 1 << 16;
+
+/// Reads the magnitude of an integer as an exponent to raise the given integers to, if none of
+/// the results could be larger than [`MAX_POW_BITS`][].
+fn as_exponent(bases: &[&ramp::int::Int], exponent: &ramp::int::Int) -> Option<usize> {
+    let exponent = as_index(&exponent.clone().abs())?;
+    let bits = bases.iter().map(|base| base.bit_length() as usize).max()?;
+    match bits.checked_mul(exponent) {
+        Some(size) if size <= MAX_POW_BITS => Some(exponent),
+        _ => None,
+    }
+}
+
+/// Reads an integer as an index or length into a string or list.
+fn as_index(value: &ramp::int::Int) -> Option<usize> {
+    if value.sign() < 0 || value.bit_length() > 32 {
+        return None;
+    }
+    Some(usize::from(value))
+}
+
+/// Reads a complete list of strings.
+fn as_strings(list: &crate::List) -> Option<Vec<String>> {
+    if !list.complete {
+        return None;
+    }
+    list.iter()
+        .map(|value| match value {
+            Some(Value::String(string)) => Some(string.clone()),
+            _ => None,
+        })
+        .collect()
+}
+
+/// The byte offset of each character boundary of a string, including the end.
+fn boundaries(string: &str) -> Vec<usize> {
+    string
+        .char_indices()
+        .map(|(i, _)| i)
+        .chain(std::iter::once(string.len()))
+        .collect()
+}
+
+/// Parses a number written as an integer (`-12`), decimal (`1.5`) or fraction (`3/4`).
+fn parse_number(string: &str) -> Option<Value> {
+    use ramp::{int::Int, rational::Rational};
+    let digits = |part: &str| -> Option<Int> {
+        let unsigned = part.strip_prefix('-').unwrap_or(part);
+        if unsigned.is_empty() || !unsigned.chars().all(|ch| ch.is_ascii_digit()) {
+            return None;
+        }
+        part.parse().ok()
+    };
+    if let Some((unit, fractional)) = string.split_once('.') {
+        if fractional.is_empty() || fractional.starts_with('-') {
+            return None;
+        }
+        let denominator = Int::from(10).pow(fractional.len());
+        let numerator = digits(&format!("{}{}", unit, fractional))?;
+        return Some(Value::Rational(Rational::new(numerator, denominator)));
+    }
+    if let Some((numerator, denominator)) = string.split_once('/') {
+        let numerator = digits(numerator)?;
+        let denominator = digits(denominator)?;
+        if denominator.sign() <= 0 {
+            return None;
+        }
+        return Some(Value::Rational(Rational::new(numerator, denominator)));
+    }
+    digits(string).map(Value::Integer)
+}
+
+native_function! {
+    fn string_length(string, length) {
+        use crate::Value::*;
+        match (string, length) {
+            (Some(String(string)), _) => {
+                let length = ramp::int::Int::from(string.chars().count());
+                answer![string, length];
+            }
+            _ => {}
+        }
+    }
+}
+
+native_function! {
+    fn string_concat(lhs, rhs, out) {
+        use crate::Value::*;
+        match (lhs, rhs, out) {
+            (Some(String(lhs)), Some(String(rhs)), _) => answer![lhs, rhs, lhs.clone() + &rhs],
+            (_, _, Some(String(out))) => {
+                for i in boundaries(&out) {
+                    answer![&out[..i], &out[i..], out];
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+native_function! {
+    fn string_substring(string, start, length, substring) {
+        use crate::Value::*;
+        match (string, start, length, substring) {
+            (Some(String(string)), Some(Integer(start)), Some(Integer(length)), _) => {
+                let chars = string.chars().count();
+                if let (Some(i), Some(n)) = (as_index(&start), as_index(&length)) {
+                    if i + n <= chars {
+                        let substring = string.chars().skip(i).take(n).collect::<std::string::String>();
+                        answer![string, start, length, substring];
+                    }
+                }
+            }
+            (Some(String(string)), _, _, Some(String(substring))) => {
+                let length = ramp::int::Int::from(substring.chars().count());
+                for (i, offset) in boundaries(&string).into_iter().enumerate() {
+                    if string[offset..].starts_with(substring.as_str()) {
+                        answer![string, ramp::int::Int::from(i), length, substring];
+                    }
+                }
+            }
+            (Some(String(string)), _, _, None) => {
+                let boundaries = boundaries(&string);
+                let substrings = boundaries
+                    .iter()
+                    .enumerate()
+                    .flat_map(|(i, &start)| {
+                        boundaries[i..].iter().enumerate().map(move |(n, &end)| (i, n, start, end))
+                    })
+                    .collect::<Vec<_>>();
+                for (i, n, start, end) in substrings {
+                    answer![string, ramp::int::Int::from(i), ramp::int::Int::from(n), &string[start..end]];
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+native_function! {
+    fn string_index_of(string, substring, index) {
+        use crate::Value::*;
+        match (string, substring, index) {
+            (Some(String(string)), Some(String(substring)), _) => {
+                for (i, offset) in boundaries(&string).into_iter().enumerate() {
+                    if string[offset..].starts_with(substring.as_str()) {
+                        answer![string, substring, ramp::int::Int::from(i)];
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+native_function! {
+    fn string_split(string, separator, parts) {
+        use crate::Value::*;
+        match (string, separator, parts) {
+            (Some(String(string)), Some(String(separator)), _) if !separator.is_empty() => {
+                let parts = string.split(separator.as_str()).map(str::to_owned).collect::<Vec<_>>();
+                answer![string, separator, parts];
+            }
+            (None, Some(String(separator)), Some(List(parts))) => {
+                if let Some(strings) = as_strings(&parts) {
+                    answer![strings.join(&separator), separator, List(parts)];
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+native_function! {
+    fn string_join(parts, separator, string) {
+        use crate::Value::*;
+        match (parts, separator, string) {
+            (Some(List(parts)), Some(String(separator)), _) => {
+                if let Some(strings) = as_strings(&parts) {
+                    answer![List(parts), separator, strings.join(&separator)];
+                }
+            }
+            (None, Some(String(separator)), Some(String(string))) if !separator.is_empty() => {
+                let parts = string.split(separator.as_str()).map(str::to_owned).collect::<Vec<_>>();
+                answer![parts, separator, string];
+            }
+            _ => {}
+        }
+    }
+}
+
+macro_rules! string_map {
+    ($name:ident, $method:ident) => {
+        native_function! {
+            fn $name(string, out) {
+                use crate::Value::*;
+                match (string, out) {
+                    (Some(String(string)), _) => answer![string, string.$method().to_owned()],
+                    _ => {}
+                }
+            }
+        }
+    };
+}
+
+string_map!(string_upper, to_uppercase);
+string_map!(string_lower, to_lowercase);
+string_map!(string_trim, trim);
+string_map!(string_trim_start, trim_start);
+string_map!(string_trim_end, trim_end);
+
+native_function! {
+    fn string_starts_with(string, prefix) {
+        use crate::Value::*;
+        match (string, prefix) {
+            (Some(String(string)), Some(String(prefix))) if string.starts_with(prefix.as_str()) => answer![string, prefix],
+            _ => {}
+        }
+    }
+}
+
+native_function! {
+    fn string_ends_with(string, suffix) {
+        use crate::Value::*;
+        match (string, suffix) {
+            (Some(String(string)), Some(String(suffix))) if string.ends_with(suffix.as_str()) => answer![string, suffix],
+            _ => {}
+        }
+    }
+}
+
+native_function! {
+    fn string_char_code(ch, code) {
+        use crate::Value::*;
+        match (ch, code) {
+            (Some(String(ch)), _) if ch.chars().count() == 1 => {
+                let code = ramp::int::Int::from(ch.chars().next().unwrap() as u32);
+                answer![ch, code];
+            }
+            (None, Some(Integer(code))) => {
+                if let Some(ch) = as_index(&code).and_then(|code| std::char::from_u32(code as u32)) {
+                    answer![ch.to_string(), code];
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+native_function! {
+    fn string_chars(string, chars) {
+        use crate::Value::*;
+        match (string, chars) {
+            (Some(String(string)), _) => {
+                let chars = string.chars().map(|ch| ch.to_string()).collect::<Vec<_>>();
+                answer![string, chars];
+            }
+            (None, Some(List(chars))) => {
+                if let Some(strings) = as_strings(&chars) {
+                    if strings.iter().all(|ch| ch.chars().count() == 1) {
+                        answer![strings.concat(), List(chars)];
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+native_function! {
+    fn string_codes(string, codes) {
+        use crate::Value::*;
+        match (string, codes) {
+            (Some(String(string)), _) => {
+                let codes = string.chars().map(|ch| ramp::int::Int::from(ch as u32)).collect::<Vec<_>>();
+                answer![string, codes];
+            }
+            (None, Some(List(codes))) if codes.complete => {
+                let string = codes
+                    .iter()
+                    .map(|code| match code {
+                        Some(Integer(code)) => as_index(code).and_then(|code| std::char::from_u32(code as u32)),
+                        _ => None,
+                    })
+                    .collect::<Option<std::string::String>>();
+                if let Some(string) = string {
+                    answer![string, List(codes)];
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+native_function! {
+    fn string_number(string, number) {
+        use crate::Value::*;
+        match (string, number) {
+            (Some(String(string)), _) => {
+                if let Some(number) = parse_number(&string) {
+                    answer![string, number];
+                }
+            }
+            (None, Some(Integer(number))) => answer![number.to_string(), number],
+            (None, Some(Rational(number))) => answer![number.to_string(), number],
+            _ => {}
+        }
+    }
+}
+
+native_function! {
+    // A key that is not in an incomplete record may still be in the unknown part of the record,
+    // so the field cannot be found.
+    fn record_get(record, key, value) {
+        use crate::Value::*;
+        match (record, key, value) {
+            (Some(Record(record)), Some(key), _) => {
+                if let Some(field) = as_key(&key).and_then(|name| record.fields.get(&name)) {
+                    yield vec![Some(Record(record.clone())), Some(key), field.clone()];
+                }
+            }
+            (Some(Record(record)), None, _) => {
+                let fields = sorted_fields(&record)
+                    .into_iter()
+                    .map(|(key, value)| (key_value(key), value.clone()))
+                    .collect::<Vec<_>>();
+                for (key, field) in fields {
+                    yield vec![Some(Record(record.clone())), Some(key), field];
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+native_function! {
+    fn record_put(record, key, value, out) {
+        use crate::Value::*;
+        match (record, key, out) {
+            (Some(Record(record)), Some(key), _) => {
+                if let Some(name) = as_key(&key) {
+                    let mut updated = record.clone();
+                    updated.fields.insert(name, value.clone());
+                    yield vec![Some(Record(record)), Some(key), value, Some(Record(updated))];
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+native_function! {
+    // Deleting a key that is not in a complete record leaves the record unchanged. If the record
+    // is incomplete, the key may be in the unknown part, so it cannot be deleted.
+    fn record_delete(record, key, out) {
+        use crate::Value::*;
+        match (record, key, out) {
+            (Some(Record(record)), Some(key), _) => {
+                if let Some(name) = as_key(&key) {
+                    let mut updated = record.clone();
+                    if updated.fields.remove(&name).is_some() || record.complete {
+                        answer![Record(record), key, Record(updated)];
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+native_function! {
+    fn record_keys(record, keys) {
+        use crate::Value::*;
+        match (record, keys) {
+            (Some(Record(record)), _) => {
+                let keys = sorted_fields(&record)
+                    .into_iter()
+                    .map(|(key, _)| Some(key_value(key)))
+                    .collect();
+                let keys = crate::List::new(keys, record.complete);
+                answer![Record(record), List(keys)];
+            }
+            _ => {}
+        }
+    }
+}
+
+native_function! {
+    fn record_values(record, values) {
+        use crate::Value::*;
+        match (record, values) {
+            (Some(Record(record)), _) => {
+                let values = sorted_fields(&record)
+                    .into_iter()
+                    .map(|(_, value)| value.clone())
+                    .collect();
+                let values = crate::List::new(values, record.complete);
+                answer![Record(record), List(values)];
+            }
+            _ => {}
+        }
+    }
+}
+
+native_function! {
+    // Fields of the second record replace those of the first. The result is only complete if
+    // both records are.
+    fn record_merge(lhs, rhs, out) {
+        use crate::Value::*;
+        match (lhs, rhs, out) {
+            (Some(Record(lhs)), Some(Record(rhs)), _) => {
+                let mut fields = lhs.fields.clone();
+                fields.extend(rhs.fields.clone());
+                let merged = crate::Record::new(fields, lhs.complete && rhs.complete);
+                answer![Record(lhs), Record(rhs), Record(merged)];
+            }
+            _ => {}
+        }
+    }
+}
+
+native_function! {
+    // As with records, a key that is not in an incomplete map may still be in the unknown part.
+    fn map_get(map, key, value) {
+        use crate::Value::*;
+        match (map, key, value) {
+            (Some(Map(map)), Some(key), _) => {
+                if let Some(entry) = map.get(&key) {
+                    yield vec![Some(Map(map.clone())), Some(key), entry.clone()];
+                }
+            }
+            (Some(Map(map)), None, _) => {
+                let entries = map.entries.clone();
+                for (key, entry) in entries {
+                    yield vec![Some(Map(map.clone())), Some(key), entry];
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+native_function! {
+    fn map_put(map, key, value, out) {
+        use crate::Value::*;
+        match (map, key, out) {
+            (Some(Map(map)), Some(key), _) if is_ground(&key) => {
+                let mut updated = map.clone();
+                updated.set(key.clone(), value.clone());
+                yield vec![Some(Map(map)), Some(key), value, Some(Map(updated))];
+            }
+            _ => {}
+        }
+    }
+}
+
+native_function! {
+    fn map_delete(map, key, out) {
+        use crate::Value::*;
+        match (map, key, out) {
+            (Some(Map(map)), Some(key), _) => {
+                let mut updated = map.clone();
+                if updated.remove(&key).is_some() || map.complete {
+                    answer![Map(map), key, Map(updated)];
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+native_function! {
+    fn map_keys(map, keys) {
+        use crate::Value::*;
+        match (map, keys) {
+            (Some(Map(map)), _) => {
+                let keys = map.entries.iter().map(|(key, _)| Some(key.clone())).collect();
+                let keys = crate::List::new(keys, map.complete);
+                answer![Map(map), List(keys)];
+            }
+            _ => {}
+        }
+    }
+}
+
+native_function! {
+    fn map_values(map, values) {
+        use crate::Value::*;
+        match (map, values) {
+            (Some(Map(map)), _) => {
+                let values = map.entries.iter().map(|(_, value)| value.clone()).collect();
+                let values = crate::List::new(values, map.complete);
+                answer![Map(map), List(values)];
+            }
+            _ => {}
+        }
+    }
+}
+
+native_function! {
+    fn map_pairs(map, pairs) {
+        use crate::Value::*;
+        match (map, pairs) {
+            (Some(Map(map)), _) => {
+                let pairs = map
+                    .entries
+                    .iter()
+                    .map(|(key, value)| {
+                        Some(List(crate::List::new(vec![Some(key.clone()), value.clone()], true)))
+                    })
+                    .collect();
+                let pairs = crate::List::new(pairs, map.complete);
+                answer![Map(map), List(pairs)];
+            }
+            // Later pairs replace earlier ones with the same key.
+            (None, Some(List(pairs))) if pairs.complete => {
+                let map = pairs
+                    .values
+                    .iter()
+                    .try_fold(crate::Map::default(), |mut map, pair| match pair {
+                        Some(List(pair)) if pair.complete && pair.len() == 2 => match &pair[0] {
+                            Some(key) if is_ground(key) => {
+                                map.set(key.clone(), pair[1].clone());
+                                Some(map)
+                            }
+                            _ => None,
+                        },
+                        _ => None,
+                    });
+                if let Some(map) = map {
+                    answer![Map(map), List(pairs)];
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+native_function! {
+    // Entries of the second map replace those of the first. The result is only complete if both
+    // maps are.
+    fn map_merge(lhs, rhs, out) {
+        use crate::Value::*;
+        match (lhs, rhs, out) {
+            (Some(Map(lhs)), Some(Map(rhs)), _) => {
+                let mut merged = lhs.clone();
+                for (key, value) in rhs.iter() {
+                    merged.set(key.clone(), value.clone());
+                }
+                merged.complete = lhs.complete && rhs.complete;
+                answer![Map(lhs), Map(rhs), Map(merged)];
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Calls to lambdas, clause inspection and copying terms are intercepted by the interpreter, as
+/// they require access to the rest of the program or to the variables of the binding. This
+/// function is bound only so that those handles are defined.
+fn intercepted(
+    _: Vec<Option<crate::Value>>,
+) -> Box<dyn Iterator<Item = Vec<Option<crate::Value>>>> {
+    unreachable!("intercepted natives should have been handled by the interpreter")
+}
+
 thread_local! {
     pub(crate) static LIB: Lumber<'static> = Lumber::builder()
         .core(false)
@@ -171,11 +1245,81 @@ thread_local! {
         .bind("bitor/3", bitor)
         .bind("bitand/3", bitand)
         .bind("bitxor/3", bitxor)
+        .bind("pow/3", pow)
+        .bind("abs/2", abs)
+        .bind("neg/2", neg)
+        .bind("min/3", min)
+        .bind("max/3", max)
+        .bind("gcd/3", gcd)
+        .bind("lcm/3", lcm)
+        .bind("floor/2", floor)
+        .bind("ceil/2", ceil)
+        .bind("round/2", round)
+        .bind("truncate/2", truncate)
+        .bind("numerator/2", numerator)
+        .bind("denominator/2", denominator)
+        .bind("integer/2", integer)
+        .bind("float/2", float)
+        .bind("rational/2", rational)
+        .bind("eq/3", eq)
+        .bind("neq/3", neq)
+        .bind("lt/3", lt_op)
+        .bind("gt/3", gt_op)
+        .bind("leq/3", leq_op)
+        .bind("geq/3", geq_op)
+        .bind("and/3", and)
+        .bind("or/3", or)
+        .bind("not/2", not)
+        .bind("call/1", intercepted)
+        .bind("call/2", intercepted)
+        .bind("call/3", intercepted)
+        .bind("call/4", intercepted)
+        .bind("call/5", intercepted)
+        .bind("call/6", intercepted)
+        .bind("call/7", intercepted)
+        .bind("call/8", intercepted)
         .bind("leq/2", leq)
         .bind("geq/2", geq)
         .bind("lt/2", lt)
         .bind("gt/2", gt)
         .bind("print/1", print)
+        .bind("var/1", var)
+        .bind("ground/1", ground)
+        .bind("copy_term/2", intercepted)
+        .bind("struct_name/2", struct_name)
+        .bind("struct/3", r#struct)
+        .bind("clause/2", intercepted)
+        .bind("record::get/3", record_get)
+        .bind("record::put/4", record_put)
+        .bind("record::delete/3", record_delete)
+        .bind("record::keys/2", record_keys)
+        .bind("record::values/2", record_values)
+        .bind("record::pairs/2", record_pairs)
+        .bind("record::merge/3", record_merge)
+        .bind("map::get/3", map_get)
+        .bind("map::put/4", map_put)
+        .bind("map::delete/3", map_delete)
+        .bind("map::keys/2", map_keys)
+        .bind("map::values/2", map_values)
+        .bind("map::pairs/2", map_pairs)
+        .bind("map::merge/3", map_merge)
+        .bind("string::length/2", string_length)
+        .bind("string::concat/3", string_concat)
+        .bind("string::substring/4", string_substring)
+        .bind("string::index_of/3", string_index_of)
+        .bind("string::split/3", string_split)
+        .bind("string::join/3", string_join)
+        .bind("string::upper/2", string_upper)
+        .bind("string::lower/2", string_lower)
+        .bind("string::trim/2", string_trim)
+        .bind("string::trim_start/2", string_trim_start)
+        .bind("string::trim_end/2", string_trim_end)
+        .bind("string::starts_with/2", string_starts_with)
+        .bind("string::ends_with/2", string_ends_with)
+        .bind("string::char_code/2", string_char_code)
+        .bind("string::chars/2", string_chars)
+        .bind("string::codes/2", string_codes)
+        .bind("string::number/2", string_number)
         .build(PathBuf::from(file!()).parent().unwrap(), include_str!("core.lumber"))
         .unwrap();
 }
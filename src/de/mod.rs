@@ -1,4 +1,4 @@
-use crate::{Error, Record, Struct, Value};
+use crate::{Error, Map, Record, Struct, Value};
 use serde::de::{self, Deserialize, DeserializeSeed, IntoDeserializer, Visitor};
 use std::ops::Index;
 
@@ -40,17 +40,17 @@ macro_rules! deserialize_int {
     };
 }
 
-macro_rules! deserialize_rat {
+macro_rules! deserialize_float {
     ($name:ident, $visit:ident, $t:ty) => {
         fn $name<V>(self, visitor: V) -> crate::Result<V::Value>
         where
             V: Visitor<'de>,
         {
-            let rat = self
-                .input
-                .and_then(Value::as_rational)
-                .ok_or_else(|| Error::de("expected rational"))?;
-            visitor.$visit(rat.to_f64() as $t)
+            match self.input {
+                Some(Value::Float(float)) => visitor.$visit(*float as $t),
+                Some(Value::Rational(rat)) => visitor.$visit(rat.to_f64() as $t),
+                _ => Err(Error::de("expected float or rational")),
+            }
         }
     };
 }
@@ -68,15 +68,12 @@ impl<'de, 'a> de::Deserializer<'de> for &'a Deserializer<'de> {
         };
         match value {
             Value::Integer(..) => self.deserialize_i64(visitor),
-            Value::Rational(..) => self.deserialize_f64(visitor),
+            Value::Rational(..) | Value::Float(..) => self.deserialize_f64(visitor),
             Value::String(..) => self.deserialize_string(visitor),
             Value::List(..) => self.deserialize_seq(visitor),
-            Value::Struct(st) if st.is_atom() => match st.as_atom().unwrap() {
-                "true" | "false" => self.deserialize_bool(visitor),
-                _ => Err(Error::de("cannot deserialize arbitrary structs")),
-            },
+            value if value.is_bool() => self.deserialize_bool(visitor),
             Value::Struct(..) => Err(Error::de("cannot deserialize arbitrary structs")),
-            Value::Record(..) => self.deserialize_map(visitor),
+            Value::Record(..) | Value::Map(..) => self.deserialize_map(visitor),
             Value::Any(..) => Err(Error::de("cannot deserialize an `Any` value")),
         }
     }
@@ -85,15 +82,9 @@ impl<'de, 'a> de::Deserializer<'de> for &'a Deserializer<'de> {
     where
         V: Visitor<'de>,
     {
-        let b = self
-            .input
-            .and_then(Value::as_struct)
-            .and_then(Struct::as_atom)
-            .ok_or_else(|| Error::de("expected boolean"))?;
-        match b {
-            "true" => visitor.visit_bool(true),
-            "false" => visitor.visit_bool(false),
-            _ => Err(Error::de("expected boolean")),
+        match self.input.and_then(Value::as_bool) {
+            Some(value) => visitor.visit_bool(value),
+            None => Err(Error::de("expected boolean")),
         }
     }
 
@@ -108,8 +99,8 @@ impl<'de, 'a> de::Deserializer<'de> for &'a Deserializer<'de> {
     deserialize_int!(deserialize_u64, visit_u64);
     deserialize_int!(deserialize_u128, visit_u128);
 
-    deserialize_rat!(deserialize_f32, visit_f32, f32);
-    deserialize_rat!(deserialize_f64, visit_f64, f64);
+    deserialize_float!(deserialize_f32, visit_f32, f32);
+    deserialize_float!(deserialize_f64, visit_f64, f64);
 
     fn deserialize_char<V>(self, visitor: V) -> crate::Result<V::Value>
     where
@@ -295,14 +286,17 @@ impl<'de, 'a> de::Deserializer<'de> for &'a Deserializer<'de> {
     where
         V: Visitor<'de>,
     {
-        let record = self
-            .input
-            .and_then(Value::as_record)
-            .ok_or_else(|| Error::de("expected record"))?;
-        visitor.visit_map(&mut MapDeserializer {
-            input: record,
-            index: 0,
-        })
+        match self.input {
+            Some(Value::Record(record)) => visitor.visit_map(&mut MapDeserializer {
+                input: record,
+                index: 0,
+            }),
+            Some(Value::Map(map)) => visitor.visit_map(&mut EntriesDeserializer {
+                input: map,
+                index: 0,
+            }),
+            _ => Err(Error::de("expected record or map")),
+        }
     }
 
     fn deserialize_struct<V>(
@@ -451,6 +445,35 @@ impl<'de, 'a> de::MapAccess<'de> for &'a mut MapDeserializer<'de> {
     }
 }
 
+struct EntriesDeserializer<'de> {
+    input: &'de Map,
+    index: usize,
+}
+
+impl<'de, 'a> de::MapAccess<'de> for &'a mut EntriesDeserializer<'de> {
+    type Error = Error;
+
+    fn next_key_seed<K>(&mut self, seed: K) -> crate::Result<Option<K::Value>>
+    where
+        K: DeserializeSeed<'de>,
+    {
+        self.input
+            .entries
+            .get(self.index)
+            .map(|(key, _)| seed.deserialize(&Deserializer::from_optional(Some(key))))
+            .transpose()
+    }
+
+    fn next_value_seed<V>(&mut self, seed: V) -> crate::Result<V::Value>
+    where
+        V: DeserializeSeed<'de>,
+    {
+        let (_, value) = &self.input.entries[self.index];
+        self.index += 1;
+        seed.deserialize(&Deserializer::from_optional(value.as_ref()))
+    }
+}
+
 struct EnumDeserializer<'de> {
     input: &'de Struct,
 }
@@ -558,6 +581,13 @@ mod test {
         assert_eq!(from_value::<f64>(&Value::rational(1.5)).unwrap(), 1.5f64);
     }
 
+    #[test]
+    fn deserialize_float() {
+        assert_eq!(from_value::<f32>(&Value::float(1.5)).unwrap(), 1.5f32);
+        assert_eq!(from_value::<f64>(&Value::float(0.1)).unwrap(), 0.1f64);
+        assert!(from_value::<f64>(&Value::integer(1)).is_err());
+    }
+
     #[test]
     fn deserialize_string() {
         assert_eq!(
@@ -576,6 +606,26 @@ mod test {
         assert_eq!(from_value::<bool>(&Value::atom("false")).unwrap(), false);
         assert!(from_value::<bool>(&Value::atom("what")).is_err());
         assert!(from_value::<bool>(&Value::integer(3)).is_err());
+        assert_eq!(from_value::<bool>(&Value::bool(true)).unwrap(), true);
+    }
+
+    #[test]
+    fn deserialize_bool_any() {
+        #[derive(Deserialize, Debug, Eq, PartialEq)]
+        #[serde(untagged)]
+        enum Untagged {
+            Bool(bool),
+            Int(i64),
+        }
+
+        assert_eq!(
+            from_value::<Untagged>(&Value::bool(false)).unwrap(),
+            Untagged::Bool(false),
+        );
+        assert_eq!(
+            from_value::<Untagged>(&Value::integer(3)).unwrap(),
+            Untagged::Int(3),
+        );
     }
 
     #[test]
@@ -937,4 +987,19 @@ mod test {
             map,
         );
     }
+
+    #[test]
+    fn deserialize_map() {
+        use std::collections::HashMap;
+        let mut map = HashMap::new();
+        map.insert(1, String::from("admin"));
+        map.insert(2, String::from("user"));
+        let value = Value::map(vec![
+            (Value::integer(1), "admin"),
+            (Value::integer(2), "user"),
+        ]);
+
+        assert_eq!(from_value::<HashMap<u32, String>>(&value).unwrap(), map);
+        assert!(from_value::<HashMap<u32, String>>(&Value::integer(1)).is_err());
+    }
 }
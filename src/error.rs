@@ -1,21 +1,35 @@
-use crate::ast::Scope;
+use crate::ast::{Scope, SourceSpan};
 #[cfg(feature = "serde")]
 use serde::{de, ser};
 use std::collections::HashMap;
-use std::fmt::{self, Display, Formatter, Write};
+use std::fmt::{self, Display, Formatter};
+use std::path::{Path, PathBuf};
+
+mod compile_error;
+mod lint;
+
+pub use compile_error::CompileError;
+pub use lint::{Lint, LintLevel};
 
 /// The kind of error that has occurred.
 #[derive(Copy, Clone, Eq, PartialEq, Debug)]
+#[non_exhaustive]
 pub enum ErrorKind {
     /// The error occurred while interacting with the file system.
     Io,
     /// An error was encountered in the source file, preventing the program from being created.
-    /// This error likely cannot be handled programmatically.
+    /// The specific problem can be found using [`Error::compile_error`][].
     Parse,
     /// An error has occurred while attempting to extract a pattern from a binding.
     Binding,
-    /// Contains multiple errors of various sources. This error can be printed to the user to
-    /// help with debugging. This error likely cannot be handled programmatically.
+    /// In debug mode, a predicate produced a number of answers which does not agree with its
+    /// declared determinism.
+    Determinism,
+    /// A problem was found in the source file which did not prevent the program from being
+    /// created. The [`Lint`][] which reported it can be found using [`Error::lint`][].
+    Warning,
+    /// Contains multiple errors of various sources, which can be retrieved using
+    /// [`Error::errors`][]. This error can be printed to the user to help with debugging.
     Multiple,
     /// An error has occurred during serialization of a Rust value to a Lumber value.
     #[cfg(feature = "serde")]
@@ -25,22 +39,88 @@ pub enum ErrorKind {
     De,
 }
 
+/// The location in a source file at which an error was found.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct Location {
+    pub(crate) file: Option<PathBuf>,
+    pub(crate) line: usize,
+    pub(crate) column: usize,
+    pub(crate) end_line: usize,
+    pub(crate) end_column: usize,
+    pub(crate) snippet: String,
+}
+
+impl Location {
+    /// The file in which the error was found. If the program was not read from a file (such as
+    /// when using [`Lumber::from_str`][crate::Lumber::from_str]), there is no file.
+    pub fn file(&self) -> Option<&Path> {
+        self.file.as_deref()
+    }
+
+    /// The line on which the erroneous code starts, counting from 1.
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    /// The column at which the erroneous code starts, counting from 1.
+    pub fn column(&self) -> usize {
+        self.column
+    }
+
+    /// The line on which the erroneous code ends, counting from 1.
+    pub fn end_line(&self) -> usize {
+        self.end_line
+    }
+
+    /// The column just after the end of the erroneous code, counting from 1.
+    pub fn end_column(&self) -> usize {
+        self.end_column
+    }
+
+    /// The full text of the line on which the erroneous code starts.
+    pub fn snippet(&self) -> &str {
+        &self.snippet
+    }
+}
+
+impl Display for Location {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match &self.file {
+            Some(file) => write!(f, "{}:{}:{}", file.display(), self.line, self.column),
+            None => write!(f, "{}:{}", self.line, self.column),
+        }
+    }
+}
+
 /// An error that has occurred within Lumber.
 #[derive(Debug)]
 pub struct Error {
     pub(crate) kind: ErrorKind,
     pub(crate) message: String,
+    pub(crate) location: Option<Location>,
+    pub(crate) code: Option<&'static str>,
+    pub(crate) label: Option<String>,
+    pub(crate) notes: Vec<String>,
+    pub(crate) help: Option<String>,
+    pub(crate) detail: Option<CompileError>,
+    pub(crate) module: Option<String>,
+    pub(crate) errors: Vec<Error>,
     pub(crate) source: Option<Box<dyn std::error::Error + 'static>>,
 }
 
 impl Error {
-    pub(crate) fn parse<S: ?Sized + ToOwned<Owned = String>>(message: &S) -> Self
-    where
-        String: std::borrow::Borrow<S>,
-    {
+    fn new(kind: ErrorKind, message: String) -> Self {
         Self {
-            kind: ErrorKind::Parse,
-            message: message.to_owned(),
+            kind,
+            message,
+            location: None,
+            code: None,
+            label: None,
+            notes: vec![],
+            help: None,
+            detail: None,
+            module: None,
+            errors: vec![],
             source: None,
         }
     }
@@ -49,50 +129,197 @@ impl Error {
     where
         String: std::borrow::Borrow<S>,
     {
-        Self {
-            kind: ErrorKind::Binding,
-            message: message.to_owned(),
-            source: None,
-        }
+        Self::new(ErrorKind::Binding, message.to_owned())
+    }
+
+    pub(crate) fn determinism(message: String) -> Self {
+        Self::new(ErrorKind::Determinism, message)
     }
 
     #[cfg(feature = "serde")]
     pub(crate) fn ser<S: Display>(message: S) -> Self {
-        Self {
-            kind: ErrorKind::Ser,
-            message: message.to_string(),
-            source: None,
-        }
+        Self::new(ErrorKind::Ser, message.to_string())
     }
 
     #[cfg(feature = "serde")]
     pub(crate) fn de<S: Display>(message: S) -> Self {
+        Self::new(ErrorKind::De, message.to_string())
+    }
+
+    pub(crate) fn compile(detail: CompileError) -> Self {
+        let message = detail.to_string();
         Self {
-            kind: ErrorKind::De,
-            message: message.to_string(),
-            source: None,
+            code: Some(detail.code()),
+            label: Some(detail.label().to_owned()),
+            notes: detail.notes(),
+            help: detail.help(),
+            detail: Some(detail),
+            ..Self::new(ErrorKind::Parse, message)
         }
     }
 
     pub(crate) fn multiple_by_module(errors: HashMap<Scope, Vec<Self>>) -> Self {
+        let mut errors = errors
+            .into_iter()
+            .map(|(scope, errors)| (scope.to_string(), errors))
+            .collect::<Vec<_>>();
+        errors.sort_by(|(a, _), (b, _)| a.cmp(b));
         Self {
-            kind: ErrorKind::Multiple,
-            message: errors
+            errors: errors
                 .into_iter()
-                .map(|(scope, errors)| {
-                    let mut message = String::new();
-                    if errors.is_empty() {
-                        return message;
-                    }
-                    write!(message, "-- {} errors in module {} --", errors.len(), scope).unwrap();
-                    for error in &errors {
-                        write!(message, "\n\n{}", error).unwrap();
-                    }
-                    message
+                .flat_map(|(scope, errors)| {
+                    errors.into_iter().map(move |mut error| {
+                        error.module = Some(scope.clone());
+                        error
+                    })
                 })
-                .collect::<String>(),
-            source: None,
+                .collect(),
+            ..Self::new(ErrorKind::Multiple, String::new())
+        }
+    }
+}
+
+impl Error {
+    /// The kind of error that occurred.
+    pub fn kind(&self) -> ErrorKind {
+        self.kind
+    }
+
+    /// A description of the problem, without its location or any other details.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// The location in the source code at which this error was found, if it was found in the
+    /// source code. Errors grouping multiple errors do not have a location of their own.
+    pub fn location(&self) -> Option<&Location> {
+        self.location.as_ref()
+    }
+
+    /// A stable code identifying what sort of problem this error describes, such as `L0001` for
+    /// singleton variables. Tools should match on this code instead of on the message, which may
+    /// change between versions. Errors which are not caused by the source code have no code.
+    ///
+    /// The codes are listed alongside the problems they describe in [`CompileError`][].
+    pub fn code(&self) -> Option<&str> {
+        self.code
+    }
+
+    /// The specific problem found in the source code, if this error was caused by the source code.
+    pub fn compile_error(&self) -> Option<&CompileError> {
+        self.detail.as_ref()
+    }
+
+    /// The lint which reported this error, if it was reported by a lint. Errors reported by a lint
+    /// are warnings, unless the lint has been set to [`LintLevel::Deny`][].
+    pub fn lint(&self) -> Option<Lint> {
+        self.detail.as_ref().and_then(CompileError::lint)
+    }
+
+    /// The module in which this error was found, if it was found while compiling a program with
+    /// multiple errors.
+    pub fn module(&self) -> Option<&str> {
+        self.module.as_deref()
+    }
+
+    /// The errors contained in an error of kind [`ErrorKind::Multiple`][], grouped by the module
+    /// in which they were found. Other errors contain no errors.
+    pub fn errors(&self) -> &[Error] {
+        &self.errors
+    }
+
+    /// Takes the errors contained in an error of kind [`ErrorKind::Multiple`][], grouped by the
+    /// module in which they were found.
+    pub fn into_errors(self) -> Vec<Error> {
+        self.errors
+    }
+
+    /// A short description of the problem, shown beside the erroneous code.
+    pub fn label(&self) -> Option<&str> {
+        self.label.as_deref()
+    }
+
+    /// Additional information about the problem.
+    pub fn notes(&self) -> &[String] {
+        &self.notes
+    }
+
+    /// A suggestion of how the problem might be fixed.
+    pub fn help(&self) -> Option<&str> {
+        self.help.as_deref()
+    }
+
+    /// Turns this error into a warning.
+    pub(crate) fn into_warning(self) -> Self {
+        Self {
+            kind: ErrorKind::Warning,
+            ..self
+        }
+    }
+
+    /// Attaches the location of a span to this error, if it does not already have a location.
+    pub(crate) fn at(mut self, span: &SourceSpan) -> Self {
+        if self.location.is_none() {
+            self.location = span.location();
         }
+        self
+    }
+
+    /// Renders this error in the style of rustc, with the erroneous code underlined.
+    ///
+    /// ```text
+    /// error[L0001]: Singleton variable A in predicate test/0.
+    ///  --> main.lumber:3:9
+    ///   |
+    /// 3 |     foo(A).
+    ///   |         ^ used only once
+    ///   |
+    ///   = help: if the value is not needed, use `_` instead
+    /// ```
+    fn render(&self, f: &mut Formatter) -> fmt::Result {
+        let severity = match self.kind {
+            ErrorKind::Warning => "warning",
+            _ => "error",
+        };
+        match self.code {
+            Some(code) => write!(f, "{}[{}]: {}", severity, code, self.message)?,
+            None => write!(f, "{}: {}", severity, self.message)?,
+        }
+        let gutter = match &self.location {
+            Some(location) => location.line.to_string().len(),
+            None => 1,
+        };
+        let margin = " ".repeat(gutter);
+        if let Some(location) = &self.location {
+            let width = if location.end_line == location.line {
+                location.end_column.saturating_sub(location.column)
+            } else {
+                (location.snippet.chars().count() + 1).saturating_sub(location.column)
+            };
+            write!(f, "\n{}--> {}", margin, location)?;
+            write!(f, "\n{} |", margin)?;
+            write!(f, "\n{} | {}", location.line, location.snippet)?;
+            write!(
+                f,
+                "\n{} | {}{}",
+                margin,
+                " ".repeat(location.column - 1),
+                "^".repeat(width.max(1)),
+            )?;
+            if let Some(label) = &self.label {
+                write!(f, " {}", label)?;
+            }
+        }
+        if !self.notes.is_empty() || self.help.is_some() {
+            write!(f, "\n{} |", margin)?;
+        }
+        for note in &self.notes {
+            write!(f, "\n{} = note: {}", margin, note)?;
+        }
+        if let Some(help) = &self.help {
+            write!(f, "\n{} = help: {}", margin, help)?;
+        }
+        Ok(())
     }
 }
 
@@ -124,26 +351,73 @@ impl de::Error for Error {
 
 impl Display for Error {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
-        self.message.fmt(f)
+        match self.kind {
+            ErrorKind::Parse | ErrorKind::Warning => self.render(f),
+            ErrorKind::Multiple => {
+                let mut start = 0;
+                while start < self.errors.len() {
+                    let module = &self.errors[start].module;
+                    let len = self.errors[start..]
+                        .iter()
+                        .take_while(|error| &error.module == module)
+                        .count();
+                    if start != 0 {
+                        write!(f, "\n\n")?;
+                    }
+                    write!(
+                        f,
+                        "-- {} {} in module {} --",
+                        len,
+                        if len == 1 { "error" } else { "errors" },
+                        module.as_deref().unwrap_or_default(),
+                    )?;
+                    for error in &self.errors[start..start + len] {
+                        write!(f, "\n\n{}", error)?;
+                    }
+                    start += len;
+                }
+                Ok(())
+            }
+            _ => self.message.fmt(f),
+        }
     }
 }
 
 impl From<std::io::Error> for Error {
     fn from(error: std::io::Error) -> Self {
+        let message = error.to_string();
         Self {
-            kind: ErrorKind::Io,
-            message: error.to_string(),
             source: Some(Box::new(error)),
+            ..Self::new(ErrorKind::Io, message)
         }
     }
 }
 
 impl<R: pest::RuleType + 'static> From<pest::error::Error<R>> for Error {
     fn from(error: pest::error::Error<R>) -> Self {
+        let (line, column) = match error.line_col {
+            pest::error::LineColLocation::Pos(start) => start,
+            pest::error::LineColLocation::Span(start, _) => start,
+        };
+        let (end_line, end_column) = match error.line_col {
+            pest::error::LineColLocation::Pos(end) => end,
+            pest::error::LineColLocation::Span(_, end) => end,
+        };
+        let location = Location {
+            file: error.path().map(PathBuf::from),
+            line,
+            column,
+            end_line,
+            end_column,
+            snippet: error.line().to_owned(),
+        };
+        let detail = CompileError::Syntax {
+            message: error.variant.message().into_owned(),
+        };
         Self {
-            kind: ErrorKind::Parse,
-            message: error.to_string(),
+            location: Some(location),
             source: Some(Box::new(error)),
+            ..Self::compile(detail)
         }
     }
 }
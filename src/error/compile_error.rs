@@ -0,0 +1,629 @@
+use super::Lint;
+use crate::ast::Handle;
+use std::fmt::{self, Display, Formatter};
+
+/// A specific problem found in the source code of a Lumber program, which prevented it from being
+/// compiled. Problems which are reported by a [`Lint`][] may instead be reported as warnings.
+///
+/// Each variant corresponds to one [error code][crate::Error::code], and carries the values that
+/// were involved in the problem. Scopes, modules and types are described by their names as they
+/// would be written in the source code.
+#[derive(Clone, PartialEq, Eq, Debug)]
+#[non_exhaustive]
+pub enum CompileError {
+    /// `L0001`: A variable is used only once. If the variable is in a lambda, there is no
+    /// predicate.
+    SingletonVariable {
+        variable: String,
+        predicate: Option<Handle>,
+    },
+    /// `L0002`: A predicate cannot be found.
+    UnresolvedPredicate { handle: Handle, scope: String },
+    /// `L0003`: A predicate is not exported by its module.
+    InvisiblePredicate { handle: Handle, scope: String },
+    /// `L0004`: A reference matches predicates from multiple glob imports.
+    AmbiguousReference {
+        handle: Handle,
+        candidates: Vec<Handle>,
+    },
+    /// `L0005`: Aliases refer to each other in a loop.
+    AliasLoop { path: Vec<Handle> },
+    /// `L0006`: A module is declared multiple times.
+    DuplicateModule { module: String },
+    /// `L0007`: A predicate is exported multiple times.
+    DuplicateExport { handle: Handle },
+    /// `L0008`: A predicate is declared incomplete multiple times.
+    DuplicateIncomplete { handle: Handle },
+    /// `L0009`: A predicate is declared mutable multiple times.
+    DuplicateMutable { handle: Handle },
+    /// `L0010`: A scope goes above the main module.
+    NegativeScope { scope: String },
+    /// `L0011`: A predicate is imported multiple times.
+    DuplicateImport { handle: Handle, from: Handle },
+    /// `L0012`: A module is glob imported multiple times.
+    DuplicateGlob { module: String },
+    /// `L0013`: A native function is declared multiple times.
+    DuplicateNative { handle: Handle },
+    /// `L0014`: An operator is not defined.
+    UnrecognizedOperator { operator: String },
+    /// `L0015`: An escape sequence is invalid.
+    InvalidEscape { escape: String },
+    /// `L0016`: A map key contains variables.
+    UnboundMapKey { key: String },
+    /// `L0017`: A map key appears multiple times.
+    DuplicateMapKey { key: String },
+    /// `L0018`: A type is malformed.
+    InvalidType { reason: String },
+    /// `L0019`: A built in type is redefined.
+    BuiltinType { name: String },
+    /// `L0020`: A type is exported multiple times.
+    DuplicateTypeExport { name: String },
+    /// `L0021`: A type is imported multiple times.
+    DuplicateTypeImport { name: String, from: String },
+    /// `L0022`: A signature is declared multiple times.
+    DuplicateSignature { handle: Handle },
+    /// `L0023`: A mode is malformed.
+    InvalidMode { handle: Handle },
+    /// `L0024`: A determinism is declared multiple times.
+    DuplicateDeterminism { handle: Handle },
+    /// `L0025`: An operator is defined by a predicate of the wrong arity.
+    InvalidOperatorArity { operator: String, handle: Handle },
+    /// `L0026`: An operator has an invalid precedence.
+    InvalidOperatorPrecedence {
+        operator: String,
+        precedence: String,
+    },
+    /// `L0027`: A built in operator is redefined.
+    BuiltinOperator { operator: String },
+    /// `L0028`: An operator is defined multiple times.
+    DuplicateOperator { operator: String, handle: Handle },
+    /// `L0029`: An alias changes the arity of a predicate.
+    AliasArityMismatch { input: Handle, output: Handle },
+    /// `L0030`: A library is referenced but not linked.
+    UnlinkedLibrary { handle: Handle, library: String },
+    /// `L0031`: A predicate is not exported by a library.
+    UnexportedLibraryPredicate { handle: Handle, library: String },
+    /// `L0032`: A glob imported module cannot be found.
+    UnresolvedGlob { module: String },
+    /// `L0033`: A native function is not bound.
+    UnboundNative { handle: Handle },
+    /// `L0034`: A native function is also implemented.
+    ImplementedNative { handle: Handle },
+    /// `L0035`: A native function is also imported.
+    ImportedNative { handle: Handle },
+    /// `L0036`: A native function is declared mutable.
+    MutableNative { handle: Handle },
+    /// `L0037`: A native function is declared incomplete.
+    IncompleteNative { handle: Handle },
+    /// `L0038`: An exported predicate cannot be found.
+    UnresolvedExport { handle: Handle },
+    /// `L0039`: An alias is declared mutable.
+    MutableAlias { handle: Handle },
+    /// `L0040`: An alias is declared incomplete.
+    IncompleteAlias { handle: Handle },
+    /// `L0041`: A definition conflicts with an imported predicate, which may have been imported
+    /// under a different name.
+    ConflictingDefinition {
+        handle: Handle,
+        import: Handle,
+        alias: Option<Handle>,
+    },
+    /// `L0042`: A type is not exported by its module.
+    InvisibleType { name: String, scope: String },
+    /// `L0043`: An imported type cannot be found.
+    UnresolvedImportedType { name: String, module: String },
+    /// `L0044`: An exported type cannot be found.
+    UnresolvedExportedType { name: String },
+    /// `L0045`: A signature has the wrong number of types.
+    SignatureArityMismatch {
+        handle: Handle,
+        types: usize,
+        arguments: usize,
+    },
+    /// `L0046`: A signature is declared for an undefined predicate.
+    UndefinedSignature { handle: Handle },
+    /// `L0047`: A mode or determinism is declared for an undefined predicate.
+    UndefinedMode { handle: Handle },
+    /// `L0048`: A type cannot be found.
+    UnresolvedType { name: String, scope: String },
+    /// `L0049`: A predicate is imported under multiple aliases.
+    MultipleAliases {
+        handle: Handle,
+        aliases: Vec<Handle>,
+    },
+    /// `L0050`: A clause or call does not match the signature of the predicate. The argument is
+    /// counted from 1.
+    SignatureMismatch {
+        handle: Handle,
+        call: bool,
+        argument: usize,
+        expected: String,
+    },
+    /// `L0051`: A call can never satisfy any of the declared modes of the predicate.
+    UnsatisfiableMode {
+        handle: Handle,
+        mode: String,
+        modes: Vec<String>,
+    },
+    /// `L0052`: The source code is not syntactically valid.
+    Syntax { message: String },
+    /// `L0053`: A question contains a lambda.
+    LambdaInQuestion,
+    /// `L0054`: A predicate is imported by name, but never used.
+    UnusedImport { handle: Handle, from: Handle },
+    /// `L0055`: A private predicate is never used.
+    UnusedPredicate { handle: Handle },
+    /// `L0056`: A predicate hides a predicate of the same name from a glob import.
+    ShadowedGlob { handle: Handle, module: String },
+    /// `L0057`: A call can never match any clause of the predicate, so the clause containing it
+    /// can never succeed.
+    UnreachableClause { handle: Handle, call: Handle },
+    /// `L0058`: A lint level is set for a lint that does not exist.
+    UnknownLint { name: String },
+    /// `L0059`: A test is declared for an undefined predicate.
+    UndefinedTest { handle: Handle },
+    /// `L0060`: A test is declared for a mutable predicate.
+    MutableTest { handle: Handle },
+}
+
+impl CompileError {
+    /// The stable code identifying this kind of problem.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::SingletonVariable { .. } => "L0001",
+            Self::UnresolvedPredicate { .. } => "L0002",
+            Self::InvisiblePredicate { .. } => "L0003",
+            Self::AmbiguousReference { .. } => "L0004",
+            Self::AliasLoop { .. } => "L0005",
+            Self::DuplicateModule { .. } => "L0006",
+            Self::DuplicateExport { .. } => "L0007",
+            Self::DuplicateIncomplete { .. } => "L0008",
+            Self::DuplicateMutable { .. } => "L0009",
+            Self::NegativeScope { .. } => "L0010",
+            Self::DuplicateImport { .. } => "L0011",
+            Self::DuplicateGlob { .. } => "L0012",
+            Self::DuplicateNative { .. } => "L0013",
+            Self::UnrecognizedOperator { .. } => "L0014",
+            Self::InvalidEscape { .. } => "L0015",
+            Self::UnboundMapKey { .. } => "L0016",
+            Self::DuplicateMapKey { .. } => "L0017",
+            Self::InvalidType { .. } => "L0018",
+            Self::BuiltinType { .. } => "L0019",
+            Self::DuplicateTypeExport { .. } => "L0020",
+            Self::DuplicateTypeImport { .. } => "L0021",
+            Self::DuplicateSignature { .. } => "L0022",
+            Self::InvalidMode { .. } => "L0023",
+            Self::DuplicateDeterminism { .. } => "L0024",
+            Self::InvalidOperatorArity { .. } => "L0025",
+            Self::InvalidOperatorPrecedence { .. } => "L0026",
+            Self::BuiltinOperator { .. } => "L0027",
+            Self::DuplicateOperator { .. } => "L0028",
+            Self::AliasArityMismatch { .. } => "L0029",
+            Self::UnlinkedLibrary { .. } => "L0030",
+            Self::UnexportedLibraryPredicate { .. } => "L0031",
+            Self::UnresolvedGlob { .. } => "L0032",
+            Self::UnboundNative { .. } => "L0033",
+            Self::ImplementedNative { .. } => "L0034",
+            Self::ImportedNative { .. } => "L0035",
+            Self::MutableNative { .. } => "L0036",
+            Self::IncompleteNative { .. } => "L0037",
+            Self::UnresolvedExport { .. } => "L0038",
+            Self::MutableAlias { .. } => "L0039",
+            Self::IncompleteAlias { .. } => "L0040",
+            Self::ConflictingDefinition { .. } => "L0041",
+            Self::InvisibleType { .. } => "L0042",
+            Self::UnresolvedImportedType { .. } => "L0043",
+            Self::UnresolvedExportedType { .. } => "L0044",
+            Self::SignatureArityMismatch { .. } => "L0045",
+            Self::UndefinedSignature { .. } => "L0046",
+            Self::UndefinedMode { .. } => "L0047",
+            Self::UnresolvedType { .. } => "L0048",
+            Self::MultipleAliases { .. } => "L0049",
+            Self::SignatureMismatch { .. } => "L0050",
+            Self::UnsatisfiableMode { .. } => "L0051",
+            Self::Syntax { .. } => "L0052",
+            Self::LambdaInQuestion => "L0053",
+            Self::UnusedImport { .. } => "L0054",
+            Self::UnusedPredicate { .. } => "L0055",
+            Self::ShadowedGlob { .. } => "L0056",
+            Self::UnreachableClause { .. } => "L0057",
+            Self::UnknownLint { .. } => "L0058",
+            Self::UndefinedTest { .. } => "L0059",
+            Self::MutableTest { .. } => "L0060",
+        }
+    }
+
+    /// The lint which reports this problem, if it is reported as a lint rather than always being
+    /// an error.
+    pub fn lint(&self) -> Option<Lint> {
+        match self {
+            Self::SingletonVariable { .. } => Some(Lint::SingletonVariable),
+            Self::UnusedImport { .. } => Some(Lint::UnusedImport),
+            Self::UnusedPredicate { .. } => Some(Lint::UnusedPredicate),
+            Self::ShadowedGlob { .. } => Some(Lint::ShadowedGlob),
+            Self::UnreachableClause { .. } => Some(Lint::UnreachableClause),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn label(&self) -> &'static str {
+        match self {
+            Self::SingletonVariable { .. } => "used only once",
+            Self::UnresolvedPredicate { .. } => "not found in this scope",
+            Self::InvisiblePredicate { .. } => "not exported by its module",
+            Self::AmbiguousReference { .. } => "ambiguous reference",
+            Self::AliasLoop { .. } => "alias refers back to itself",
+            Self::DuplicateModule { .. } => "declared again here",
+            Self::DuplicateExport { .. } => "exported again here",
+            Self::DuplicateIncomplete { .. } => "declared incomplete again here",
+            Self::DuplicateMutable { .. } => "declared mutable again here",
+            Self::NegativeScope { .. } => "goes above the main module",
+            Self::DuplicateImport { .. } => "imported again here",
+            Self::DuplicateGlob { .. } => "imported again here",
+            Self::DuplicateNative { .. } => "declared again here",
+            Self::UnrecognizedOperator { .. } => "unrecognized operator",
+            Self::InvalidEscape { .. } => "invalid escape sequence",
+            Self::UnboundMapKey { .. } => "contains variables",
+            Self::DuplicateMapKey { .. } => "duplicate key",
+            Self::InvalidType { .. } => "invalid type",
+            Self::BuiltinType { .. } => "built in type",
+            Self::DuplicateTypeExport { .. } => "exported again here",
+            Self::DuplicateTypeImport { .. } => "imported again here",
+            Self::DuplicateSignature { .. } => "declared again here",
+            Self::InvalidMode { .. } => "invalid mode",
+            Self::DuplicateDeterminism { .. } => "declared again here",
+            Self::InvalidOperatorArity { .. } => "wrong arity",
+            Self::InvalidOperatorPrecedence { .. } => "invalid precedence",
+            Self::BuiltinOperator { .. } => "built in operator",
+            Self::DuplicateOperator { .. } => "defined again here",
+            Self::AliasArityMismatch { .. } => "arity changed here",
+            Self::UnlinkedLibrary { .. } => "library is not linked",
+            Self::UnexportedLibraryPredicate { .. } => "not exported by the library",
+            Self::UnresolvedGlob { .. } => "module not found",
+            Self::UnboundNative { .. } => "declared here",
+            Self::ImplementedNative { .. }
+            | Self::ImportedNative { .. }
+            | Self::MutableNative { .. }
+            | Self::IncompleteNative { .. } => "declared native here",
+            Self::UnresolvedExport { .. } => "exported here",
+            Self::MutableAlias { .. } => "declared mutable here",
+            Self::IncompleteAlias { .. } => "declared incomplete here",
+            Self::ConflictingDefinition { .. } => "conflicting definition",
+            Self::InvisibleType { .. } => "not exported by its module",
+            Self::UnresolvedImportedType { .. } => "type not found",
+            Self::UnresolvedExportedType { .. } => "exported here",
+            Self::SignatureArityMismatch { .. } => "wrong number of types",
+            Self::UndefinedSignature { .. } => "no such predicate in this module",
+            Self::UndefinedMode { .. } => "no such predicate in this module",
+            Self::UnresolvedType { .. } => "type not found",
+            Self::MultipleAliases { .. } => "aliased multiple times",
+            Self::SignatureMismatch { .. } => "does not match signature",
+            Self::UnsatisfiableMode { .. } => "no declared mode admits this call",
+            Self::Syntax { .. } => "unexpected input",
+            Self::LambdaInQuestion => "lambda in question",
+            Self::UnusedImport { .. } => "never used",
+            Self::UnusedPredicate { .. } => "never used",
+            Self::ShadowedGlob { .. } => "shadows a glob import",
+            Self::UnreachableClause { .. } => "matches no clause",
+            Self::UnknownLint { .. } => "unknown lint",
+            Self::UndefinedTest { .. } => "no such predicate in this module",
+            Self::MutableTest { .. } => "declared mutable here",
+        }
+    }
+
+    pub(crate) fn notes(&self) -> Vec<String> {
+        match self {
+            Self::UnsatisfiableMode { handle, modes, .. } => vec![format!(
+                "{} is declared with the modes {}",
+                handle,
+                modes.join(", "),
+            )],
+            Self::UnknownLint { .. } => vec![format!(
+                "the known lints are {}",
+                Lint::ALL
+                    .iter()
+                    .map(|lint| lint.name())
+                    .collect::<Vec<_>>()
+                    .join(", "),
+            )],
+            _ => vec![],
+        }
+    }
+
+    pub(crate) fn help(&self) -> Option<String> {
+        match self {
+            Self::SingletonVariable { .. } => {
+                Some("if the value is not needed, use `_` instead".to_owned())
+            }
+            Self::InvisiblePredicate { .. } | Self::InvisibleType { .. } => {
+                Some("export it from its module using `:- pub`".to_owned())
+            }
+            Self::AmbiguousReference { .. } => {
+                Some("import the intended predicate explicitly using `:- use`".to_owned())
+            }
+            Self::UnrecognizedOperator { .. } => {
+                Some("operators must be defined using `:- op` before they are used".to_owned())
+            }
+            Self::InvalidOperatorPrecedence { .. } => {
+                Some("precedence must be between 1 and 10".to_owned())
+            }
+            Self::UnboundNative { .. } => Some("bind it using `LumberBuilder::bind`".to_owned()),
+            Self::UnusedImport { .. } => Some("remove the import".to_owned()),
+            Self::UnusedPredicate { handle } => Some(format!(
+                "if it is meant to be used elsewhere, export it using `:- pub({})`",
+                handle
+            )),
+            Self::ShadowedGlob { handle, .. } => Some(format!(
+                "rename {} to avoid confusion with the glob imported predicate",
+                handle
+            )),
+            Self::ConflictingDefinition { import, .. } => Some(format!(
+                "declare {} as incomplete in its module to extend it",
+                import
+            )),
+            _ => None,
+        }
+    }
+}
+
+impl Display for CompileError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::SingletonVariable {
+                variable,
+                predicate: Some(predicate),
+            } => write!(
+                f,
+                "Singleton variable {} in predicate {}.",
+                variable, predicate
+            ),
+            Self::SingletonVariable {
+                variable,
+                predicate: None,
+            } => write!(f, "Singleton variable {} in lambda.", variable),
+            Self::UnresolvedPredicate { handle, scope } => {
+                write!(f, "Unresolved predicate {} in scope {}.", handle, scope)
+            }
+            Self::InvisiblePredicate { handle, scope } => write!(
+                f,
+                "Predicate {} is not visible from scope {}.",
+                handle, scope
+            ),
+            Self::AmbiguousReference { handle, candidates } => {
+                write!(
+                    f,
+                    "Ambiguous reference {}. Could be referring to any of:",
+                    handle
+                )?;
+                for candidate in candidates {
+                    write!(f, "\n\t{}", candidate)?;
+                }
+                Ok(())
+            }
+            Self::AliasLoop { path } => write!(
+                f,
+                "Alias loop detected: {}",
+                path.iter()
+                    .map(|handle| handle.to_string())
+                    .collect::<Vec<_>>()
+                    .join(" -> "),
+            ),
+            Self::DuplicateModule { module } => {
+                write!(f, "Module {} declared multiple times.", module)
+            }
+            Self::DuplicateExport { handle } => write!(f, "{} exported multiple times.", handle),
+            Self::DuplicateIncomplete { handle } => {
+                write!(f, "{} decared as incomplete multiple times.", handle)
+            }
+            Self::DuplicateMutable { handle } => {
+                write!(f, "{} set as mutable multiple times.", handle)
+            }
+            Self::NegativeScope { scope } => {
+                write!(f, "Scope {} goes above the main module.", scope)
+            }
+            Self::DuplicateImport { handle, from } => {
+                write!(f, "{} already imported from {}.", handle, from)
+            }
+            Self::DuplicateGlob { module } => {
+                write!(f, "Module {} imported multiple times.", module)
+            }
+            Self::DuplicateNative { handle } => {
+                write!(f, "Native function {} declared multiple times.", handle)
+            }
+            Self::UnrecognizedOperator { operator } => {
+                write!(f, "Unrecognized operator `{}`.", operator)
+            }
+            Self::InvalidEscape { escape } => write!(
+                f,
+                "Escape sequence `{}` does not describe a valid character.",
+                escape
+            ),
+            Self::UnboundMapKey { key } => {
+                write!(f, "Map key `{}` must not contain variables.", key)
+            }
+            Self::DuplicateMapKey { key } => write!(f, "Map key `{}` appears multiple times.", key),
+            Self::InvalidType { reason } => write!(f, "Invalid type: {}.", reason),
+            Self::BuiltinType { name } => {
+                write!(f, "Type {} is built in, and cannot be redefined.", name)
+            }
+            Self::DuplicateTypeExport { name } => {
+                write!(f, "Type {} exported multiple times.", name)
+            }
+            Self::DuplicateTypeImport { name, from } => {
+                write!(f, "Type {} already imported from {}.", name, from)
+            }
+            Self::DuplicateSignature { handle } => {
+                write!(f, "Signature of {} declared multiple times.", handle)
+            }
+            Self::InvalidMode { handle } => write!(
+                f,
+                "Invalid mode for {}: each parameter must be one of `!`, `?` or `_`.",
+                handle
+            ),
+            Self::DuplicateDeterminism { handle } => {
+                write!(f, "Determinism of {} declared multiple times.", handle)
+            }
+            Self::InvalidOperatorArity { operator, handle } => write!(
+                f,
+                "Operator `{}` must be defined by a predicate of arity 3, but {} was provided.",
+                operator, handle
+            ),
+            Self::InvalidOperatorPrecedence {
+                operator,
+                precedence,
+            } => write!(
+                f,
+                "Operator `{}` has invalid precedence {}.",
+                operator, precedence
+            ),
+            Self::BuiltinOperator { operator } => write!(
+                f,
+                "Operator `{}` is built in, and cannot be redefined.",
+                operator
+            ),
+            Self::DuplicateOperator { operator, handle } => write!(
+                f,
+                "Operator `{}` is already defined as {}.",
+                operator, handle
+            ),
+            Self::AliasArityMismatch { input, output } => write!(
+                f,
+                "Cannot change arity of {} when aliasing to {}.",
+                input, output
+            ),
+            Self::UnlinkedLibrary { handle, library } => write!(
+                f,
+                "Referencing predicate {} from unlinked library {}.",
+                handle, library
+            ),
+            Self::UnexportedLibraryPredicate { handle, library } => write!(
+                f,
+                "No predicate {} is exported by the library {}.",
+                handle, library
+            ),
+            Self::UnresolvedGlob { module } => {
+                write!(f, "Unresolved module {} in glob import.", module)
+            }
+            Self::UnboundNative { handle } => write!(f, "Native function {} is not bound.", handle),
+            Self::ImplementedNative { handle } => {
+                write!(f, "Native function {} cannot also be implemented.", handle)
+            }
+            Self::ImportedNative { handle } => {
+                write!(f, "Native function {} cannot also be imported.", handle)
+            }
+            Self::MutableNative { handle } => {
+                write!(f, "Native function {} cannot be set as mutable.", handle)
+            }
+            Self::IncompleteNative { handle } => {
+                write!(f, "Native function {} cannot be set as incomplete.", handle)
+            }
+            Self::UnresolvedExport { handle } => {
+                write!(f, "Exported predicate {} cannot be found.", handle)
+            }
+            Self::MutableAlias { handle } => write!(f, "Cannot set alias {} as mutable.", handle),
+            Self::IncompleteAlias { handle } => {
+                write!(f, "Cannot set alias {} as incomplete.", handle)
+            }
+            Self::ConflictingDefinition {
+                handle,
+                import,
+                alias,
+            } => {
+                write!(
+                    f,
+                    "Definition of {} conflicts with imported {}",
+                    handle, import
+                )?;
+                if let Some(alias) = alias {
+                    write!(f, " (aliased as {})", alias)?;
+                }
+                write!(f, ".")
+            }
+            Self::InvisibleType { name, scope } => {
+                write!(f, "Type {} is not visible from scope {}.", name, scope)
+            }
+            Self::UnresolvedImportedType { name, module } => write!(
+                f,
+                "Imported type {} cannot be found in module {}.",
+                name, module
+            ),
+            Self::UnresolvedExportedType { name } => {
+                write!(f, "Exported type {} cannot be found.", name)
+            }
+            Self::SignatureArityMismatch {
+                handle,
+                types,
+                arguments,
+            } => write!(
+                f,
+                "Signature of {} lists {} types, but the predicate has {} arguments.",
+                handle, types, arguments
+            ),
+            Self::UndefinedSignature { handle } => write!(
+                f,
+                "Signature is declared for {}, but it is not defined in this module.",
+                handle
+            ),
+            Self::UndefinedMode { handle } => write!(
+                f,
+                "Mode or determinism is declared for {}, but it is not defined in this module.",
+                handle
+            ),
+            Self::UnresolvedType { name, scope } => {
+                write!(f, "Unresolved type {} in scope {}.", name, scope)
+            }
+            Self::MultipleAliases { handle, aliases } => {
+                write!(f, "{} is aliased multiple times, as:", handle)?;
+                for alias in aliases {
+                    write!(f, "\n\t{}", alias)?;
+                }
+                Ok(())
+            }
+            Self::SignatureMismatch {
+                handle,
+                call,
+                argument,
+                expected,
+            } => write!(
+                f,
+                "{} {} does not match its signature: argument {} is not of type {}.",
+                if *call { "Call to" } else { "Clause of" },
+                handle,
+                argument,
+                expected
+            ),
+            Self::UnsatisfiableMode { handle, mode, .. } => write!(
+                f,
+                "Call to {} as {} can never satisfy any of its declared modes.",
+                handle, mode
+            ),
+            Self::Syntax { message } => write!(f, "Syntax error: {}.", message),
+            Self::LambdaInQuestion => write!(f, "Lambdas cannot be used in questions."),
+            Self::UnusedImport { handle, from } => {
+                write!(
+                    f,
+                    "Imported predicate {} (from {}) is never used.",
+                    handle, from
+                )
+            }
+            Self::UnusedPredicate { handle } => write!(f, "Predicate {} is never used.", handle),
+            Self::ShadowedGlob { handle, module } => write!(
+                f,
+                "{} shadows the predicate of the same name glob imported from {}.",
+                handle, module
+            ),
+            Self::UnreachableClause { handle, call } => write!(
+                f,
+                "Clause of {} can never succeed: no clause of {} matches this call.",
+                handle, call
+            ),
+            Self::UnknownLint { name } => write!(f, "Unknown lint {}.", name),
+            Self::UndefinedTest { handle } => write!(
+                f,
+                "Test {} is declared, but it is not defined in this module.",
+                handle
+            ),
+            Self::MutableTest { handle } => write!(f, "Test {} cannot be mutable.", handle),
+        }
+    }
+}
@@ -0,0 +1,87 @@
+use std::fmt::{self, Display, Formatter};
+use std::str::FromStr;
+
+/// A problem which does not prevent a program from being compiled, but is likely a mistake.
+///
+/// Each lint can be set to a [`LintLevel`][] for the whole program using
+/// [`LumberBuilder::lint`][crate::LumberBuilder::lint], or for a single module (and its
+/// submodules) using the `:- allow(lint)`, `:- warn(lint)` or `:- deny(lint)` directives,
+/// where the lint is written by the name listed here.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+#[non_exhaustive]
+pub enum Lint {
+    /// `singleton_variable`: A variable is used only once in a clause.
+    SingletonVariable,
+    /// `unused_import`: A predicate is imported by name, but never used or re-exported.
+    UnusedImport,
+    /// `unused_predicate`: A predicate is neither exported nor used within its module. Predicates
+    /// of the main module may be used by questions, so are never reported.
+    UnusedPredicate,
+    /// `shadowed_glob`: A predicate defined or imported by name hides a predicate of the same name
+    /// from a glob import.
+    ShadowedGlob,
+    /// `unreachable_clause`: A clause calls a predicate in a way that none of its clauses can
+    /// match, so the clause can never succeed.
+    UnreachableClause,
+}
+
+impl Lint {
+    /// All lints.
+    pub const ALL: &'static [Lint] = &[
+        Lint::SingletonVariable,
+        Lint::UnusedImport,
+        Lint::UnusedPredicate,
+        Lint::ShadowedGlob,
+        Lint::UnreachableClause,
+    ];
+
+    /// The name by which this lint is referred to in directives.
+    pub fn name(self) -> &'static str {
+        match self {
+            Lint::SingletonVariable => "singleton_variable",
+            Lint::UnusedImport => "unused_import",
+            Lint::UnusedPredicate => "unused_predicate",
+            Lint::ShadowedGlob => "shadowed_glob",
+            Lint::UnreachableClause => "unreachable_clause",
+        }
+    }
+
+    /// The level at which this lint is reported when it has not been configured. Singleton
+    /// variables are denied by default, while all other lints warn.
+    pub fn default_level(self) -> LintLevel {
+        match self {
+            Lint::SingletonVariable => LintLevel::Deny,
+            _ => LintLevel::Warn,
+        }
+    }
+}
+
+impl FromStr for Lint {
+    type Err = ();
+
+    fn from_str(name: &str) -> Result<Self, ()> {
+        Lint::ALL
+            .iter()
+            .copied()
+            .find(|lint| lint.name() == name)
+            .ok_or(())
+    }
+}
+
+impl Display for Lint {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        self.name().fmt(f)
+    }
+}
+
+/// How a [`Lint`][] is reported.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum LintLevel {
+    /// The lint is not reported at all.
+    Allow,
+    /// The lint is reported as a warning, which is available from
+    /// [`Lumber::warnings`][crate::Lumber::warnings] once the program has been compiled.
+    Warn,
+    /// The lint is reported as an error, preventing the program from being compiled.
+    Deny,
+}
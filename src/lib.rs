@@ -21,7 +21,9 @@ type Pairs<'i> = pest::iterators::Pairs<'i, parser::Rule>;
 type Pair<'i> = pest::iterators::Pair<'i, parser::Rule>;
 
 pub use crate::lumber::*;
-pub use error::{Error, ErrorKind};
+pub use ast::Handle;
+pub use error::{CompileError, Error, ErrorKind, Lint, LintLevel, Location};
+pub use parser::format;
 
 pub type Result<T> = std::result::Result<T, Error>;
 
@@ -0,0 +1,286 @@
+use crate::ast::*;
+use crate::{Error, Location};
+use std::collections::{HashMap, HashSet};
+use std::fmt::{self, Display, Formatter};
+use std::path::{Path, PathBuf};
+
+/// The results of checking a Lumber program without compiling it, for use by tools such as
+/// editors which need to navigate the program's source code.
+///
+/// Positions are given by line and column, both counting from 1, as in a [`Location`][].
+#[derive(Debug)]
+pub struct Analysis {
+    diagnostics: Vec<Error>,
+    modules: Vec<ModuleInfo>,
+    definitions: HashMap<Handle, Vec<SourceSpan>>,
+    references: Vec<(Handle, SourceSpan)>,
+    aliases: HashMap<Handle, Handle>,
+    exports: HashSet<Handle>,
+    docs: HashMap<Handle, String>,
+}
+
+/// A module of a Lumber program, as found by an [`Analysis`][].
+#[derive(Debug)]
+pub struct ModuleInfo {
+    scope: Scope,
+    file: Option<PathBuf>,
+    span: SourceSpan,
+    doc: String,
+    visible: Vec<Handle>,
+    exports: Vec<Handle>,
+}
+
+impl ModuleInfo {
+    /// The names of the modules in the path to this module from the root module, which is empty
+    /// for the root module itself.
+    pub fn path(&self) -> Vec<String> {
+        self.scope.into_iter().map(ToString::to_string).collect()
+    }
+
+    /// The source file of this module, if it was read from a file.
+    pub fn file(&self) -> Option<&Path> {
+        self.file.as_deref()
+    }
+
+    /// The documentation of this module, from its `//!` comments and the `///` comments on the
+    /// `:- mod` directive which declared it.
+    pub fn doc(&self) -> &str {
+        &self.doc
+    }
+
+    /// The predicates exported from this module, ordered by name.
+    pub fn exports(&self) -> &[Handle] {
+        &self.exports
+    }
+}
+
+impl Display for ModuleInfo {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        self.scope.fmt(f)
+    }
+}
+
+impl Analysis {
+    pub(crate) fn new(
+        headers: &HashMap<Scope, ModuleHeader>,
+        module: &Module,
+        diagnostics: Vec<Error>,
+    ) -> Self {
+        let mut definitions = HashMap::<Handle, Vec<SourceSpan>>::new();
+        let mut references = vec![];
+        for (handle, definition) in module.definitions() {
+            if handle.scope.head().as_ref().starts_with('#') {
+                continue;
+            }
+            for (head, body) in definition.iter() {
+                definitions
+                    .entry(handle.clone())
+                    .or_default()
+                    .push(head.span.clone());
+                references.extend(
+                    body.queries()
+                        .map(|query| (query.as_ref().clone(), query.span.clone())),
+                );
+            }
+        }
+
+        let mut modules = vec![];
+        let mut aliases = HashMap::new();
+        let mut exports = HashSet::new();
+        let mut docs = HashMap::new();
+        for header in headers.values() {
+            for handle in header.natives.iter().chain(header.definitions.iter()) {
+                definitions
+                    .entry(handle.clone())
+                    .or_insert_with(|| vec![handle.span.clone()]);
+            }
+            for (alias, source) in &header.aliases {
+                references.push((source.clone(), source.span.clone()));
+                aliases.insert(alias.clone(), source.clone());
+            }
+            for export in &header.exports {
+                references.push((export.clone(), export.span.clone()));
+                exports.insert(export.clone());
+            }
+            docs.extend(header.docs.clone());
+
+            let mut visible: Vec<_> = header
+                .definitions
+                .iter()
+                .chain(header.natives.iter())
+                .chain(header.aliases.keys())
+                .filter(|handle| !handle.scope.head().as_ref().starts_with('#'))
+                .cloned()
+                .collect();
+            for glob in &header.globs {
+                if let Some(module) = headers.get(glob) {
+                    visible.extend(
+                        module
+                            .exports
+                            .iter()
+                            .map(|handle| handle.relocate(&header.scope)),
+                    );
+                }
+            }
+            let mut module_exports: Vec<_> = header.exports.iter().cloned().collect();
+            module_exports.sort_by_cached_key(ToString::to_string);
+            modules.push(ModuleInfo {
+                scope: header.scope.clone(),
+                file: header
+                    .source
+                    .as_ref()
+                    .and_then(|source| source.path.clone()),
+                span: header.span.clone(),
+                doc: header.doc.clone(),
+                visible,
+                exports: module_exports,
+            });
+        }
+        modules.sort_by_key(ModuleInfo::path);
+
+        Self {
+            diagnostics,
+            modules,
+            definitions,
+            references,
+            aliases,
+            exports,
+            docs,
+        }
+    }
+
+    /// The errors and warnings found in the program.
+    pub fn diagnostics(&self) -> &[Error] {
+        &self.diagnostics
+    }
+
+    /// The source files of all modules of the program.
+    pub fn files(&self) -> impl Iterator<Item = &Path> {
+        self.modules
+            .iter()
+            .filter_map(|module| module.file.as_deref())
+    }
+
+    /// The modules of the program, ordered by path, starting with the root module.
+    pub fn modules(&self) -> &[ModuleInfo] {
+        &self.modules
+    }
+
+    /// The documentation of a predicate, from the `///` comments on its clauses and on the
+    /// directives which declare it. An imported predicate which is not documented where it is
+    /// imported has the documentation of the predicate it is imported from.
+    pub fn doc(&self, handle: &Handle) -> Option<&str> {
+        self.docs
+            .get(handle)
+            .or_else(|| self.docs.get(self.resolve(handle)))
+            .map(String::as_str)
+    }
+
+    /// The predicate which is defined or referenced at a position in a source file. Imported
+    /// predicates are followed back to the predicate they are imported from.
+    pub fn handle_at<P: AsRef<Path>>(&self, file: P, line: usize, column: usize) -> Option<Handle> {
+        let file = file.as_ref();
+        let definitions = self
+            .definitions
+            .iter()
+            .flat_map(|(handle, spans)| spans.iter().map(move |span| (handle, span)));
+        let references = self.references.iter().map(|(handle, span)| (handle, span));
+        definitions
+            .chain(references)
+            .filter_map(|(handle, span)| Some((handle, span.location()?)))
+            .filter(|(_, location)| contains(location, file, line, column))
+            .min_by_key(|(_, location)| {
+                (
+                    location.end_line - location.line,
+                    location.end_column as isize - location.column as isize,
+                )
+            })
+            .map(|(handle, _)| self.resolve(handle).clone())
+    }
+
+    /// The source file of the module declared by the `:- mod` directive at a position in a
+    /// source file.
+    pub fn module_at<P: AsRef<Path>>(&self, file: P, line: usize, column: usize) -> Option<&Path> {
+        let file = file.as_ref();
+        self.modules
+            .iter()
+            .filter(|module| {
+                module
+                    .span
+                    .location()
+                    .map(|location| contains(&location, file, line, column))
+                    .unwrap_or(false)
+            })
+            .find_map(|module| module.file.as_deref())
+    }
+
+    /// The locations of the clauses which define a predicate, or of the directive which
+    /// declares it if it has no clauses.
+    pub fn definitions(&self, handle: &Handle) -> Vec<Location> {
+        let mut definitions: Vec<_> = self
+            .definitions
+            .get(self.resolve(handle))
+            .into_iter()
+            .flatten()
+            .filter_map(SourceSpan::location)
+            .collect();
+        sort(&mut definitions);
+        definitions
+    }
+
+    /// The locations at which a predicate is called, imported or exported.
+    pub fn references(&self, handle: &Handle) -> Vec<Location> {
+        let handle = self.resolve(handle);
+        let mut references: Vec<_> = self
+            .references
+            .iter()
+            .filter(|(reference, _)| self.resolve(reference) == handle)
+            .filter_map(|(_, span)| span.location())
+            .collect();
+        sort(&mut references);
+        references
+    }
+
+    /// Whether a predicate is exported from the module in which it is defined. Predicates from
+    /// libraries can only be referenced if they are exported.
+    pub fn is_exported(&self, handle: &Handle) -> bool {
+        let handle = self.resolve(handle);
+        !handle.library().is_empty() || self.exports.contains(handle)
+    }
+
+    /// The predicates which may be referenced without a path from the module with the given
+    /// source file: those defined in it, and those imported into it.
+    pub fn completions<P: AsRef<Path>>(&self, file: P) -> Vec<Handle> {
+        let file = file.as_ref();
+        let mut completions: Vec<_> = self
+            .modules
+            .iter()
+            .filter(|module| module.file.as_deref() == Some(file))
+            .flat_map(|module| module.visible.iter().cloned())
+            .collect();
+        completions.sort_by_cached_key(ToString::to_string);
+        completions.dedup();
+        completions
+    }
+
+    /// Follows a chain of imports back to the predicate it refers to.
+    fn resolve<'a>(&'a self, mut handle: &'a Handle) -> &'a Handle {
+        for _ in 0..=self.aliases.len() {
+            match self.aliases.get(handle) {
+                Some(source) => handle = source,
+                None => break,
+            }
+        }
+        handle
+    }
+}
+
+fn sort(locations: &mut [Location]) {
+    locations.sort_by(|a, b| (a.file(), a.line, a.column).cmp(&(b.file(), b.line, b.column)));
+}
+
+fn contains(location: &Location, file: &Path, line: usize, column: usize) -> bool {
+    location.file() == Some(file)
+        && (location.line, location.column) <= (line, column)
+        && (line, column) <= (location.end_line, location.end_column)
+}
@@ -15,11 +15,19 @@ impl Binding {
         input_binding: &Self,
         source: &Query,
         destination: &Query,
+    ) -> Option<Self> {
+        self.transfer_patterns_from(input_binding, &source.patterns, &destination.patterns)
+    }
+
+    pub(crate) fn transfer_patterns_from(
+        self,
+        input_binding: &Self,
+        source: &[Pattern],
+        destination: &[Pattern],
     ) -> Option<Self> {
         source
-            .patterns
             .iter()
-            .zip(destination.patterns.iter())
+            .zip(destination.iter())
             .try_fold(self, |binding, (source, destination)| {
                 let applied = input_binding.apply(source).unwrap();
                 let (_, binding) = unify_patterns(&applied, destination, binding, &[])?;
@@ -133,6 +141,27 @@ impl Binding {
                     .flatten();
                 Ok(Pattern::Record(fields, rest))
             }
+            Pattern::Map(entries, rest) => {
+                let mut entries = entries
+                    .iter()
+                    .map(|(key, pattern)| Ok((key.clone(), self.apply(pattern)?)))
+                    .collect::<crate::Result<Entries>>()?;
+                let rest = rest
+                    .as_ref()
+                    .map(|pattern| -> crate::Result<Option<Box<Pattern>>> {
+                        match self.apply(&*pattern)? {
+                            Pattern::Map(mut head, rest) => {
+                                entries.append(&mut head);
+                                Ok(rest)
+                            }
+                            Pattern::Wildcard => Ok(Some(Box::new(Pattern::Wildcard))),
+                            _ => panic!("We have unified a map with a non-map value. This should not happen."),
+                        }
+                    })
+                    .transpose()?
+                    .flatten();
+                Ok(Pattern::Map(entries, rest))
+            }
             Pattern::Struct(crate::ast::Struct { name, contents }) => {
                 let contents = contents
                     .as_deref()
@@ -144,6 +173,14 @@ impl Binding {
                     contents: contents,
                 }))
             }
+            Pattern::Lambda(lambda) => {
+                let captures = lambda
+                    .captures
+                    .iter()
+                    .map(|(identifier, pattern)| Ok((identifier.clone(), self.apply(pattern)?)))
+                    .collect::<crate::Result<Vec<_>>>()?;
+                Ok(Pattern::Lambda(Box::new(lambda.with_captures(captures))))
+            }
             Pattern::Literal(..) => Ok(pattern.clone()),
             Pattern::Any(..) => Ok(pattern.clone()),
             Pattern::Bound(inner) | Pattern::Unbound(inner) => Ok(self.apply(inner)?),
@@ -1,6 +1,7 @@
-use super::{Lumber, Value};
+use super::{Analysis, Lumber, Value};
 use crate::ast::*;
 use crate::program::*;
+use crate::{Lint, LintLevel};
 use std::collections::HashMap;
 use std::path::Path;
 
@@ -38,6 +39,46 @@ impl<'p> LumberBuilder<'p> {
         self
     }
 
+    /// Sets whether to check the determinism of predicates while answering questions. Debug
+    /// mode is disabled by default.
+    ///
+    /// In debug mode, a predicate declared with `:- det` that produces the wrong number of
+    /// answers when called in one of its declared modes is reported as an error by
+    /// [`Lumber::ask_checked`][]. Only answers which are actually requested are counted.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use lumber::Lumber;
+    /// Lumber::builder()
+    ///     .debug(true) // Enables determinism checks
+    ///     // ...
+    /// #   ;
+    /// ```
+    pub fn debug(mut self, debug: bool) -> Self {
+        self.context.debug = debug;
+        self
+    }
+
+    /// Sets the level at which a lint is reported for the whole program. All lints warn by
+    /// default. A level set by a module using the `:- allow`, `:- warn` or `:- deny` directives
+    /// takes precedence over the level set here.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use lumber::{Lint, LintLevel, Lumber};
+    /// Lumber::builder()
+    ///     .lint(Lint::SingletonVariable, LintLevel::Warn) // Singleton variables are only warnings
+    ///     .lint(Lint::UnusedImport, LintLevel::Allow) // Unused imports are not reported
+    ///     // ...
+    /// #   ;
+    /// ```
+    pub fn lint(mut self, lint: Lint, level: LintLevel) -> Self {
+        self.context.lints.insert(lint, level);
+        self
+    }
+
     /// Bind a native function to the Lumber program.
     ///
     /// Arbitrary Rust code can be attached to the Lumber program at a particular
@@ -199,6 +240,60 @@ impl<'p> LumberBuilder<'p> {
         P: AsRef<Path>,
         S: AsRef<str>,
     {
+        self.link_core();
+        Lumber::new(self.context, root, source, self.natives)
+    }
+
+    /// Uses this builder to check a Lumber program located on the file system without compiling
+    /// it, as an editor would. Native functions declared by the program do not need to be bound.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use lumber::Lumber;
+    /// # use std::path::PathBuf;
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let path_to_main = PathBuf::from(file!()).parent().unwrap().join("test/bind/main.lumber");
+    /// let analysis = Lumber::builder().analyze_file(&path_to_main)?;
+    /// assert!(analysis.diagnostics().is_empty());
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// If the main module's source file, or the source files of any submodules cannot be found,
+    /// an error (typically wrapping an [`std::io::Error`]) is returned. Problems with the program
+    /// itself are not errors, but are listed in the [`Analysis::diagnostics`][].
+    pub fn analyze_file<S>(self, source: S) -> crate::Result<Analysis>
+    where
+        S: AsRef<Path>,
+    {
+        let source_code = std::fs::read_to_string(&source)?;
+        self.analyze(source, source_code)
+    }
+
+    /// Uses this builder to check a Lumber program from a source string without compiling it.
+    ///
+    /// Submodules referenced from the main module are resolved relative to the directory wich is
+    /// passed in to this method.
+    ///
+    /// # Errors
+    ///
+    /// If the source files of any submodules cannot be found, an error (typically wrapping an
+    /// [`std::io::Error`]) is returned. Problems with the program itself are not errors, but are
+    /// listed in the [`Analysis::diagnostics`][].
+    pub fn analyze<P, S>(mut self, root: P, source: S) -> crate::Result<Analysis>
+    where
+        P: AsRef<Path>,
+        S: AsRef<str>,
+    {
+        self.link_core();
+        self.context
+            .analyze(root.as_ref().to_owned(), source.as_ref())
+    }
+
+    fn link_core(&mut self) {
         if self.core {
             crate::core::LIB.with(|lib| {
                 let core = Atom::from("core");
@@ -207,6 +302,5 @@ impl<'p> LumberBuilder<'p> {
                     .insert(core, lib.clone().into_library("core"));
             });
         }
-        Lumber::new(self.context, root, source, self.natives)
     }
 }
@@ -0,0 +1,209 @@
+use super::{Analysis, ModuleInfo};
+use crate::ast::Handle;
+
+/// The format in which the documentation of a module is written by [`Analysis::document`][].
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum DocFormat {
+    /// Markdown, in the style of a README.
+    Markdown,
+    /// A standalone HTML page.
+    Html,
+}
+
+impl DocFormat {
+    /// The extension of a file written in this format. Links between the pages of modules refer
+    /// to files with this extension.
+    pub fn extension(self) -> &'static str {
+        match self {
+            DocFormat::Markdown => "md",
+            DocFormat::Html => "html",
+        }
+    }
+}
+
+impl Analysis {
+    /// Writes the documentation of a module, listing the predicates it exports along with their
+    /// doc comments, and linking to the pages of its submodules.
+    ///
+    /// The page of each module is expected to be written to a file named `index`, with the
+    /// [extension][DocFormat::extension] of the format, in a directory named after the
+    /// [path][ModuleInfo::path] of the module from the main module.
+    pub fn document(&self, module: &ModuleInfo, format: DocFormat) -> String {
+        let markdown = markdown(self, module, format);
+        match format {
+            DocFormat::Markdown => markdown,
+            DocFormat::Html => html(&module.to_string(), &markdown),
+        }
+    }
+}
+
+/// Writes the documentation of a module as Markdown, linking to the pages of its submodules in
+/// the given format.
+fn markdown(analysis: &Analysis, module: &ModuleInfo, format: DocFormat) -> String {
+    let mut markdown = format!("# Module `{}`\n\n", module);
+    if !module.doc().is_empty() {
+        markdown.push_str(module.doc());
+        markdown.push_str("\n\n");
+    }
+
+    let path = module.path();
+    let submodules: Vec<_> = analysis
+        .modules()
+        .iter()
+        .filter(|submodule| {
+            let subpath = submodule.path();
+            subpath.len() == path.len() + 1 && subpath.starts_with(&path)
+        })
+        .collect();
+    if !submodules.is_empty() {
+        markdown.push_str("## Modules\n\n");
+        for submodule in submodules {
+            let name = submodule.path().pop().unwrap();
+            markdown.push_str(&format!(
+                "- [`{}`]({}/index.{})",
+                name,
+                name,
+                format.extension(),
+            ));
+            if let Some(summary) = submodule.doc().lines().next() {
+                markdown.push_str(": ");
+                markdown.push_str(summary);
+            }
+            markdown.push('\n');
+        }
+        markdown.push('\n');
+    }
+
+    markdown.push_str("## Predicates\n\n");
+    if module.exports().is_empty() {
+        markdown.push_str("This module does not export any predicates.\n\n");
+    }
+    for handle in module.exports() {
+        markdown.push_str(&format!(
+            "### `{}`\n\n```lumber\n{}\n```\n\n",
+            handle.to_string().rsplit("::").next().unwrap(),
+            usage(handle),
+        ));
+        if let Some(doc) = analysis.doc(handle) {
+            markdown.push_str(doc);
+            markdown.push_str("\n\n");
+        }
+    }
+    markdown.pop();
+    markdown
+}
+
+/// How a predicate is called, with a `_` in place of each of its arguments.
+fn usage(handle: &Handle) -> String {
+    let mut arguments = vec!["_".to_owned(); handle.positional_arity() as usize];
+    for (name, len) in handle.fields() {
+        arguments.push(format!("{}: _", name));
+        arguments.extend((1..len).map(|_| "_".to_owned()));
+    }
+    if arguments.is_empty() {
+        handle.name()
+    } else {
+        format!("{}({})", handle.name(), arguments.join(", "))
+    }
+}
+
+/// Converts the Markdown written by [`markdown`][] to an HTML page. Only the Markdown which is
+/// commonly found in doc comments is supported: headings, lists, fenced code blocks, paragraphs,
+/// inline code and links.
+fn html(title: &str, markdown: &str) -> String {
+    let mut html = format!(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{}</title>\n</head>\n<body>\n",
+        escape(title),
+    );
+    let mut lines = markdown.lines().peekable();
+    while let Some(line) = lines.next() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        if let Some(language) = line.strip_prefix("```") {
+            match language.trim() {
+                "" => html.push_str("<pre><code>"),
+                language => html.push_str(&format!(
+                    "<pre><code class=\"language-{}\">",
+                    escape(language)
+                )),
+            }
+            for line in lines.by_ref().take_while(|line| line.trim() != "```") {
+                html.push_str(&escape(line));
+                html.push('\n');
+            }
+            html.push_str("</code></pre>\n");
+        } else if let Some(item) = line.strip_prefix("- ") {
+            html.push_str("<ul>\n");
+            html.push_str(&format!("<li>{}</li>\n", inline(item)));
+            while let Some(item) = lines.peek().and_then(|line| line.strip_prefix("- ")) {
+                html.push_str(&format!("<li>{}</li>\n", inline(item)));
+                lines.next();
+            }
+            html.push_str("</ul>\n");
+        } else if line.starts_with('#') {
+            let level = line.chars().take_while(|&ch| ch == '#').count().min(6);
+            html.push_str(&format!(
+                "<h{}>{}</h{}>\n",
+                level,
+                inline(line.trim_start_matches('#').trim()),
+                level,
+            ));
+        } else {
+            let mut paragraph = line.to_owned();
+            while let Some(line) = lines.peek() {
+                if line.trim().is_empty()
+                    || line.starts_with("```")
+                    || line.starts_with("- ")
+                    || line.starts_with('#')
+                {
+                    break;
+                }
+                paragraph.push('\n');
+                paragraph.push_str(line);
+                lines.next();
+            }
+            html.push_str(&format!("<p>{}</p>\n", inline(&paragraph)));
+        }
+    }
+    html.push_str("</body>\n</html>\n");
+    html
+}
+
+/// Converts the inline code and links in a line of Markdown to HTML.
+fn inline(text: &str) -> String {
+    let mut html = String::new();
+    let mut rest = text;
+    while let Some(index) = rest.find(['`', '[']) {
+        html.push_str(&escape(&rest[..index]));
+        rest = &rest[index..];
+        if let Some(code) = rest.strip_prefix('`') {
+            if let Some(end) = code.find('`') {
+                html.push_str(&format!("<code>{}</code>", escape(&code[..end])));
+                rest = &code[end + 1..];
+                continue;
+            }
+        } else if let Some((text, link)) = rest[1..].split_once("](") {
+            if let Some(end) = link.find(')') {
+                html.push_str(&format!(
+                    "<a href=\"{}\">{}</a>",
+                    escape(&link[..end]),
+                    inline(text),
+                ));
+                rest = &link[end + 1..];
+                continue;
+            }
+        }
+        html.push_str(&escape(&rest[..1]));
+        rest = &rest[1..];
+    }
+    html.push_str(&escape(rest));
+    html
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
@@ -1,4 +1,5 @@
 use super::Value;
+use crate::ast::{collection, display, Doc, Pretty};
 use std::fmt::{self, Display, Formatter};
 use std::iter::FromIterator;
 use std::ops::{Index, IndexMut};
@@ -71,19 +72,24 @@ where
     }
 }
 
+impl Pretty for List {
+    fn doc(&self) -> Doc {
+        collection(
+            "[",
+            self.values.iter().map(Pretty::doc).collect(),
+            if self.complete {
+                None
+            } else {
+                Some(Doc::from(""))
+            },
+            "]",
+            "[]",
+        )
+    }
+}
+
 impl Display for List {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
-        write!(f, "[")?;
-        for (i, value) in self.values.iter().enumerate() {
-            if i != 0 {
-                write!(f, ", ")?;
-            }
-            match value {
-                Some(value) => value.fmt(f)?,
-                None => write!(f, "_")?,
-            }
-        }
-        write!(f, "]")?;
-        Ok(())
+        display(self, f)
     }
 }
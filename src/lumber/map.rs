@@ -0,0 +1,121 @@
+use super::Value;
+use crate::ast::{collection, display, Doc, Pretty};
+use std::fmt::{self, Display, Formatter};
+
+/// An implementation of a map which may be incomplete, suitable for Lumber values which
+/// may themselves be unbound.
+///
+/// Unlike a [`Record`][crate::Record], the keys of a map may be any value, but they must be fully
+/// bound. Keys are compared structurally, so the integer `1` and the rational `1.0` are different
+/// keys.
+#[derive(Clone, Debug)]
+pub struct Map {
+    pub(crate) entries: Vec<(Value, Option<Value>)>,
+    pub(crate) complete: bool,
+}
+
+impl Default for Map {
+    fn default() -> Self {
+        Self {
+            entries: Vec::default(),
+            complete: true,
+        }
+    }
+}
+
+impl PartialEq for Map {
+    fn eq(&self, other: &Self) -> bool {
+        self.entries.len() == other.entries.len()
+            && self
+                .entries
+                .iter()
+                .all(|(key, value)| other.get(key) == Some(value))
+    }
+}
+
+impl Map {
+    pub(crate) fn new(entries: Vec<(Value, Option<Value>)>, complete: bool) -> Self {
+        Self { entries, complete }
+    }
+
+    /// Adds an entry to this map.
+    pub fn with(mut self, key: impl Into<Value>, value: Option<Value>) -> Self {
+        self.set(key, value);
+        self
+    }
+
+    /// Sets an entry of this map, replacing any existing entry with the same key.
+    pub fn set(&mut self, key: impl Into<Value>, value: Option<Value>) {
+        let key = key.into();
+        match self
+            .entries
+            .iter_mut()
+            .find(|(existing, _)| *existing == key)
+        {
+            Some(entry) => entry.1 = value,
+            None => self.entries.push((key, value)),
+        }
+    }
+
+    /// Gets the value associated with a key in this map.
+    pub fn get(&self, key: &Value) -> Option<&Option<Value>> {
+        self.entries
+            .iter()
+            .find(|(existing, _)| existing == key)
+            .map(|(_, value)| value)
+    }
+
+    /// Removes an entry from this map, returning its value if it was present.
+    pub fn remove(&mut self, key: &Value) -> Option<Option<Value>> {
+        let index = self
+            .entries
+            .iter()
+            .position(|(existing, _)| existing == key)?;
+        Some(self.entries.remove(index).1)
+    }
+
+    /// The number of entries in this map.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Whether this map has no entries.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Iterates over the entries of this map.
+    pub fn iter(&self) -> impl Iterator<Item = (&Value, &Option<Value>)> {
+        self.entries.iter().map(|(key, value)| (key, value))
+    }
+
+    /// Iterates over the entries of this map, mutably.
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (&Value, &mut Option<Value>)> {
+        self.entries.iter_mut().map(|(key, value)| (&*key, value))
+    }
+}
+
+impl Pretty for Map {
+    fn doc(&self) -> Doc {
+        collection(
+            "{",
+            self.entries
+                .iter()
+                .map(|(key, value)| Doc::Concat(vec![key.doc(), Doc::from(" => "), value.doc()]))
+                .collect(),
+            if self.complete {
+                None
+            } else {
+                Some(Doc::from(""))
+            },
+            "}",
+            "{=>}",
+        )
+    }
+}
+
+impl Display for Map {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        display(self, f)
+    }
+}
@@ -5,30 +5,39 @@ use crate::ast::*;
 use crate::program::*;
 use std::collections::HashMap;
 use std::path::Path;
+use std::rc::Rc;
 
 #[macro_use]
 mod macros;
 
+mod analysis;
 mod answer;
 mod binding;
 mod builder;
+mod doc;
 mod list;
+mod map;
 mod question;
 mod record;
 #[cfg(feature = "builtin-sets")]
 mod set;
 mod r#struct;
+mod test_failure;
 mod value;
 
+pub use analysis::{Analysis, ModuleInfo};
 pub use answer::FromBinding;
 pub use binding::Binding;
 pub use builder::LumberBuilder;
+pub use doc::DocFormat;
 pub use list::List;
+pub use map::Map;
 pub use question::Question;
 pub use r#struct::Struct;
 pub use record::Record;
 #[cfg(feature = "builtin-sets")]
 pub use set::Set;
+pub use test_failure::TestFailure;
 pub use value::Value;
 
 /// A Lumber program, for use either as a full program, or linked to by another Lumber program
@@ -36,6 +45,7 @@ pub use value::Value;
 #[derive(Clone, Debug)]
 pub struct Lumber<'p> {
     pub(crate) database: Database<'p>,
+    warnings: Rc<[crate::Error]>,
 }
 
 impl Default for Lumber<'_> {
@@ -113,8 +123,18 @@ impl<'p> Lumber<'p> {
         context.compile(source_file.as_ref().to_owned(), source_str, natives)
     }
 
-    pub(crate) fn build(database: Database<'p>) -> Self {
-        Self { database }
+    pub(crate) fn build(database: Database<'p>, warnings: Vec<crate::Error>) -> Self {
+        Self {
+            database,
+            warnings: warnings.into(),
+        }
+    }
+
+    /// The problems found while compiling this program which did not prevent it from being
+    /// compiled, each of kind [`ErrorKind::Warning`][crate::ErrorKind::Warning]. Which problems
+    /// are reported can be configured using [`LumberBuilder::lint`][].
+    pub fn warnings(&self) -> &[crate::Error] {
+        &self.warnings
     }
 
     /// Ask a question, returning an iterator over all possible answers, attempting to
@@ -131,16 +151,54 @@ impl<'p> Lumber<'p> {
     }
 
     /// Ask a question, returning an iterator over all possible answers, in raw binding form.
+    ///
+    /// In debug mode, the answers end early if a predicate is found to violate its declared
+    /// determinism. Use [`ask_checked`][Self::ask_checked] to find out when this happens.
     pub fn ask<'a>(&'a self, query: &'a Question) -> impl Iterator<Item = Binding> + 'a {
         self.database.unify_question(query)
     }
 
+    /// Ask a question, returning an iterator over all possible answers, in raw binding form.
+    ///
+    /// # Errors
+    ///
+    /// In debug mode, if a predicate is found to violate its declared determinism while looking
+    /// for an answer, the answers end with an error of kind
+    /// [`ErrorKind::Determinism`][crate::ErrorKind::Determinism].
+    pub fn ask_checked<'a>(
+        &'a self,
+        query: &'a Question,
+    ) -> impl Iterator<Item = crate::Result<Binding>> + 'a {
+        self.database.unify_question_checked(query)
+    }
+
     /// Ask a question, checking whether an answer exists. An answer, if it exists, may not
     /// necessarily be fully bound.
     pub fn check<'a>(&'a self, query: &'a Question) -> bool {
         self.ask(query).next().is_some()
     }
 
+    /// The tests declared by `:- test` directives in all modules of this program, ordered by
+    /// name.
+    pub fn tests(&self) -> Vec<Handle> {
+        let mut tests = self.database.tests().to_vec();
+        tests.sort_by_cached_key(ToString::to_string);
+        tests
+    }
+
+    /// Runs one of the tests of this program, which are listed by [`Lumber::tests`][]. A test
+    /// passes if its predicate has an answer. Tests may be private to their module, and do not
+    /// depend on each other.
+    ///
+    /// # Errors
+    ///
+    /// If the test has no answers, the returned failure describes the goal of its body at which
+    /// it ran out of answers. A handle which is not one of the tests of this program, such as a
+    /// test of another program, fails without running anything.
+    pub fn run_test(&self, test: &Handle) -> Result<(), TestFailure> {
+        self.database.run_test(test)
+    }
+
     pub(crate) fn into_library(self, name: &str) -> Database<'p> {
         self.database.into_library(name)
     }
@@ -1,6 +1,6 @@
 use crate::ast::*;
 use crate::parser::*;
-use crate::{Binding, Value};
+use crate::{Binding, CompileError, Value};
 use std::collections::BTreeMap;
 use std::convert::TryFrom;
 
@@ -101,7 +101,14 @@ impl TryFrom<&str> for Question {
         let mut pairs = pair.into_inner();
         let pair = pairs.next().unwrap();
         let mut context = Context::default();
-        let body = Body::new(pair, &mut context).unwrap();
+        let body = Body::new(pair, &mut context);
+        if !context.errors.is_empty() {
+            return Err(crate::Error::multiple_by_module(context.errors));
+        }
+        let body = body.unwrap();
+        if !context.take_lambdas().is_empty() {
+            return Err(crate::Error::compile(CompileError::LambdaInQuestion));
+        }
         let initial_binding = body.identifiers().collect();
         Ok(Question {
             body,
@@ -55,20 +55,30 @@ impl Record {
     }
 }
 
+/// The fields of a record are written in order of their keys.
+impl Pretty for Record {
+    fn doc(&self) -> Doc {
+        let mut fields: Vec<_> = self.fields.iter().collect();
+        fields.sort_by_key(|(key, _)| *key);
+        collection(
+            "{",
+            fields
+                .into_iter()
+                .map(|(key, value)| Doc::Concat(vec![Doc::from(format!("{}: ", key)), value.doc()]))
+                .collect(),
+            if self.complete {
+                None
+            } else {
+                Some(Doc::from(""))
+            },
+            "}",
+            "{:}",
+        )
+    }
+}
+
 impl Display for Record {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
-        write!(f, "{{")?;
-        for (i, (key, value)) in self.fields.iter().enumerate() {
-            if i != 0 {
-                write!(f, ", ")?;
-            }
-            write!(f, "{}: ", key)?;
-            match value {
-                Some(value) => value.fmt(f)?,
-                None => write!(f, "_")?,
-            }
-        }
-        write!(f, "}}")?;
-        Ok(())
+        display(self, f)
     }
 }
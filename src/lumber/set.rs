@@ -1,4 +1,5 @@
 use super::Value;
+use crate::ast::{collection, display, Doc, Pretty};
 use std::fmt::{self, Display, Formatter};
 
 /// An implementation of a set which may be incomplete, suitable for Lumber values which
@@ -15,20 +16,24 @@ impl Set {
     }
 }
 
+impl Pretty for Set {
+    fn doc(&self) -> Doc {
+        collection(
+            "{",
+            self.values.iter().map(Pretty::doc).collect(),
+            if self.complete {
+                None
+            } else {
+                Some(Doc::from(""))
+            },
+            "}",
+            "{}",
+        )
+    }
+}
+
 impl Display for Set {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
-        write!(f, "{{")?;
-        let mut values = self.values.iter();
-        for (i, value) in self.values.iter().enumerate() {
-            if i != 0 {
-                write!(f, ", ")?;
-            }
-            match value {
-                Some(value) => value.fmt(f)?,
-                None => write!(f, "_")?,
-            }
-        }
-        write!(f, "}}")?;
-        Ok(())
+        display(self, f)
     }
 }
@@ -97,15 +97,31 @@ impl Struct {
     }
 }
 
+impl Pretty for Struct {
+    fn doc(&self) -> Doc {
+        structure(
+            &self.name,
+            self.contents
+                .as_deref()
+                .map(|contents| (contents.doc(), is_collection(contents))),
+        )
+    }
+}
+
+/// Whether a value is written as a bracketed collection, which may directly follow the name of
+/// a struct.
+fn is_collection(value: &Option<Value>) -> bool {
+    match value {
+        Some(Value::List(list)) => !list.values.is_empty() || list.complete,
+        Some(Value::Record(record)) => !record.fields.is_empty() || record.complete,
+        #[cfg(feature = "builtin-sets")]
+        Some(Value::Set(set)) => !set.values.is_empty() || set.complete,
+        _ => false,
+    }
+}
+
 impl Display for Struct {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
-        self.name.fmt(f)?;
-        if let Some(contents) = &self.contents {
-            match contents.as_ref() {
-                Some(contents) => write!(f, "({})", contents)?,
-                None => write!(f, "(_)")?,
-            }
-        }
-        Ok(())
+        display(self, f)
     }
 }
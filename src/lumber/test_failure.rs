@@ -0,0 +1,76 @@
+use super::{Binding, Value};
+use crate::ast::*;
+use crate::Location;
+use std::fmt::{self, Display, Formatter};
+
+/// Describes why a test, declared with the `:- test` directive, failed.
+///
+/// A test fails when its predicate has no answers. The goal at which it failed is the first goal
+/// of its body which has no answers given those before it.
+#[derive(Clone, Debug)]
+pub struct TestFailure {
+    goal: String,
+    location: Option<Location>,
+    bindings: Vec<(String, Value)>,
+}
+
+impl TestFailure {
+    pub(crate) fn new(head: &Query, goal: &Body, binding: &Binding) -> Self {
+        let location = goal
+            .queries()
+            .find_map(|query| query.span.location())
+            .or_else(|| head.span.location())
+            .or_else(|| head.as_ref().span.location());
+        let mut bindings: Vec<(String, Value)> = vec![];
+        for identifier in goal.identifiers() {
+            if identifier.is_wildcard()
+                || identifier.name().starts_with('#')
+                || bindings.iter().any(|(name, _)| name == identifier.name())
+            {
+                continue;
+            }
+            let value = binding
+                .get(&identifier)
+                .and_then(|pattern| binding.extract(pattern).ok())
+                .flatten();
+            if let Some(value) = value {
+                bindings.push((identifier.name().to_owned(), value));
+            }
+        }
+        Self {
+            goal: goal.to_string(),
+            location,
+            bindings,
+        }
+    }
+
+    /// The goal which had no answers, written in Lumber syntax.
+    pub fn goal(&self) -> &str {
+        &self.goal
+    }
+
+    /// Where the goal which had no answers was written in the source code.
+    pub fn location(&self) -> Option<&Location> {
+        self.location.as_ref()
+    }
+
+    /// The values of the variables of the goal which had no answers, as they were bound by the
+    /// first answer to the goals before it. Variables which were not bound are not included.
+    pub fn bindings(&self) -> &[(String, Value)] {
+        &self.bindings
+    }
+}
+
+impl Display for TestFailure {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "Goal `{}` has no answers", self.goal)?;
+        if let Some(location) = &self.location {
+            write!(f, " at {}", location)?;
+        }
+        write!(f, ".")?;
+        for (name, value) in &self.bindings {
+            write!(f, "\n    where {} = {}", name, value)?;
+        }
+        Ok(())
+    }
+}
@@ -1,7 +1,7 @@
 #[cfg(feature = "builtin-sets")]
 use super::Set;
-use super::{List, Record, Struct};
-use crate::ast::{Atom, Literal, Pattern};
+use super::{List, Map, Record, Struct};
+use crate::ast::*;
 use ramp::{int::Int, rational::Rational};
 use std::any::Any;
 use std::collections::HashMap;
@@ -15,6 +15,8 @@ pub enum Value {
     Integer(Int),
     /// An arbitrary precision rational value.
     Rational(Rational),
+    /// A floating point value.
+    Float(f64),
     /// A string value.
     String(String),
     /// An unordered, duplicate-free collection of values.
@@ -24,6 +26,8 @@ pub enum Value {
     List(List),
     /// A set of key value(s) pairs.
     Record(Record),
+    /// A set of key value pairs, where the keys may be any value.
+    Map(Map),
     /// A structural value. Atoms are really just structs with no fields.
     Struct(Struct),
     /// An unknown Rust value.
@@ -36,12 +40,14 @@ impl PartialEq for Value {
         match (self, other) {
             (Value::Integer(lhs), Value::Integer(rhs)) => lhs == rhs,
             (Value::Rational(lhs), Value::Rational(rhs)) => lhs == rhs,
+            (Value::Float(lhs), Value::Float(rhs)) => Float(*lhs) == Float(*rhs),
             (Value::String(lhs), Value::String(rhs)) => lhs == rhs,
             #[cfg(feature = "builtin-sets")]
             (Value::Set(lhs), Value::Set(rhs)) => lhs == rhs,
             (Value::List(lhs), Value::List(rhs)) => lhs == rhs,
             (Value::Struct(lhs), Value::Struct(rhs)) => lhs == rhs,
             (Value::Record(lhs), Value::Record(rhs)) => lhs == rhs,
+            (Value::Map(lhs), Value::Map(rhs)) => lhs == rhs,
             (Value::Any(lhs), Value::Any(rhs)) => Rc::ptr_eq(lhs, rhs),
             _ => false,
         }
@@ -100,6 +106,15 @@ impl Value {
     as_variant!(as_rational, Rational, Rational);
     as_variant_mut!(as_rational_mut, Rational, Rational);
 
+    /// Constructs a floating point value.
+    pub fn float(float: impl Into<f64>) -> Self {
+        Self::Float(float.into())
+    }
+
+    is_variant!(is_float, Float);
+    as_variant!(as_float, f64, Float);
+    as_variant_mut!(as_float_mut, f64, Float);
+
     /// Constructs a string value.
     pub fn string(string: impl Into<String>) -> Self {
         Self::String(string.into())
@@ -114,6 +129,27 @@ impl Value {
         Self::Struct(Struct::atom(name))
     }
 
+    /// Constructs a boolean value. Lumber has no separate boolean type; booleans are represented
+    /// by the atoms `true` and `false`, which are also what the comparison and boolean operators
+    /// of `@core` evaluate to.
+    pub fn bool(value: bool) -> Self {
+        Self::atom(if value { "true" } else { "false" })
+    }
+
+    /// Checks whether this value is one of the atoms `true` or `false`.
+    pub fn is_bool(&self) -> bool {
+        self.as_bool().is_some()
+    }
+
+    /// Gets this value as a boolean, if it is one of the atoms `true` or `false`.
+    pub fn as_bool(&self) -> Option<bool> {
+        match self.as_struct()?.as_atom()? {
+            "true" => Some(true),
+            "false" => Some(false),
+            _ => None,
+        }
+    }
+
     is_variant!(is_struct, Struct);
     as_variant!(as_struct, Struct, Struct);
     as_variant_mut!(as_struct_mut, Struct, Struct);
@@ -150,6 +186,40 @@ impl Value {
     as_variant!(as_record, Record, Record);
     as_variant_mut!(as_record_mut, Record, Record);
 
+    /// Constructs a Lumber value containing a map.
+    pub fn map<K, V>(entries: impl IntoIterator<Item = (K, V)>) -> Self
+    where
+        Value: From<K> + From<V>,
+    {
+        Self::Map(Map::new(
+            entries
+                .into_iter()
+                .map(|(k, v)| (Value::from(k), Some(Value::from(v))))
+                .collect(),
+            true,
+        ))
+    }
+
+    is_variant!(is_map, Map);
+    as_variant!(as_map, Map, Map);
+    as_variant_mut!(as_map_mut, Map, Map);
+
+    /// Prints this value as Lumber source code, laid out to fit within `width` columns where
+    /// possible. Collections which do not fit on one line are broken onto several, with each of
+    /// their items on its own line.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use lumber::Value;
+    /// let value = Value::list(vec![Value::from("hello"), Value::atom("world")]);
+    /// assert_eq!(value.pretty(80), r#"["hello", world]"#);
+    /// assert_eq!(value.pretty(10), "[\n    \"hello\",\n    world,\n]");
+    /// ```
+    pub fn pretty(&self, width: usize) -> String {
+        self.doc().render(Some(width))
+    }
+
     /// Constructs a Lumber value by serializing a Rust value using Serde.
     #[cfg(feature = "serde")]
     pub fn serialize<T: serde::Serialize>(value: &T) -> crate::Result<Self> {
@@ -178,6 +248,18 @@ impl From<Rational> for Value {
     }
 }
 
+impl From<f64> for Value {
+    fn from(float: f64) -> Self {
+        Self::Float(float)
+    }
+}
+
+impl From<bool> for Value {
+    fn from(value: bool) -> Self {
+        Self::bool(value)
+    }
+}
+
 impl From<String> for Value {
     fn from(string: String) -> Self {
         Self::String(string)
@@ -207,6 +289,7 @@ impl From<Pattern> for Option<Value> {
             Pattern::Bound(inner) | Pattern::Unbound(inner) => (*inner).into(),
             Pattern::Literal(Literal::Integer(int)) => Some(Value::Integer(int.to_owned())),
             Pattern::Literal(Literal::Rational(rat)) => Some(Value::Rational(rat.to_owned())),
+            Pattern::Literal(Literal::Float(Float(float))) => Some(Value::Float(float)),
             Pattern::Literal(Literal::String(string)) => Some(Value::String(string.to_owned())),
             Pattern::List(patterns, rest) => {
                 let values = patterns.into_iter().map(Into::into).collect();
@@ -227,6 +310,15 @@ impl From<Pattern> for Option<Value> {
                 let complete = rest.is_none();
                 Some(Value::Record(Record::new(values, complete)))
             }
+            Pattern::Map(entries, rest) => {
+                // Map keys are always ground, so none are lost here.
+                let entries = entries
+                    .into_iter()
+                    .filter_map(|(key, pattern)| Some((Option::from(key)?, pattern.into())))
+                    .collect();
+                let complete = rest.is_none();
+                Some(Value::Map(Map::new(entries, complete)))
+            }
             Pattern::Struct(structure) => {
                 let contents = structure
                     .contents
@@ -234,6 +326,8 @@ impl From<Pattern> for Option<Value> {
                 Some(Value::Struct(Struct::raw(structure.name.clone(), contents)))
             }
             Pattern::Any(any) => Some(Value::Any(any)),
+            // Lambdas are opaque to Rust, so they are passed around as `Any` values.
+            Pattern::Lambda(lambda) => Some(Value::any(*lambda)),
         }
     }
 }
@@ -244,6 +338,7 @@ impl Into<Pattern> for Option<Value> {
             None => Pattern::Wildcard,
             Some(Value::Integer(int)) => Pattern::Literal(Literal::Integer(int)),
             Some(Value::Rational(rat)) => Pattern::Literal(Literal::Rational(rat)),
+            Some(Value::Float(float)) => Pattern::Literal(Literal::Float(Float(float))),
             Some(Value::String(string)) => Pattern::Literal(Literal::String(string)),
             Some(Value::List(List { values, complete })) => Pattern::List(
                 values.into_iter().map(Into::into).collect(),
@@ -266,27 +361,69 @@ impl Into<Pattern> for Option<Value> {
                     Some(Box::new(Pattern::Wildcard))
                 },
             ),
+            Some(Value::Map(Map { entries, complete })) => Pattern::Map(
+                entries
+                    .into_iter()
+                    .map(|(key, value)| (Some(key).into(), value.into()))
+                    .collect::<Entries>(),
+                if complete {
+                    None
+                } else {
+                    Some(Box::new(Pattern::Wildcard))
+                },
+            ),
             Some(Value::Struct(Struct { name, contents })) => {
                 let contents = contents.map(|contents| Box::new((*contents).into()));
                 Pattern::Struct(crate::ast::Struct::from_parts(name, contents))
             }
-            Some(Value::Any(any)) => Pattern::Any(any),
+            Some(Value::Any(any)) => match any.downcast_ref::<Lambda>() {
+                Some(lambda) => Pattern::Lambda(Box::new(lambda.clone())),
+                None => Pattern::Any(any),
+            },
         }
     }
 }
 
-impl Display for Value {
-    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+impl Pretty for Value {
+    fn doc(&self) -> Doc {
         match self {
-            Value::Integer(int) => int.fmt(f),
-            Value::Rational(rat) => rat.fmt(f),
-            Value::String(string) => write!(f, "{:?}", string),
+            Value::Integer(int) => Doc::from(int.to_string()),
+            Value::Rational(rat) => text(|f| fmt_rational(rat, f)),
+            Value::Float(float) => text(|f| fmt_float(*float, f)),
+            Value::String(string) => text(|f| fmt_string(string, f)),
             #[cfg(feature = "builtin-sets")]
-            Value::Set(set) => set.fmt(f),
-            Value::List(list) => list.fmt(f),
-            Value::Record(record) => record.fmt(f),
-            Value::Struct(structure) => structure.fmt(f),
-            Value::Any(any) => write!(f, "[{:?}]", Rc::as_ptr(any)),
+            Value::Set(set) => set.doc(),
+            Value::List(list) => list.doc(),
+            Value::Record(record) => record.doc(),
+            Value::Map(map) => map.doc(),
+            Value::Struct(structure) => structure.doc(),
+            Value::Any(any) => match any.downcast_ref::<Lambda>() {
+                Some(lambda) => lambda.doc(),
+                None => Doc::from("_"),
+            },
+        }
+    }
+}
+
+/// Unbound values are written as wildcards.
+impl Pretty for Option<Value> {
+    fn doc(&self) -> Doc {
+        match self {
+            Some(value) => value.doc(),
+            None => Doc::from("_"),
         }
     }
 }
+
+/// Values are written as Lumber source code, which is laid out across multiple lines, as by
+/// [`Value::pretty`][], when the alternate flag (`{:#}`) is used.
+///
+/// Incomplete collections are written with a `..` tail, the fields of records are written in
+/// order of their keys, and unbound values and unknown Rust values are written as wildcards.
+/// Numbers which cannot be written as literals (rationals with no finite decimal expansion, and
+/// infinite or `NaN` floats) are written in a form which will not parse.
+impl Display for Value {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        display(self, f)
+    }
+}
@@ -0,0 +1,473 @@
+//! Prints Lumber source code in a canonical layout.
+use super::{Parser, Rule};
+use crate::Pair;
+
+const INDENT: &str = "    ";
+
+/// Formats the source code of a Lumber module with a consistent layout.
+///
+/// Directives are grouped at the top of the module, followed by the clauses, one per line. The
+/// goals in the body of a rule are each printed on their own line, indented beneath its head.
+/// Spacing around operators and punctuation is normalized, and optional trailing commas are
+/// removed. Comments are kept beside the code they were written beside, and blank lines between
+/// directives or clauses are kept, but not within them.
+///
+/// # Errors
+///
+/// If the source code is malformed, the syntax error is returned.
+///
+/// # Examples
+///
+/// ```rust
+/// let formatted = lumber::format("test(A,B):-foo(A),bar( B, ). :- pub(test/2).").unwrap();
+/// assert_eq!(
+///     formatted,
+///     ":- pub(test/2).\n\ntest(A, B) :-\n    foo(A),\n    bar(B).\n",
+/// );
+/// ```
+pub fn format<S: AsRef<str>>(source_code: S) -> crate::Result<String> {
+    let source = source_code.as_ref();
+    let module = Parser::parse_module(source, None)?.next().unwrap();
+    let comments = Parser::parse_comments(source)
+        .into_iter()
+        .map(|span| (span.start(), span.end()))
+        .collect();
+    Ok(Formatter { source, comments }.module(module))
+}
+
+struct Formatter<'i> {
+    source: &'i str,
+    /// The start and end of each comment, in order.
+    comments: Vec<(usize, usize)>,
+}
+
+/// A line of formatted code, along with the comments written around the code it was formatted
+/// from, which are printed on the lines before it or at the end of it.
+struct Line {
+    code: String,
+    indent: usize,
+    start: usize,
+    end: usize,
+    leading: Vec<(usize, usize)>,
+    trailing: Vec<(usize, usize)>,
+}
+
+impl Line {
+    fn new(code: String, indent: usize, pair: &Pair) -> Self {
+        Self {
+            code,
+            indent,
+            start: pair.as_span().start(),
+            end: pair.as_span().end(),
+            leading: vec![],
+            trailing: vec![],
+        }
+    }
+}
+
+/// A directive or clause.
+struct Item {
+    directive: bool,
+    start: usize,
+    end: usize,
+    lines: Vec<Line>,
+    blank_before: bool,
+}
+
+impl Formatter<'_> {
+    fn module(&self, module: Pair) -> String {
+        let mut items: Vec<_> = module
+            .into_inner()
+            .filter(|pair| matches!(pair.as_rule(), Rule::directive | Rule::clause))
+            .map(|pair| self.item(pair))
+            .collect();
+
+        let mut header = vec![];
+        let mut footer = vec![];
+        for &(start, end) in &self.comments {
+            let next = items.iter().position(|item| item.end > start);
+            match next {
+                Some(index) if items[index].start <= start => {
+                    attach(self.source, &mut items[index].lines, (start, end));
+                }
+                _ => {
+                    let next = next.unwrap_or(items.len());
+                    let same_line =
+                        next > 0 && !self.source[items[next - 1].end..start].contains('\n');
+                    if same_line {
+                        let line = items[next - 1].lines.last_mut().unwrap();
+                        line.trailing.push((start, end));
+                    } else if next == 0 {
+                        header.push((start, end));
+                    } else if next == items.len() {
+                        footer.push((start, end));
+                    } else {
+                        items[next].lines[0].leading.push((start, end));
+                    }
+                }
+            }
+        }
+
+        // Comments before the first item stay at the top of the module if they are separated
+        // from it by a blank line, or if they document the module. Otherwise, they stay with the
+        // item.
+        let first = items
+            .first()
+            .map(|item| item.start)
+            .unwrap_or(self.source.len());
+        let detached = (0..header.len())
+            .rev()
+            .find(|&index| {
+                let next = header
+                    .get(index + 1)
+                    .map(|comment| comment.0)
+                    .unwrap_or(first);
+                self.blank_between(header[index].1, next)
+            })
+            .map(|index| index + 1)
+            .unwrap_or(if items.is_empty() { header.len() } else { 0 })
+            .max(
+                header
+                    .iter()
+                    .rposition(|&(start, _)| self.source[start..].starts_with("//!"))
+                    .map_or(0, |index| index + 1),
+            );
+        if let Some(item) = items.first_mut() {
+            let mut leading = header.split_off(detached);
+            leading.append(&mut item.lines[0].leading);
+            item.lines[0].leading = leading;
+        }
+
+        // Directives are moved away from the clauses around them, so an item is separated from
+        // the one before it if there was a blank line anywhere since the last item of its kind.
+        let mut blank = [false, false];
+        for index in 1..items.len() {
+            let previous = &items[index - 1];
+            let end = previous
+                .lines
+                .last()
+                .unwrap()
+                .trailing
+                .last()
+                .map(|comment| comment.1);
+            let start = items[index].lines[0]
+                .leading
+                .first()
+                .map(|comment| comment.0);
+            let gap = self.blank_between(
+                end.unwrap_or(previous.end).max(previous.end),
+                start.unwrap_or(items[index].start),
+            );
+            blank = [blank[0] || gap, blank[1] || gap];
+            let kind = items[index].directive as usize;
+            items[index].blank_before = blank[kind];
+            blank[kind] = false;
+        }
+
+        let mut output = String::new();
+        for &comment in &header {
+            output.push_str(self.comment(comment));
+            output.push('\n');
+        }
+        let (directives, clauses): (Vec<_>, Vec<_>) = items.iter().partition(|item| item.directive);
+        for group in &[directives, clauses] {
+            if group.is_empty() {
+                continue;
+            }
+            if !output.is_empty() {
+                output.push('\n');
+            }
+            for (index, item) in group.iter().enumerate() {
+                if index != 0 && item.blank_before {
+                    output.push('\n');
+                }
+                self.print_lines(&mut output, &item.lines);
+            }
+        }
+        if let Some(&(start, _)) = footer.first() {
+            let end = items.last().map(|item| item.end).unwrap_or(0);
+            if self.blank_between(end, start) {
+                output.push('\n');
+            }
+        }
+        for &comment in &footer {
+            output.push_str(self.comment(comment));
+            output.push('\n');
+        }
+        output
+    }
+
+    fn item(&self, pair: Pair) -> Item {
+        let span = pair.as_span();
+        let directive = pair.as_rule() == Rule::directive;
+        let lines = match pair.as_rule() {
+            Rule::directive => vec![Line::new(self.inline(pair.clone()), 0, &pair)],
+            Rule::clause => self.clause(pair.into_inner().next().unwrap()),
+            _ => unreachable!(),
+        };
+        Item {
+            directive,
+            start: span.start(),
+            end: span.end(),
+            lines,
+            blank_before: false,
+        }
+    }
+
+    fn clause(&self, pair: Pair) -> Vec<Line> {
+        let kind = pair.as_rule();
+        let mut pairs = pair.clone().into_inner();
+        let head = pairs.next().unwrap();
+        match kind {
+            Rule::fact => vec![Line::new(format!("{}.", self.inline(head)), 0, &pair)],
+            Rule::rule => {
+                let mut lines = vec![Line::new(
+                    format!("{} :-", self.inline(head.clone())),
+                    0,
+                    &head,
+                )];
+                let disjunction = pairs.next().unwrap().into_inner().next().unwrap();
+                let conjunctions: Vec<_> = disjunction.into_inner().collect();
+                for (index, conjunction) in conjunctions.iter().enumerate() {
+                    let processions: Vec<_> = conjunction.clone().into_inner().collect();
+                    for (position, procession) in processions.iter().enumerate() {
+                        let separator = if position + 1 != processions.len() {
+                            ","
+                        } else if index + 1 != conjunctions.len() {
+                            ";"
+                        } else {
+                            "."
+                        };
+                        let code = format!("{}{}", self.inline(procession.clone()), separator);
+                        lines.push(Line::new(code, 1, procession));
+                    }
+                }
+                lines
+            }
+            Rule::function => {
+                let terms: Vec<_> = pairs.next().unwrap().into_inner().collect();
+                if terms.len() == 1 {
+                    let code = format!(
+                        "{} <- {}.",
+                        self.inline(head),
+                        self.inline(terms[0].clone())
+                    );
+                    return vec![Line::new(code, 0, &pair)];
+                }
+                let mut lines = vec![Line::new(
+                    format!("{} <-", self.inline(head.clone())),
+                    0,
+                    &head,
+                )];
+                for (index, term) in terms.iter().enumerate() {
+                    let separator = if index + 1 == terms.len() { "." } else { "," };
+                    let code = format!("{}{}", self.inline(term.clone()), separator);
+                    lines.push(Line::new(code, 1, term));
+                }
+                lines
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    /// Prints a part of a directive or clause on a single line.
+    fn inline(&self, pair: Pair) -> String {
+        let kind = pair.as_rule();
+        let text = pair.as_str();
+        let mut pairs = pair.into_inner();
+        match kind {
+            Rule::atom
+            | Rule::variable
+            | Rule::wildcard
+            | Rule::literal
+            | Rule::operator
+            | Rule::symbolic_operator
+            | Rule::integer_10
+            | Rule::associativity
+            | Rule::determinism
+            | Rule::up
+            | Rule::root
+            | Rule::lib => text.to_owned(),
+
+            Rule::directive => format!(":- {}.", self.join(pairs, "")),
+            Rule::nat => format!("nat({})", self.join(pairs, ", ")),
+            Rule::mod_ => format!("mod({})", self.join(pairs, ", ")),
+            Rule::use_ => format!("use({})", self.join(pairs, ", ")),
+            Rule::pub_ => format!("pub({})", self.join(pairs, ", ")),
+            Rule::mut_ => format!("mut({})", self.join(pairs, ", ")),
+            Rule::inc => format!("inc({})", self.join(pairs, ", ")),
+            Rule::op => format!("op({})", self.join(pairs, ", ")),
+            Rule::type_ => format!("type({})", self.join(pairs, ", ")),
+            Rule::mode => format!("mode({})", self.join(pairs, ", ")),
+            Rule::det => format!("det({})", self.join(pairs, ", ")),
+            Rule::test => format!("test({})", self.join(pairs, ", ")),
+            Rule::lint => {
+                let level = pairs.next().unwrap().as_str();
+                format!("{}({})", level, self.join(pairs, ", "))
+            }
+            Rule::multi_handle => {
+                let path = self.inline(pairs.next().unwrap());
+                match pairs.next() {
+                    Some(handles) => format!("{}({})", path, self.inline(handles)),
+                    None => path,
+                }
+            }
+            Rule::alias => {
+                let input = self.inline(pairs.next().unwrap());
+                let output = self.inline(pairs.next().unwrap());
+                format!("alias({}, as: {})", input, output)
+            }
+            Rule::handle => self.join(pairs, ""),
+            Rule::arity => pairs
+                .map(|pair| match pair.as_rule() {
+                    Rule::atom => format!(":{}", pair.as_str()),
+                    _ => format!("/{}", pair.as_str()),
+                })
+                .collect(),
+
+            Rule::scope_prefix | Rule::scope => self.join(pairs, "::"),
+            Rule::head | Rule::predicate | Rule::function_head | Rule::call => {
+                let name = self.inline(pairs.next().unwrap());
+                let bang = match kind {
+                    Rule::function_head | Rule::call => "!",
+                    _ => "",
+                };
+                match pairs.next() {
+                    Some(params) => format!("{}{}({})", name, bang, self.inline(params)),
+                    None => format!("{}{}", name, bang),
+                }
+            }
+            Rule::params
+            | Rule::named_params
+            | Rule::bare_params
+            | Rule::handles
+            | Rule::list_entries
+            | Rule::fields
+            | Rule::entries => self.join(pairs, ", "),
+            Rule::named_param | Rule::field => {
+                let name = self.inline(pairs.next().unwrap());
+                format!("{}: {}", name, self.join(pairs, ""))
+            }
+            Rule::entry => self.join(pairs, " => "),
+
+            Rule::disjunction => self.join(pairs, "; "),
+            Rule::conjunction => self.join(pairs, ", "),
+            Rule::procession => self.join(pairs, " -> "),
+            Rule::assumption => self.join(pairs, " <- "),
+            Rule::operation => self.join(pairs, " "),
+            Rule::unification | Rule::expression => {
+                let pair = pairs.next().unwrap();
+                match pair.as_rule() {
+                    Rule::disjunction | Rule::operation => format!("({})", self.inline(pair)),
+                    _ => self.inline(pair),
+                }
+            }
+            Rule::set_aggregation => format!("{{{}}}", self.join(pairs, "")),
+            Rule::list_aggregation => format!("[{}]", self.join(pairs, "")),
+            Rule::aggregation_body => self.join(pairs, " : "),
+
+            Rule::struct_ => {
+                let name = self.inline(pairs.next().unwrap());
+                match pairs.next() {
+                    Some(pair) if pair.as_rule() == Rule::pattern => {
+                        format!("{}({})", name, self.inline(pair))
+                    }
+                    Some(pair) => format!("{}{}", name, self.inline(pair)),
+                    None => name,
+                }
+            }
+            Rule::unbound_pattern => format!("?{}", self.join(pairs, "")),
+            Rule::bound_pattern => format!("!{}", self.join(pairs, "")),
+            Rule::lambda => {
+                let mut pairs: Vec<_> = pairs.collect();
+                let body = self.inline(pairs.pop().unwrap());
+                let parameters = pairs
+                    .into_iter()
+                    .map(|pair| self.inline(pair))
+                    .collect::<String>();
+                format!("[{}] :- {}", parameters, body)
+            }
+            Rule::list => format!("[{}]", self.join(pairs, "")),
+            Rule::set => format!("{{{}}}", self.join(pairs, "")),
+            Rule::record if text[1..].trim_start().starts_with(':') => "{:}".to_owned(),
+            Rule::map if text[1..].trim_start().starts_with("=>") => "{=>}".to_owned(),
+            Rule::record | Rule::map => format!("{{{}}}", self.join(pairs, "")),
+            Rule::list_tail | Rule::set_tail | Rule::record_tail | Rule::map_tail => {
+                format!(", ..{}", self.join(pairs, ""))
+            }
+
+            _ => self.join(pairs, ""),
+        }
+    }
+
+    fn join(&self, pairs: crate::Pairs, separator: &str) -> String {
+        pairs
+            .map(|pair| self.inline(pair))
+            .collect::<Vec<_>>()
+            .join(separator)
+    }
+
+    fn print_lines(&self, output: &mut String, lines: &[Line]) {
+        for line in lines {
+            let indent = INDENT.repeat(line.indent);
+            for &comment in &line.leading {
+                output.push_str(&indent);
+                output.push_str(self.comment(comment));
+                output.push('\n');
+            }
+            output.push_str(&indent);
+            output.push_str(&line.code);
+            for &comment in &line.trailing {
+                output.push(' ');
+                output.push_str(self.comment(comment));
+            }
+            output.push('\n');
+        }
+    }
+
+    fn comment(&self, (start, end): (usize, usize)) -> &str {
+        self.source[start..end].trim_end()
+    }
+
+    /// Whether there is a blank line between two positions in the source code, not counting the
+    /// lines of any comments in between.
+    fn blank_between(&self, start: usize, end: usize) -> bool {
+        let mut newlines = 0;
+        let mut position = start;
+        for &(comment_start, comment_end) in &self.comments {
+            if comment_end <= start || comment_start >= end {
+                continue;
+            }
+            newlines += self.source[position..comment_start].matches('\n').count();
+            if newlines >= 2 {
+                return true;
+            }
+            newlines = 0;
+            position = comment_end;
+        }
+        newlines + self.source[position..end].matches('\n').count() >= 2
+    }
+}
+
+/// Attaches a comment found within a directive or clause to the line it was written beside. A
+/// comment written after the code of a line, on the same line, is printed at the end of that
+/// line. Any other comment is printed on its own before the next line.
+fn attach(source: &str, lines: &mut [Line], comment: (usize, usize)) {
+    let (start, _) = comment;
+    if let Some(line) = lines
+        .iter_mut()
+        .find(|line| line.start <= start && start < line.end)
+    {
+        line.leading.push(comment);
+        return;
+    }
+    let previous = lines
+        .iter()
+        .rposition(|line| line.end <= start)
+        .unwrap_or(0);
+    if !source[lines[previous].end..start].contains('\n') || previous + 1 == lines.len() {
+        lines[previous].trailing.push(comment);
+    } else {
+        lines[previous + 1].leading.push(comment);
+    }
+}
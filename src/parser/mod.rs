@@ -1,14 +1,77 @@
 //! Handles parsing of Lumber source files.
+use pest::error::InputLocation;
 use pest::Parser as _;
 
+mod format;
+
+pub use format::format;
+
 /// A PEG parser for the Lumber language.
 #[derive(pest_derive::Parser)]
 #[grammar = "./parser/lumber.pest"]
 pub(crate) struct Parser;
 
 impl Parser {
-    pub fn parse_module<'i>(source_code: &'i str) -> crate::Result<crate::Pairs<'i>> {
-        Ok(Self::parse(Rule::module, source_code)?)
+    pub fn parse_module<'i>(
+        source_code: &'i str,
+        path: Option<&std::path::Path>,
+    ) -> crate::Result<crate::Pairs<'i>> {
+        Self::parse(Rule::module, source_code).map_err(|error| match path {
+            Some(path) => error.with_path(&path.to_string_lossy()).into(),
+            None => error.into(),
+        })
+    }
+
+    /// Finds every syntax error in a module, by skipping the clause or directive in which each
+    /// error is found and trying again. Along with the errors, the source code is returned with
+    /// the erroneous clauses replaced by whitespace, so that it can be parsed successfully while
+    /// every position in it still refers to the same place in the original source code.
+    pub fn recover_module(
+        source_code: &str,
+        path: Option<&std::path::Path>,
+    ) -> (String, Vec<crate::Error>) {
+        let mut code = source_code.to_owned();
+        let mut errors = vec![];
+        loop {
+            let error = match Self::parse(Rule::module, &code) {
+                Ok(..) => return (code, errors),
+                Err(error) => error,
+            };
+            let position = match error.location {
+                InputLocation::Pos(position) => position,
+                InputLocation::Span((start, _)) => start,
+            };
+            errors.push(match path {
+                Some(path) => error.with_path(&path.to_string_lossy()).into(),
+                None => error.into(),
+            });
+            let (start, end) = clause_bounds(&code, position);
+            if code[start..end].trim().is_empty() {
+                return (String::new(), errors);
+            }
+            let blank = code[start..end]
+                .chars()
+                .map(|ch| match ch {
+                    '\r' | '\n' => ch.to_string(),
+                    _ => " ".repeat(ch.len_utf8()),
+                })
+                .collect::<String>();
+            code.replace_range(start..end, &blank);
+        }
+    }
+
+    /// Finds the comments in a module, which are otherwise skipped while parsing. Only the text
+    /// of line comments is included, not the line break which ends them.
+    pub fn parse_comments<'i>(source_code: &'i str) -> Vec<pest::Span<'i>> {
+        Self::parse(Rule::comments, source_code)
+            .map(|pairs| {
+                pairs
+                    .flatten()
+                    .filter(|pair| pair.as_rule() == Rule::comment)
+                    .map(|pair| pair.as_span())
+                    .collect()
+            })
+            .unwrap_or_default()
     }
 
     pub fn parse_handle<'i>(source_code: &'i str) -> crate::Result<crate::Pairs<'i>> {
@@ -20,5 +83,82 @@ impl Parser {
     }
 }
 
+/// Finds the clause or directive containing the byte at `position`, assuming that the code before
+/// it is valid. A clause ends at a `.` which is followed by whitespace, is not part of a `..`, and is
+/// not within brackets, strings or comments. After `position` the brackets may not be balanced, so
+/// they are ignored.
+fn clause_bounds(code: &str, position: usize) -> (usize, usize) {
+    let bytes = code.as_bytes();
+    let skip_until = |from: usize, pattern: &[u8]| {
+        (from..bytes.len())
+            .find(|&i| bytes[i..].starts_with(pattern))
+            .map_or(bytes.len(), |i| i + pattern.len())
+    };
+    let mut start = 0;
+    let mut depth = 0;
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'/' if bytes[i..].starts_with(b"//") => {
+                i = skip_until(i, b"\n");
+                continue;
+            }
+            b'/' if bytes[i..].starts_with(b"/*") => {
+                let mut nesting = 0;
+                while i < bytes.len() {
+                    if bytes[i..].starts_with(b"/*") {
+                        nesting += 1;
+                        i += 2;
+                    } else if bytes[i..].starts_with(b"*/") {
+                        nesting -= 1;
+                        i += 2;
+                        if nesting == 0 {
+                            break;
+                        }
+                    } else {
+                        i += 1;
+                    }
+                }
+                continue;
+            }
+            b'"' => {
+                i += 1;
+                while i < bytes.len() && bytes[i] != b'"' {
+                    i += if bytes[i] == b'\\' { 2 } else { 1 };
+                }
+            }
+            b'\'' | b'#' => {
+                let hashes = bytes[i..].iter().take_while(|&&byte| byte == b'#').count();
+                match bytes.get(i + hashes) {
+                    Some(&quote) if quote == b'\'' || quote == b'"' => {
+                        let mut terminator = vec![quote];
+                        terminator.resize(hashes + 1, b'#');
+                        i = skip_until(i + hashes + 1, &terminator);
+                        continue;
+                    }
+                    _ => {}
+                }
+            }
+            b'(' | b'[' | b'{' => depth += 1,
+            b')' | b']' | b'}' => depth -= 1,
+            b'.' if depth <= 0 || i >= position => {
+                let terminates = bytes
+                    .get(i + 1)
+                    .map_or(true, |next| next.is_ascii_whitespace())
+                    && (i == 0 || bytes[i - 1] != b'.');
+                if terminates {
+                    if i >= position {
+                        return (start, i + 1);
+                    }
+                    start = i + 1;
+                }
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+    (start, bytes.len())
+}
+
 #[cfg(test)]
 mod test;
@@ -0,0 +1,10 @@
+use super::*;
+
+yes!(condition_comparison, Rule::condition, "A < B");
+yes!(condition_operation, Rule::condition, "A + 1 == B * 2");
+yes!(condition_boolean, Rule::condition, "A < B && B != c");
+yes!(condition_named, Rule::condition, "A `in` B");
+yes!(condition_unification, Rule::unification, "A >= 3");
+no!(condition_value, Rule::condition, "A");
+no!(condition_predicate, Rule::condition, "test(A)");
+no!(condition_implication, Rule::condition, "test(A) -> test(B)");
@@ -7,3 +7,30 @@ yes!(directive_pub, Rule::directive, ":- pub(hello/2).");
 yes!(directive_mut, Rule::directive, ":- mut(hello/2).");
 yes!(directive_inc, Rule::directive, ":- inc(hello/2).");
 no!(directive_other, Rule::directive, ":- what.");
+yes!(directive_op, Rule::directive, ":- op(<>, 8, left, concat/3).");
+yes!(directive_op_right, Rule::directive, ":- op(=>, 1, right, implies/3).");
+no!(directive_op_named, Rule::directive, ":- op(`in`, 1, left, in/3).");
+no!(directive_op_assoc, Rule::directive, ":- op(<>, 8, none, concat/3).");
+yes!(directive_pub_type, Rule::directive, ":- pub(person).");
+yes!(directive_type, Rule::directive, ":- type(person, { name: string, age: integer }).");
+yes!(directive_type_struct, Rule::directive, ":- type(shape, circle(number)).");
+yes!(directive_type_signature, Rule::directive, ":- type(greet/2, [person, string]).");
+no!(directive_type_missing, Rule::directive, ":- type(person).");
+no!(directive_type_variable_name, Rule::directive, ":- type(Person, string).");
+yes!(directive_mode, Rule::directive, ":- mode(add(!, !, ?)).");
+yes!(directive_mode_named, Rule::directive, ":- mode(get(!, key: !, value: _)).");
+yes!(directive_mode_empty, Rule::directive, ":- mode(hello).");
+yes!(directive_det, Rule::directive, ":- det(add/3).");
+yes!(directive_det_semidet, Rule::directive, ":- det(lookup/3, semidet).");
+yes!(directive_det_multi, Rule::directive, ":- det(member/2, multi).");
+yes!(directive_det_nondet, Rule::directive, ":- det(member/2, nondet).");
+no!(directive_det_other, Rule::directive, ":- det(add/3, often).");
+no!(directive_mode_handle, Rule::directive, ":- mode(add/3).");
+yes!(directive_allow, Rule::directive, ":- allow(singleton_variable).");
+yes!(directive_warn, Rule::directive, ":- warn(unused_import).");
+yes!(directive_deny, Rule::directive, ":- deny(unreachable_clause).");
+no!(directive_lint_other, Rule::directive, ":- forbid(unused_import).");
+no!(directive_lint_handle, Rule::directive, ":- deny(test/0).");
+yes!(directive_test, Rule::directive, ":- test(adds_numbers).");
+no!(directive_test_handle, Rule::directive, ":- test(adds_numbers/0).");
+no!(directive_test_scope, Rule::directive, ":- test(math::adds_numbers).");
@@ -0,0 +1,14 @@
+use super::*;
+
+yes!(doc_comment_empty, Rule::doc_comment, "///");
+yes!(doc_comment_text, Rule::doc_comment, "/// Says hello.");
+no!(doc_comment_four_slashes, Rule::doc_comment, "//// Not a doc comment.");
+no!(doc_comment_multiline, Rule::doc_comment, "/// one\n/// two");
+yes!(module_doc_text, Rule::module_doc, "//! A module.");
+yes!(module_doc_comment_before_clause, Rule::module, "/// Says hello.\nhello.\n");
+yes!(module_doc_comment_before_directive, Rule::module, "/// Says hello.\n:- pub(hello/0).\nhello.\n");
+yes!(module_doc_module, Rule::module, "//! A module.\n//! More about it.\n\nhello.\n");
+yes!(module_doc_after_comment, Rule::module, "// Licensed under MIT.\n//! A module.\nhello.\n");
+yes!(module_doc_after_clause, Rule::module, "hello.\n//! A module.\n");
+yes!(module_doc_four_slashes, Rule::module, "hello.\n//// Just a comment.\n");
+yes!(module_doc_in_body, Rule::module, "hello :-\n    /// Not here.\n    world.\n");
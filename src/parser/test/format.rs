@@ -0,0 +1,53 @@
+use super::*;
+
+macro_rules! formats {
+    ($name:ident, $src:literal, $expected:literal) => {
+        #[test]
+        fn $name() {
+            let formatted = format($src).unwrap();
+            assert_eq!(formatted, $expected);
+            assert_eq!(format(&formatted).unwrap(), formatted, "formatting should be idempotent");
+        }
+    };
+}
+
+formats!(format_fact, "test( a ,b ).", "test(a, b).\n");
+formats!(format_rule, "test(A,B):-foo(A),bar( B, ).", "test(A, B) :-\n    foo(A),\n    bar(B).\n");
+formats!(format_disjunction, "test:-a,b;c.", "test :-\n    a,\n    b;\n    c.\n");
+formats!(format_nested, "test:-(a;b),c->d.", "test :-\n    (a; b),\n    c -> d.\n");
+formats!(format_function, "add!(A,B)<-A+B.", "add!(A, B) <- A + B.\n");
+formats!(format_function_assumptions, "f!(A)<-B<-A*2,B+1.", "f!(A) <-\n    B <- A * 2,\n    B + 1.\n");
+formats!(format_operators, "test(A):-A<-(1+2)*3,A>=9.", "test(A) :-\n    A <- (1 + 2) * 3,\n    A >= 9.\n");
+formats!(format_structures, "test([a,b,..T],{x:1,..R},{a=>b,},{1,2},{:},{=>},[],p{x:1},q(A)).", "test([a, b, ..T], {x: 1, ..R}, {a => b}, {1, 2}, {:}, {=>}, [], p{x: 1}, q(A)).\n");
+formats!(format_lambda, "test(F):-F=[X,]:-foo(X).", "test(F) :-\n    F = [X] :- foo(X).\n");
+formats!(format_aggregation, "test(L):-L<-[X:foo(X)].", "test(L) :-\n    L <- [X : foo(X)].\n");
+formats!(format_named_params, "test(a,b:B,c:C,D).", "test(a, b: B, c: C, D).\n");
+formats!(format_literals, r##"test("a  b",#"raw"#,'Quoted Atom',0x1F,1.5,2e3)."##, "test(\"a  b\", #\"raw\"#, 'Quoted Atom', 0x1F, 1.5, 2e3).\n");
+formats!(format_patterns, "test(!A,?,_Ignored,!).", "test(!A, ?, _Ignored, !).\n");
+formats!(format_directives, ":-use( ^::a(foo/1 , alias(bar/2:x,as:baz/2:x) , t ) ).:-op(+++,5,left,add/3).:-det(foo/1,semidet).:-deny(unused_import).:-test( foo ).", ":- use(^::a(foo/1, alias(bar/2:x, as: baz/2:x), t)).\n:- op(+++, 5, left, add/3).\n:- det(foo/1, semidet).\n:- deny(unused_import).\n:- test(foo).\n");
+formats!(format_directives_first, "a.\n:- pub(a/0).\nb.\n", ":- pub(a/0).\n\na.\nb.\n");
+formats!(format_blank_lines, "a.\n\n\n\nb.\nc.\n", "a.\n\nb.\nc.\n");
+formats!(format_comments, "// Header.\n\n// About a.\na. // After a.\nb :- // After head.\n    // Before c.\n    c, /* After c. */\n    d(e /* Inside d. */).\n// Footer.\n", "// Header.\n\n// About a.\na. // After a.\nb :- // After head.\n    // Before c.\n    c, /* After c. */\n    /* Inside d. */\n    d(e).\n// Footer.\n");
+formats!(format_doc_comments, "//! About the module.\n/// About a.\na.\n/// About b.\n:- pub(b/0).\nb.\n", "//! About the module.\n\n/// About b.\n:- pub(b/0).\n\n/// About a.\na.\nb.\n");
+formats!(format_comment_only, "// Nothing here.\n", "// Nothing here.\n");
+formats!(format_empty, "", "");
+
+#[test]
+fn format_syntax_error() {
+    let error = format("test(.").unwrap_err();
+    assert_eq!(error.code(), Some("L0052"));
+}
+
+#[test]
+fn format_core() {
+    let formatted = format(include_str!("../../core/core.lumber")).unwrap();
+    assert_eq!(format(&formatted).unwrap(), formatted);
+}
+
+#[test]
+fn comments_skip_strings() {
+    let source = "a(\"// no\", '/* no */'). // yes\n/* yes /* nested */ */ b.";
+    let comments = Parser::parse_comments(source);
+    let comments: Vec<_> = comments.iter().map(|span| span.as_str()).collect();
+    assert_eq!(comments, vec!["// yes", "/* yes /* nested */ */"]);
+}
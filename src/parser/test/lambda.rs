@@ -0,0 +1,15 @@
+use super::*;
+
+yes!(lambda_basic, Rule::lambda, "[X] :- test(X)");
+yes!(lambda_no_params, Rule::lambda, "[] :- test");
+yes!(lambda_multiple_params, Rule::lambda, "[X, Y] :- test(X, Y)");
+yes!(lambda_trailing_comma, Rule::lambda, "[X, Y,] :- test(X, Y)");
+yes!(lambda_pattern_params, Rule::lambda, "[{ a: A }, [B, ..C]] :- test(A, B, C)");
+yes!(lambda_procession, Rule::lambda, "[X] :- test(X) -> print(X)");
+yes!(lambda_parenthesized, Rule::lambda, "[X] :- (test(X), print(X); fail)");
+yes!(lambda_as_pattern, Rule::pattern, "[X] :- test(X)");
+yes!(lambda_nested, Rule::lambda, "[X] :- call([Y] :- test(X, Y), X)");
+yes!(lambda_in_predicate, Rule::predicate, "call([X] :- test(X), 3)");
+no!(lambda_no_body, Rule::lambda, "[X] :-");
+no!(lambda_conjunction, Rule::lambda, "[X] :- test(X), print(X)");
+no!(lambda_tail, Rule::lambda, "[X, ..Y] :- test(X, Y)");
@@ -11,16 +11,36 @@ yes!(literal_integer_hex_lower, Rule::literal, "0xffa0");
 yes!(literal_integer_hex_mix, Rule::literal, "0xf0FfA");
 no!(literal_integer_hex_over, Rule::literal, "0xFG");
 no!(literal_integer_zero_start, Rule::literal, "0123");
+yes!(literal_integer_negative, Rule::literal, "-123");
+no!(literal_integer_negative_space, Rule::literal, "- 123");
 
 yes!(literal_integer_bin, Rule::literal, "0b10");
 yes!(literal_integer_bin_zero_start, Rule::literal, "0b0010");
 no!(literal_integer_bin_over, Rule::literal, "0b021");
 
 yes!(literal_decimal, Rule::literal, "123.456");
+yes!(literal_decimal_zero_start, Rule::literal, "1.05");
+yes!(literal_decimal_negative, Rule::literal, "-0.5");
 no!(literal_decimal_too_many, Rule::literal, "123.456.789");
 no!(literal_decimal_hex, Rule::literal, "0x0ff.3");
 
+yes!(literal_float, Rule::literal, "1.5f");
+yes!(literal_float_whole, Rule::literal, "1f");
+yes!(literal_float_exponent, Rule::literal, "1e10");
+yes!(literal_float_exponent_negative, Rule::literal, "2.5E-3f");
+yes!(literal_float_negative, Rule::literal, "-1.5f");
+no!(literal_float_exponent_empty, Rule::literal, "1e");
+no!(literal_float_hex, Rule::literal, "0x1f.5");
+
 yes!(literal_string, Rule::literal, "\"hello\"");
+no!(literal_string_negative, Rule::literal, "-\"hello\"");
 yes!(literal_string_strong, Rule::literal, "##\"he\"\"#llo\"##");
+yes!(literal_string_escapes, Rule::literal, r#""\"\\\n\t\r\0""#);
+yes!(literal_string_unicode, Rule::literal, r#""\u{1F332}\u{a}""#);
+no!(literal_string_unknown_escape, Rule::literal, r#""\q""#);
+no!(literal_string_unicode_empty, Rule::literal, r#""\u{}""#);
+no!(literal_string_unicode_long, Rule::literal, r#""\u{1234567}""#);
+no!(literal_string_unterminated_escape, Rule::literal, r#""\""#);
+yes!(literal_string_raw_backslash, Rule::literal, r##"#"\q"#"##);
 
 no!(literal_boolean, Rule::literal, "true");
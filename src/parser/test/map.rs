@@ -0,0 +1,13 @@
+use super::*;
+
+yes!(map_empty, Rule::map, "{=>}");
+yes!(map_entry, Rule::map, "{ 1 => a }");
+yes!(map_entries_multiple, Rule::map, "{ 1 => a, \"two\" => B, c(3) => [4] }");
+yes!(map_tail, Rule::map, "{ 1 => a, ..M }");
+yes!(map_tail_implicit_wildcard, Rule::map, "{ 1 => a, .. }");
+yes!(map_nested, Rule::map, "{ 1 => a, ..{ 2 => b } }");
+no!(map_empty_braces, Rule::map, "{}");
+no!(map_record, Rule::map, "{ a: 1 }");
+no!(map_missing_value, Rule::map, "{ 1 => }");
+no!(map_only_tail, Rule::map, "{ ..M }");
+no!(map_record_tail, Rule::map, "{ 1 => a, ..{ b: 2 } }");
@@ -26,17 +26,23 @@ mod aggregation;
 mod atom;
 mod body;
 mod call;
+mod condition;
 mod directive;
+mod doc_comment;
 mod evaluation;
 mod expression;
 mod fact;
+mod format;
 mod function;
 mod handle;
+mod lambda;
 mod list;
 mod literal;
+mod map;
 mod multi_handle;
 mod operator;
 mod predicate;
+mod recovery;
 mod record;
 mod rule;
 mod scope;
@@ -14,3 +14,4 @@ yes!(multi_handle_lib, Rule::multi_handle, "^(len/2)");
 yes!(multi_handle_root, Rule::multi_handle, "~(len/2)");
 no!(multi_handle_nested_scope, Rule::multi_handle, "@std::list(len(test/2))");
 no!(multi_handle_invalid_alias, Rule::multi_handle, "@std::list(alias(x/0, y/0))");
+yes!(multi_handle_type, Rule::multi_handle, "hello(person, world/2)");
@@ -10,3 +10,5 @@ no!(operator_bound, Rule::operator, "!");
 no!(operator_unbound, Rule::operator, "?");
 no!(operator_assumption, Rule::operator, "<-");
 no!(operator_implication, Rule::operator, "->");
+yes!(operator_not_equal, Rule::operator, "!=");
+yes!(operator_comparison, Rule::operator, "<=");
@@ -0,0 +1,37 @@
+use super::*;
+
+#[test]
+fn recover_keeps_positions() {
+    let (code, errors) = Parser::recover_module("a.\nb(.\nc.\n", None);
+    assert_eq!(errors.len(), 1);
+    assert_eq!(code, "a.\n   \nc.\n");
+}
+
+#[test]
+fn recover_skips_strings_and_comments() {
+    let source = "a(\"x. y\").\nb(.\n// c. d\nc('e. f').\n";
+    let (code, errors) = Parser::recover_module(source, None);
+    assert_eq!(errors.len(), 1);
+    assert_eq!(code, "a(\"x. y\").\n   \n// c. d\nc('e. f').\n");
+}
+
+#[test]
+fn recover_unbalanced_brackets() {
+    let (code, errors) = Parser::recover_module("a([.\nb.\nc(.\n", None);
+    assert_eq!(errors.len(), 2);
+    assert_eq!(code, "    \nb.\n   \n");
+}
+
+#[test]
+fn recover_record_rest() {
+    let (code, errors) = Parser::recover_module("a(, { b: B, .. }).\nc.\n", None);
+    assert_eq!(errors.len(), 1);
+    assert_eq!(code, format!("{}\nc.\n", " ".repeat(18)));
+}
+
+#[test]
+fn recover_valid() {
+    let (code, errors) = Parser::recover_module("a.\nb :- a.\n", None);
+    assert!(errors.is_empty());
+    assert_eq!(code, "a.\nb :- a.\n");
+}
@@ -6,7 +6,10 @@ use std::collections::HashMap;
 #[derive(Clone, Debug)]
 pub(crate) struct DatabaseEntry<'p> {
     public: bool,
-    definition: DatabaseDefinition<'p>,
+    pub(super) definition: DatabaseDefinition<'p>,
+    /// The modes in which this predicate may be called. If empty, it may be called in any mode.
+    modes: Vec<Mode>,
+    determinism: Option<Determinism>,
 }
 
 impl<'p> DatabaseEntry<'p> {
@@ -14,6 +17,8 @@ impl<'p> DatabaseEntry<'p> {
         Self {
             public: false,
             definition,
+            modes: vec![],
+            determinism: None,
         }
     }
 
@@ -40,12 +45,8 @@ impl DatabaseDefinition<'_> {
 
     fn handles_mut<'a>(&'a mut self) -> Box<dyn Iterator<Item = &mut Handle> + 'a> {
         match self {
-            Self::Static(def) => Box::new(def.bodies_mut().flat_map(|body| body.handles_mut())),
-            Self::Mutable(def) => Box::new(
-                def.get_mut()
-                    .bodies_mut()
-                    .flat_map(|body| body.handles_mut()),
-            ),
+            Self::Static(def) => Box::new(def.handles_mut()),
+            Self::Mutable(def) => Box::new(def.get_mut().handles_mut()),
             Self::Alias(handle) => Box::new(std::iter::once(handle)),
             _ => Box::new(std::iter::empty()),
         }
@@ -57,6 +58,13 @@ pub(crate) struct Database<'p> {
     /// All currently active definitions in this program. They may not be the same as they
     /// were when the program was created, due to mutable definitions.
     pub(super) definitions: HashMap<Handle, DatabaseEntry<'p>>,
+    /// Whether to check determinism declarations while answering questions.
+    pub(super) debug: bool,
+    /// The first determinism declaration found to be violated while answering the current
+    /// question in debug mode, and the query at which it was found.
+    pub(super) violation: RefCell<Option<(String, SourceSpan)>>,
+    /// Predicates which are run as tests. The tests of a library are not included.
+    pub(super) tests: Vec<Handle>,
 }
 
 impl<'p> Database<'p> {
@@ -80,7 +88,12 @@ impl<'p> Database<'p> {
                 )
             })
             .collect();
-        Self { definitions }
+        Self {
+            definitions,
+            debug: false,
+            violation: RefCell::default(),
+            tests: vec![],
+        }
     }
 
     pub fn apply_header(
@@ -124,6 +137,17 @@ impl<'p> Database<'p> {
                 .definition
                 .set_mutable();
         }
+        for (handle, modes) in &header.modes {
+            self.definitions.get_mut(handle).unwrap().modes = modes.clone();
+        }
+        for (handle, determinism) in &header.determinisms {
+            self.definitions.get_mut(handle).unwrap().determinism = Some(*determinism);
+        }
+        self.tests.extend(header.tests.iter().cloned());
+    }
+
+    pub fn set_debug(&mut self, debug: bool) {
+        self.debug = debug;
     }
 
     pub fn lookup(&self, handle: &Handle, public: bool) -> Option<&DatabaseDefinition<'p>> {
@@ -148,6 +172,25 @@ impl<'p> Database<'p> {
         }
     }
 
+    /// The declared modes of a predicate, following aliases.
+    pub fn modes(&self, handle: &Handle) -> &[Mode] {
+        self.resolve(handle, false)
+            .and_then(|handle| self.definitions.get(handle))
+            .map(|entry| entry.modes.as_slice())
+            .unwrap_or(&[])
+    }
+
+    /// The declared determinism of a predicate, following aliases.
+    pub fn determinism(&self, handle: &Handle) -> Option<Determinism> {
+        self.resolve(handle, false)
+            .and_then(|handle| self.definitions.get(handle))
+            .and_then(|entry| entry.determinism)
+    }
+
+    pub fn tests(&self) -> &[Handle] {
+        &self.tests
+    }
+
     pub fn exports(&self, handle: &Handle) -> bool {
         self.definitions
             .get(handle)
@@ -157,6 +200,7 @@ impl<'p> Database<'p> {
 
     pub fn into_library(mut self, name: &str) -> Self {
         let lib = Atom::from(name);
+        self.tests.clear();
         self.definitions = self
             .definitions
             .into_iter()
@@ -1,4 +1,5 @@
 mod database;
+mod modes;
 mod native_function;
 pub(crate) mod unification;
 
@@ -0,0 +1,132 @@
+use super::*;
+use crate::ast::*;
+use crate::CompileError;
+use std::collections::HashSet;
+
+impl Database<'_> {
+    /// Checks every call made by the clauses of this program against the declared modes of the
+    /// callee, reporting calls that could never satisfy any of them. Linked libraries have
+    /// already been checked, so only the definitions of this program are inspected.
+    pub fn check_modes(&self) -> Vec<(Scope, crate::Error)> {
+        let mut errors = vec![];
+        for (handle, entry) in &self.definitions {
+            if !handle.library().is_empty() {
+                continue;
+            }
+            match &entry.definition {
+                DatabaseDefinition::Static(definition) => {
+                    self.check_definition(handle, definition, &mut errors)
+                }
+                DatabaseDefinition::Mutable(definition) => {
+                    self.check_definition(handle, &definition.borrow(), &mut errors)
+                }
+                _ => {}
+            }
+        }
+        errors
+    }
+
+    fn check_definition(
+        &self,
+        handle: &Handle,
+        definition: &Definition,
+        errors: &mut Vec<(Scope, crate::Error)>,
+    ) {
+        for (head, body) in definition.iter() {
+            let mut check = ModeCheck {
+                database: self,
+                seen: head.identifiers().collect(),
+                errors: vec![],
+            };
+            check.body(body);
+            errors.extend(
+                check
+                    .errors
+                    .into_iter()
+                    .map(|error| (handle.module(), error)),
+            );
+        }
+    }
+}
+
+/// Walks a clause body in the order it will be run, tracking which variables could have been
+/// bound by the time each call is made. Variables from the head are assumed to be bound by
+/// the caller, so only variables which have definitely not been seen yet are known to be unbound.
+struct ModeCheck<'a, 'p> {
+    database: &'a Database<'p>,
+    seen: HashSet<Identifier>,
+    errors: Vec<crate::Error>,
+}
+
+impl ModeCheck<'_, '_> {
+    fn body(&mut self, body: &Body) {
+        let disjunction = match &body.0 {
+            Some(disjunction) => disjunction,
+            None => return,
+        };
+        // Each case of a disjunction starts from what was seen before the disjunction. Anything
+        // seen in any of the cases could have been bound once the disjunction is done.
+        let before = self.seen.clone();
+        let mut after = before.clone();
+        for conjunction in &disjunction.cases {
+            self.seen = before.clone();
+            for procession in &conjunction.terms {
+                for step in &procession.steps {
+                    self.unification(step);
+                }
+            }
+            after.extend(self.seen.drain());
+        }
+        self.seen = after;
+    }
+
+    fn unification(&mut self, unification: &Unification) {
+        match unification {
+            Unification::Query(query) => self.query(query),
+            Unification::Body(body) => self.body(body),
+            Unification::Assumption(output, expression) => {
+                self.expression(expression);
+                self.seen.extend(output.identifiers());
+            }
+        }
+    }
+
+    fn expression(&mut self, expression: &Expression) {
+        match expression {
+            Expression::Operation(_, steps) => {
+                for step in steps {
+                    self.unification(step);
+                }
+            }
+            Expression::Value(..) => {}
+            #[cfg(feature = "builtin-sets")]
+            Expression::SetAggregation(_, body) => self.body(body),
+            Expression::ListAggregation(_, body) => self.body(body),
+        }
+    }
+
+    fn query(&mut self, query: &Query) {
+        let modes = self.database.modes(query.as_ref());
+        if !modes.is_empty() {
+            let seen = &self.seen;
+            let arguments = Mode {
+                params: query
+                    .patterns
+                    .iter()
+                    .map(|pattern| Instantiation::of(pattern, &|id| !seen.contains(id)))
+                    .collect(),
+            };
+            if !modes.iter().any(|mode| mode.admits(&arguments.params)) {
+                self.errors.push(
+                    crate::Error::compile(CompileError::UnsatisfiableMode {
+                        handle: query.as_ref().clone(),
+                        mode: arguments.to_string(),
+                        modes: modes.iter().map(|mode| mode.to_string()).collect(),
+                    })
+                    .at(&query.span),
+                );
+            }
+        }
+        self.seen.extend(query.identifiers());
+    }
+}
@@ -0,0 +1,238 @@
+use super::super::{Database, DatabaseDefinition};
+use super::{unify_patterns, Bindings};
+use crate::ast::*;
+use crate::Binding;
+use std::collections::HashMap;
+
+impl Database<'_> {
+    /// Unifies the head and body of each clause of a definition with the arguments of a
+    /// `@core::clause` query. The definition must be visible to the caller: a private definition
+    /// may only be inspected from its own module or its submodules, so a question may only inspect
+    /// public definitions. Mutable definitions are changed at runtime, so they have no fixed
+    /// clauses, and are treated as having none.
+    pub(super) fn unify_clause<'a>(
+        &'a self,
+        query: &'a Query,
+        binding: Binding,
+        caller: Option<&Handle>,
+    ) -> Bindings<'a> {
+        let handle = match binding
+            .apply(&query.patterns[0])
+            .ok()
+            .and_then(clause_handle)
+        {
+            Some(handle) => handle,
+            None => return Box::new(std::iter::empty()),
+        };
+        let visible = matches!(caller, Some(caller) if handle.module() >= caller.module());
+        let definition = match self.lookup(&handle, !visible) {
+            Some(DatabaseDefinition::Static(definition)) => definition,
+            _ => return Box::new(std::iter::empty()),
+        };
+        Box::new(definition.iter().filter_map(move |(head, body)| {
+            let mut binding = binding.clone();
+            let term = ClauseTerm::new(head, body, &mut binding);
+            let (_, binding) = unify_patterns(&term.query(head), &query.patterns[0], binding, &[])?;
+            let (_, binding) = unify_patterns(&term.body(body), &query.patterns[1], binding, &[])?;
+            Some(binding)
+        }))
+    }
+}
+
+/// Finds the handle described by a clause head term, `name([args])`.
+fn clause_handle(head: Pattern) -> Option<Handle> {
+    match head {
+        Pattern::Struct(Struct {
+            name,
+            contents: Some(contents),
+        }) => match *contents {
+            Pattern::List(args, None) => Some(Handle::from_parts(
+                Scope::parse_path(name.as_ref()),
+                Arity::new_len(args.len() as u32),
+            )),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Converts a clause into a term, with all of its variables replaced by fresh variables of
+/// the binding it is being inspected from.
+struct ClauseTerm {
+    variables: HashMap<Identifier, Identifier>,
+}
+
+impl ClauseTerm {
+    fn new(head: &Query, body: &Body, binding: &mut Binding) -> Self {
+        let variables = head
+            .identifiers()
+            .chain(body.identifiers())
+            .filter(|identifier| !identifier.is_wildcard())
+            .fold(HashMap::new(), |mut variables, identifier| {
+                variables
+                    .entry(identifier)
+                    .or_insert_with(|| binding.fresh_variable());
+                variables
+            });
+        Self { variables }
+    }
+
+    fn named(name: &str, terms: Vec<Pattern>) -> Pattern {
+        Pattern::Struct(Struct::from_parts(
+            Atom::from(name),
+            Some(Box::new(Pattern::List(terms, None))),
+        ))
+    }
+
+    /// Goals joined by a connective are only wrapped when there is more than one of them.
+    fn joined(name: &str, mut terms: Vec<Pattern>) -> Pattern {
+        if terms.len() == 1 {
+            terms.pop().unwrap()
+        } else {
+            Self::named(name, terms)
+        }
+    }
+
+    fn query(&self, query: &Query) -> Pattern {
+        Self::named(
+            &query.handle.scope.to_string(),
+            query
+                .patterns
+                .iter()
+                .map(|pattern| self.pattern(pattern))
+                .collect(),
+        )
+    }
+
+    fn body(&self, body: &Body) -> Pattern {
+        match &body.0 {
+            Some(disjunction) => self.disjunction(disjunction),
+            None => Pattern::Struct(Struct::from_parts(Atom::from("true"), None)),
+        }
+    }
+
+    fn disjunction(&self, disjunction: &Disjunction) -> Pattern {
+        Self::joined(
+            "or",
+            disjunction
+                .cases
+                .iter()
+                .map(|case| self.conjunction(case))
+                .collect(),
+        )
+    }
+
+    fn conjunction(&self, conjunction: &Conjunction) -> Pattern {
+        Self::joined(
+            "and",
+            conjunction
+                .terms
+                .iter()
+                .map(|term| self.procession(term))
+                .collect(),
+        )
+    }
+
+    fn procession(&self, procession: &Procession) -> Pattern {
+        Self::joined(
+            "then",
+            procession
+                .steps
+                .iter()
+                .map(|step| self.unification(step))
+                .collect(),
+        )
+    }
+
+    fn unification(&self, unification: &Unification) -> Pattern {
+        match unification {
+            Unification::Query(query) => self.query(query),
+            Unification::Body(body) => self.body(body),
+            Unification::Assumption(output, expression) => self.assumption(output, expression),
+        }
+    }
+
+    fn equal(&self, lhs: &Pattern, rhs: &Pattern) -> Pattern {
+        Self::named(
+            &Scope::builtin("equal").to_string(),
+            vec![self.pattern(lhs), self.pattern(rhs)],
+        )
+    }
+
+    fn assumption(&self, output: &Pattern, expression: &Expression) -> Pattern {
+        match expression {
+            Expression::Value(value) => self.equal(output, value),
+            Expression::Operation(result, steps) => Self::joined(
+                "and",
+                steps
+                    .iter()
+                    .map(|step| self.unification(step))
+                    .chain(std::iter::once(self.equal(output, result)))
+                    .collect(),
+            ),
+            #[cfg(feature = "builtin-sets")]
+            Expression::SetAggregation(pattern, body) => Self::named(
+                "findset",
+                vec![self.pattern(pattern), self.body(body), self.pattern(output)],
+            ),
+            Expression::ListAggregation(pattern, body) => Self::named(
+                "findall",
+                vec![self.pattern(pattern), self.body(body), self.pattern(output)],
+            ),
+        }
+    }
+
+    fn tail(&self, tail: &Option<Box<Pattern>>) -> Option<Box<Pattern>> {
+        tail.as_ref().map(|tail| Box::new(self.pattern(tail)))
+    }
+
+    fn pattern(&self, pattern: &Pattern) -> Pattern {
+        match pattern {
+            Pattern::Variable(identifier) => match self.variables.get(identifier) {
+                Some(variable) => Pattern::Variable(variable.clone()),
+                None => Pattern::Wildcard,
+            },
+            Pattern::Struct(Struct { name, contents }) => Pattern::Struct(Struct::from_parts(
+                name.clone(),
+                contents
+                    .as_ref()
+                    .map(|contents| Box::new(self.pattern(contents))),
+            )),
+            Pattern::List(head, tail) => Pattern::List(
+                head.iter().map(|pattern| self.pattern(pattern)).collect(),
+                self.tail(tail),
+            ),
+            #[cfg(feature = "builtin-sets")]
+            Pattern::Set(head, tail) => Pattern::Set(
+                head.iter().map(|pattern| self.pattern(pattern)).collect(),
+                self.tail(tail),
+            ),
+            Pattern::Record(fields, tail) => Pattern::Record(
+                fields
+                    .iter()
+                    .map(|(key, pattern)| (key.clone(), self.pattern(pattern)))
+                    .collect(),
+                self.tail(tail),
+            ),
+            Pattern::Map(entries, tail) => Pattern::Map(
+                entries
+                    .iter()
+                    .map(|(key, pattern)| (key.clone(), self.pattern(pattern)))
+                    .collect(),
+                self.tail(tail),
+            ),
+            Pattern::Lambda(lambda) => Pattern::Lambda(Box::new(
+                lambda.with_captures(
+                    lambda
+                        .captures
+                        .iter()
+                        .map(|(identifier, pattern)| (identifier.clone(), self.pattern(pattern)))
+                        .collect(),
+                ),
+            )),
+            Pattern::Bound(inner) => Pattern::Bound(Box::new(self.pattern(inner))),
+            Pattern::Unbound(inner) => Pattern::Unbound(Box::new(self.pattern(inner))),
+            Pattern::Literal(..) | Pattern::Any(..) | Pattern::Wildcard => pattern.clone(),
+        }
+    }
+}
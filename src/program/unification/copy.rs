@@ -0,0 +1,54 @@
+use super::super::Database;
+use super::{unify_patterns, Bindings};
+use crate::ast::*;
+use crate::Binding;
+use std::collections::HashMap;
+
+impl Database<'_> {
+    /// Unifies the second argument of a `@core::copy_term` query with a copy of the first, in
+    /// which every unbound variable is replaced by a fresh variable. Variables which are shared in
+    /// the original are also shared in the copy.
+    pub(super) fn unify_copy_term<'a>(
+        &'a self,
+        query: &'a Query,
+        mut binding: Binding,
+    ) -> Bindings<'a> {
+        let copy = copy_pattern(&query.patterns[0], &mut HashMap::new(), &mut binding);
+        Box::new(
+            unify_patterns(&copy, &query.patterns[1], binding, &[])
+                .map(|(_, binding)| binding)
+                .into_iter(),
+        )
+    }
+}
+
+/// Copies a pattern, renaming each of its variables to a fresh variable which is bound to a copy
+/// of whatever the original variable was bound to. Renamed variables are recorded before their
+/// values are copied, so that cyclic bindings terminate.
+fn copy_pattern(
+    pattern: &Pattern,
+    variables: &mut HashMap<Identifier, Identifier>,
+    binding: &mut Binding,
+) -> Pattern {
+    let mut pattern = pattern.clone();
+    for variable in pattern.variables_mut() {
+        if variable.is_wildcard() {
+            continue;
+        }
+        if let Some(renamed) = variables.get(variable) {
+            *variable = renamed.clone();
+            continue;
+        }
+        let renamed = binding.fresh_variable();
+        variables.insert(variable.clone(), renamed.clone());
+        match binding.0.get(variable).cloned() {
+            None | Some(Pattern::Wildcard) => {}
+            Some(value) => {
+                let value = copy_pattern(&value, variables, binding);
+                binding.set(renamed.clone(), value);
+            }
+        }
+        *variable = renamed;
+    }
+    pattern
+}
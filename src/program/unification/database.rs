@@ -1,7 +1,8 @@
 use super::super::{Database, DatabaseDefinition};
+use super::determinism::DeterminismCheck;
 use super::{unify_patterns, Bindings};
 use crate::ast::*;
-use crate::{Binding, Question};
+use crate::{Binding, Question, TestFailure};
 
 impl Database<'_> {
     pub(crate) fn unify_question<'a>(
@@ -9,12 +10,98 @@ impl Database<'_> {
         question: &'a Question,
     ) -> impl Iterator<Item = Binding> + 'a {
         let body = question.as_ref();
-        self.unify_body(body, question.initial_binding.clone(), true)
+        self.unify_body(body, question.initial_binding.clone(), None)
     }
 
-    fn unify_body<'a>(&'a self, body: &'a Body, binding: Binding, public: bool) -> Bindings<'a> {
+    /// Answers a question like [`unify_question`][Self::unify_question], but ends with an error
+    /// if a determinism declaration is violated while looking for the next answer.
+    pub(crate) fn unify_question_checked<'a>(
+        &'a self,
+        question: &'a Question,
+    ) -> impl Iterator<Item = crate::Result<Binding>> + 'a {
+        self.violation.replace(None);
+        let mut answers = self.unify_question(question);
+        let mut done = false;
+        std::iter::from_fn(move || {
+            if done {
+                return None;
+            }
+            let answer = answers.next();
+            match self.violation.take() {
+                Some((message, span)) => {
+                    done = true;
+                    Some(Err(crate::Error::determinism(message).at(&span)))
+                }
+                None => answer.map(Ok),
+            }
+        })
+    }
+
+    /// Runs a test, which passes if any clause of its definition has an answer. Otherwise, the
+    /// failure is described by the clause which got furthest before it ran out of answers. A
+    /// handle which is not a test of this program fails as a whole.
+    pub(crate) fn run_test(&self, test: &Handle) -> Result<(), TestFailure> {
+        let definition = match self.lookup(test, false) {
+            Some(DatabaseDefinition::Static(definition)) if self.tests().contains(test) => {
+                definition
+            }
+            _ => return Err(no_answers(test)),
+        };
+        let mut failure: Option<(usize, TestFailure)> = None;
+        for (head, body) in definition.iter() {
+            let binding: Binding = head.identifiers().chain(body.identifiers()).collect();
+            if self
+                .unify_body(body, binding.clone(), Some(&head.handle))
+                .next()
+                .is_some()
+            {
+                return Ok(());
+            }
+            let (progress, clause_failure) = self.find_failure(head, body, binding);
+            if !matches!(&failure, Some((furthest, _)) if *furthest >= progress) {
+                failure = Some((progress, clause_failure));
+            }
+        }
+        match failure {
+            Some((_, failure)) => Err(failure),
+            None => Err(no_answers(test)),
+        }
+    }
+
+    /// Finds the goal at which a clause with no answers fails, by trying the goals of its body
+    /// one more at a time. Also returns how many goals succeeded before it. If the body is a
+    /// disjunction, it fails as a whole.
+    fn find_failure(&self, head: &Query, body: &Body, binding: Binding) -> (usize, TestFailure) {
+        let terms = match &body.0 {
+            Some(disjunction) if disjunction.cases.len() == 1 => &disjunction.cases[0].terms,
+            _ => return (0, TestFailure::new(head, body, &binding)),
+        };
+        let mut answer = binding.clone();
+        for (index, term) in terms.iter().enumerate() {
+            let prefix = Conjunction {
+                terms: terms[..=index].to_vec(),
+            };
+            let next = self
+                .unify_conjunction(&prefix, binding.clone(), Some(&head.handle))
+                .next();
+            match next {
+                Some(next) => answer = next,
+                None => return (index, TestFailure::new(head, &goal(term.clone()), &answer)),
+            }
+        }
+        unreachable!()
+    }
+
+    /// Unifies a body, which is part of a clause or lambda of the `caller` predicate, or of a
+    /// question if there is no caller. Only public definitions are visible to a question.
+    fn unify_body<'a>(
+        &'a self,
+        body: &'a Body,
+        binding: Binding,
+        caller: Option<&'a Handle>,
+    ) -> Bindings<'a> {
         match &body.0 {
-            Some(disjunction) => self.unify_disjunction(disjunction, binding, public),
+            Some(disjunction) => self.unify_disjunction(disjunction, binding, caller),
             None => Box::new(std::iter::once(binding)),
         }
     }
@@ -23,13 +110,13 @@ impl Database<'_> {
         &'a self,
         disjunction: &'a Disjunction,
         binding: Binding,
-        public: bool,
+        caller: Option<&'a Handle>,
     ) -> Bindings<'a> {
         Box::new(
             disjunction
                 .cases
                 .iter()
-                .flat_map(move |case| self.unify_conjunction(case, binding.clone(), public)),
+                .flat_map(move |case| self.unify_conjunction(case, binding.clone(), caller)),
         )
     }
 
@@ -37,11 +124,11 @@ impl Database<'_> {
         &'a self,
         conjunction: &'a Conjunction,
         binding: Binding,
-        public: bool,
+        caller: Option<&'a Handle>,
     ) -> Bindings<'a> {
         let bindings = Box::new(std::iter::once(binding));
         conjunction.terms.iter().fold(bindings, |bindings, term| {
-            Box::new(bindings.flat_map(move |binding| self.unify_procession(term, binding, public)))
+            Box::new(bindings.flat_map(move |binding| self.unify_procession(term, binding, caller)))
         })
     }
 
@@ -49,14 +136,14 @@ impl Database<'_> {
         &'a self,
         procession: &'a Procession,
         binding: Binding,
-        public: bool,
+        caller: Option<&'a Handle>,
     ) -> Bindings<'a> {
         let bindings = Box::new(std::iter::once(binding.clone()));
         procession
             .steps
             .iter()
             .fold(bindings, |mut bindings, step| match bindings.next() {
-                Some(binding) => self.perform_unification(step, binding, public),
+                Some(binding) => self.perform_unification(step, binding, caller),
                 None => Box::new(std::iter::empty()),
             })
     }
@@ -65,15 +152,25 @@ impl Database<'_> {
         &'a self,
         unification: &'a Unification,
         binding: Binding,
-        public: bool,
+        caller: Option<&'a Handle>,
     ) -> Bindings<'a> {
         match unification {
+            Unification::Query(query) if query.as_ref().is_call() => {
+                self.unify_call(query, binding)
+            }
+            Unification::Query(query) if query.as_ref().is_copy_term() => {
+                self.unify_copy_term(query, binding)
+            }
+            Unification::Query(query) if query.as_ref().is_clause() => {
+                self.unify_clause(query, binding, caller)
+            }
             Unification::Query(query) => {
-                let definition = match self.lookup(query.as_ref(), public) {
+                let definition = match self.lookup(query.as_ref(), caller.is_none()) {
                     Some(definition) => definition,
                     None => return Box::new(std::iter::empty()),
                 };
-                match definition {
+                let determinism = self.checked_determinism(query, &binding);
+                let answers: Bindings<'a> = match definition {
                     DatabaseDefinition::Static(definition) => {
                         self.unify_definition(&query, definition, binding)
                     }
@@ -99,11 +196,20 @@ impl Database<'_> {
                         }))
                     }
                     _ => unreachable!(),
+                };
+                match determinism {
+                    Some(determinism) => Box::new(DeterminismCheck::new(
+                        answers,
+                        query,
+                        determinism,
+                        &self.violation,
+                    )),
+                    None => answers,
                 }
             }
-            Unification::Body(body) => self.unify_body(body, binding, public),
+            Unification::Body(body) => self.unify_body(body, binding, caller),
             Unification::Assumption(output, expression) => Box::new(
-                self.unify_expression(expression, binding, public)
+                self.unify_expression(expression, binding, caller)
                     .filter_map(move |(binding, pattern)| {
                         Some(unify_patterns(&output, &pattern, binding, &[])?.1)
                     }),
@@ -123,7 +229,7 @@ impl Database<'_> {
                 .chain(body.identifiers())
                 .collect::<Binding>()
                 .transfer_from(&input_binding, &query, &head)
-                .map(move |binding| self.unify_body(body, binding, false))
+                .map(move |binding| self.unify_body(body, binding, Some(&head.handle)))
                 .into_iter()
                 .flatten()
                 .filter_map(move |output_binding| {
@@ -134,11 +240,61 @@ impl Database<'_> {
         }))
     }
 
+    fn unify_call<'a>(&'a self, query: &'a Query, binding: Binding) -> Bindings<'a> {
+        let (callee, args) = query.patterns.split_first().unwrap();
+        let lambda = match binding.apply(callee) {
+            Ok(Pattern::Lambda(lambda)) => lambda,
+            _ => return Box::new(std::iter::empty()),
+        };
+        let (head, body) = match self.lookup(&lambda.handle, false) {
+            Some(DatabaseDefinition::Static(definition)) => definition.iter().next().unwrap(),
+            _ => return Box::new(std::iter::empty()),
+        };
+        if head.patterns.len() != args.len() {
+            return Box::new(std::iter::empty());
+        }
+        let mut lambda_binding: Binding = head.identifiers().chain(body.identifiers()).collect();
+        for (identifier, pattern) in &lambda.captures {
+            lambda_binding.set(identifier.clone(), pattern.clone());
+        }
+        // Captured variables may have been bound by the lambda, so they are transferred back
+        // out along with the arguments, by way of the callee pattern.
+        let output = std::iter::once(Pattern::Lambda(Box::new(
+            lambda.with_captures(
+                lambda
+                    .captures
+                    .iter()
+                    .map(|(identifier, _)| {
+                        (identifier.clone(), Pattern::Variable(identifier.clone()))
+                    })
+                    .collect(),
+            ),
+        )))
+        .chain(head.patterns.iter().cloned())
+        .collect::<Vec<_>>();
+        Box::new(
+            lambda_binding
+                .transfer_patterns_from(&binding, args, &head.patterns)
+                .map(move |lambda_binding| {
+                    self.unify_body(body, lambda_binding, Some(&head.handle))
+                })
+                .into_iter()
+                .flatten()
+                .filter_map(move |output_binding| {
+                    binding.clone().transfer_patterns_from(
+                        &output_binding,
+                        &output,
+                        &query.patterns,
+                    )
+                }),
+        )
+    }
+
     fn unify_expression<'a>(
         &'a self,
         expression: &'a Expression,
         binding: Binding,
-        public: bool,
+        caller: Option<&'a Handle>,
     ) -> Box<dyn Iterator<Item = (Binding, Pattern)> + 'a> {
         match expression {
             Expression::Operation(pattern, unifications) => Box::new(
@@ -148,7 +304,7 @@ impl Database<'_> {
                         Box::new(std::iter::once(binding)) as Bindings,
                         |bindings: Bindings, term: &Unification| -> Bindings {
                             Box::new(bindings.flat_map(move |binding| {
-                                self.perform_unification(term, binding, public)
+                                self.perform_unification(term, binding, caller)
                             }))
                         },
                     )
@@ -158,14 +314,14 @@ impl Database<'_> {
             #[cfg(feature = "builtin-sets")]
             Expression::SetAggregation(pattern, body) => {
                 let solutions = self
-                    .unify_disjunction(&body.0, binding.clone(), public)
+                    .unify_disjunction(&body.0, binding.clone(), caller)
                     .map(|binding| binding.apply(&pattern).unwrap())
                     .collect();
                 Box::new(std::iter::once((binding, Pattern::Set(solutions, None))))
             }
             Expression::ListAggregation(pattern, body) => {
                 let solutions = self
-                    .unify_body(body, binding.clone(), public)
+                    .unify_body(body, binding.clone(), caller)
                     .map(|binding| binding.apply(&pattern).unwrap())
                     .collect();
                 Box::new(std::iter::once((binding, Pattern::List(solutions, None))))
@@ -173,3 +329,19 @@ impl Database<'_> {
         }
     }
 }
+
+/// The failure of a test which has no clauses to run.
+fn no_answers(test: &Handle) -> TestFailure {
+    let query = Query::new(test.clone(), vec![]);
+    let goal = goal(Procession {
+        steps: vec![Unification::Query(query.clone())],
+    });
+    TestFailure::new(&query, &goal, &Binding::default())
+}
+
+/// A body consisting of a single goal.
+fn goal(term: Procession) -> Body {
+    Body(Some(Disjunction {
+        cases: vec![Conjunction { terms: vec![term] }],
+    }))
+}
@@ -0,0 +1,117 @@
+use super::super::Database;
+use super::Bindings;
+use crate::ast::*;
+use crate::Binding;
+use std::cell::RefCell;
+
+impl Database<'_> {
+    /// The determinism to check for a query, if this database is in debug mode and the query
+    /// is being called in one of the predicate's declared modes. A predicate with no declared
+    /// modes is checked in every mode.
+    pub(super) fn checked_determinism(
+        &self,
+        query: &Query,
+        binding: &Binding,
+    ) -> Option<Determinism> {
+        if !self.debug {
+            return None;
+        }
+        let determinism = self.determinism(query.as_ref())?;
+        let modes = self.modes(query.as_ref());
+        let arguments = query
+            .patterns
+            .iter()
+            .map(|pattern| instantiation(pattern, binding))
+            .collect::<Vec<_>>();
+        if modes.is_empty() || modes.iter().any(|mode| mode.matches(&arguments)) {
+            Some(determinism)
+        } else {
+            None
+        }
+    }
+}
+
+/// The current instantiation of an argument of a query.
+fn instantiation(pattern: &Pattern, binding: &Binding) -> Instantiation {
+    match pattern {
+        Pattern::Variable(identifier) => match binding.get(identifier) {
+            Some(pattern) => instantiation(pattern, binding),
+            None => Instantiation::Unbound,
+        },
+        Pattern::Bound(inner) | Pattern::Unbound(inner) => instantiation(inner, binding),
+        Pattern::Wildcard => Instantiation::Unbound,
+        _ => Instantiation::Bound,
+    }
+}
+
+/// Wraps the answers to a query, checking that the number of answers agrees with the declared
+/// determinism of the predicate. Only the answers that are actually requested are counted, so a
+/// predicate that produces too many answers is only caught if they are asked for.
+///
+/// When the determinism is violated, no more answers are produced and the violation is recorded
+/// in the database, to be reported as an error by [`Lumber::ask_checked`][crate::Lumber::ask_checked].
+pub(super) struct DeterminismCheck<'a> {
+    answers: Bindings<'a>,
+    query: &'a Query,
+    determinism: Determinism,
+    violation: &'a RefCell<Option<(String, SourceSpan)>>,
+    count: usize,
+}
+
+impl<'a> DeterminismCheck<'a> {
+    pub fn new(
+        answers: Bindings<'a>,
+        query: &'a Query,
+        determinism: Determinism,
+        violation: &'a RefCell<Option<(String, SourceSpan)>>,
+    ) -> Self {
+        Self {
+            answers,
+            query,
+            determinism,
+            violation,
+            count: 0,
+        }
+    }
+
+    fn violate(&mut self, message: String) {
+        let mut violation = self.violation.borrow_mut();
+        if violation.is_none() {
+            *violation = Some((message, self.query.span.clone()));
+        }
+    }
+}
+
+impl Iterator for DeterminismCheck<'_> {
+    type Item = Binding;
+
+    fn next(&mut self) -> Option<Binding> {
+        match self.answers.next() {
+            Some(binding) => {
+                self.count += 1;
+                match self.determinism.max() {
+                    Some(max) if self.count > max => {
+                        self.violate(format!(
+                            "{} is declared {}, but produced more than {} answer.",
+                            self.query.as_ref(),
+                            self.determinism,
+                            max,
+                        ));
+                        None
+                    }
+                    _ => Some(binding),
+                }
+            }
+            None => {
+                if self.count < self.determinism.min() {
+                    self.violate(format!(
+                        "{} is declared {}, but produced no answers.",
+                        self.query.as_ref(),
+                        self.determinism,
+                    ));
+                }
+                None
+            }
+        }
+    }
+}
@@ -1,6 +1,9 @@
 use crate::Binding;
 
+mod clause;
+mod copy;
 mod database;
+mod determinism;
 mod patterns;
 
 type Bindings<'a> = Box<dyn Iterator<Item = Binding> + 'a>;
@@ -86,6 +86,25 @@ pub(crate) fn unify_patterns(
             Some((Pattern::Literal(lhs.clone()), binding.clone()))
         }
         (Pattern::Literal(..), Pattern::Literal(..)) => None,
+        // Lambdas must be the same lambda, and then the values they captured must match.
+        (Pattern::Lambda(lhs), Pattern::Lambda(rhs)) if lhs.handle == rhs.handle => {
+            let (identifiers, lhs_captures): (Vec<_>, Vec<_>) =
+                lhs.captures.iter().cloned().unzip();
+            let rhs_captures = rhs
+                .captures
+                .iter()
+                .map(|(_, pattern)| pattern.clone())
+                .collect::<Vec<_>>();
+            let (captures, binding) =
+                unify_sequence(&lhs_captures, &rhs_captures, binding, occurs)?;
+            Some((
+                Pattern::Lambda(Box::new(
+                    lhs.with_captures(identifiers.into_iter().zip(captures).collect()),
+                )),
+                binding,
+            ))
+        }
+        (Pattern::Lambda(..), Pattern::Lambda(..)) => None,
         // Structs must match in name, and then their contents must match
         (Pattern::Struct(lhs), Pattern::Struct(rhs))
             if lhs.name == rhs.name && lhs.contents.is_none() && rhs.contents.is_none() =>
@@ -231,6 +250,62 @@ pub(crate) fn unify_patterns(
                 binding,
             ))
         }
+        // Maps unify just like records, but their keys are compared for equality rather than
+        // unified, as they are always ground.
+        (Pattern::Map(lhs, None), Pattern::Map(rhs, None)) => {
+            let (entries, binding) = unify_entries(&lhs, &rhs, binding, occurs)?;
+            Some((Pattern::Map(entries, None), binding))
+        }
+        (other @ Pattern::Map(full, None), Pattern::Map(head, Some(tail)))
+        | (Pattern::Map(head, Some(tail)), other @ Pattern::Map(full, None)) => {
+            match tail.as_ref() {
+                Pattern::Variable(ident) => {
+                    let (output, tail, binding) =
+                        unify_entries_partial(head, full, binding, occurs)?;
+                    let tail_pat = binding.get(ident).unwrap().clone();
+                    let mut occurs = occurs.to_owned();
+                    occurs.push(ident.clone());
+                    let (tail, mut binding) =
+                        unify_patterns(&Pattern::Map(tail, None), &tail_pat, binding, &occurs)?;
+                    binding.set(ident.clone(), tail.clone());
+                    Some((Pattern::Map(output, Some(Box::new(tail))), binding))
+                }
+                Pattern::Wildcard => {
+                    let (mut output, mut tail, binding) =
+                        unify_entries_partial(head, full, binding, occurs)?;
+                    output.append(&mut tail);
+                    Some((Pattern::Map(output, None), binding))
+                }
+                Pattern::Map(cont, tail) => {
+                    let mut combined = head.clone();
+                    combined.append(&mut cont.clone());
+                    let lhs = Pattern::Map(combined, tail.clone());
+                    unify_patterns(&lhs, other, binding, occurs)
+                }
+                _ => None,
+            }
+        }
+        (Pattern::Map(lhs, Some(lhs_tail)), Pattern::Map(rhs, Some(rhs_tail))) => {
+            let (intersection, mut lhs_rest, mut rhs_rest, mut binding) =
+                unify_entries_difference(lhs, rhs, binding, occurs)?;
+            let unknown_tail = binding.fresh_variable();
+            let new_rhs_tail = Pattern::Map(
+                lhs_rest.clone(),
+                Some(Box::new(Pattern::Variable(unknown_tail.clone()))),
+            );
+            let new_lhs_tail = Pattern::Map(
+                rhs_rest.clone(),
+                Some(Box::new(Pattern::Variable(unknown_tail.clone()))),
+            );
+            lhs_rest.append(&mut rhs_rest);
+            let out_tail = Pattern::Map(lhs_rest, Some(Box::new(Pattern::Variable(unknown_tail))));
+            let (_, binding) = unify_patterns(lhs_tail, &new_lhs_tail, binding, occurs)?;
+            let (_, binding) = unify_patterns(rhs_tail, &new_rhs_tail, binding, occurs)?;
+            Some((
+                Pattern::Map(intersection, Some(Box::new(out_tail))),
+                binding,
+            ))
+        }
         // Otherwise, it's a failure!
         _ => None,
     }
@@ -333,6 +408,67 @@ fn unify_fields_difference(
     ))
 }
 
+fn unify_entries(
+    lhs: &Entries,
+    rhs: &Entries,
+    binding: Binding,
+    occurs: &[Identifier],
+) -> Option<(Entries, Binding)> {
+    if lhs.len() != rhs.len() {
+        return None;
+    }
+    lhs.iter().try_fold(
+        (Entries::default(), binding),
+        |(mut entries, binding), (key, pattern)| {
+            let (unified, binding) = unify_patterns(pattern, rhs.get(key)?, binding, occurs)?;
+            entries.insert(key.clone(), unified);
+            Some((entries, binding))
+        },
+    )
+}
+
+fn unify_entries_partial(
+    part: &Entries,
+    full: &Entries,
+    binding: Binding,
+    occurs: &[Identifier],
+) -> Option<(Entries, Entries, Binding)> {
+    let mut full = full.clone();
+    let (entries, binding) = part.iter().try_fold(
+        (Entries::default(), binding),
+        |(mut entries, binding), (key, pattern)| {
+            let (unified, binding) = unify_patterns(pattern, &full.remove(key)?, binding, occurs)?;
+            entries.insert(key.clone(), unified);
+            Some((entries, binding))
+        },
+    )?;
+    Some((entries, full, binding))
+}
+
+fn unify_entries_difference(
+    lhs: &Entries,
+    rhs: &Entries,
+    binding: Binding,
+    occurs: &[Identifier],
+) -> Option<(Entries, Entries, Entries, Binding)> {
+    let mut rhs_rest = rhs.clone();
+    let (intersection, lhs_rest, binding) = lhs.iter().try_fold(
+        (Entries::default(), Entries::default(), binding),
+        |(mut intersection, mut lhs_rest, binding), (key, pattern)| match rhs_rest.remove(key) {
+            Some(other) => {
+                let (unified, binding) = unify_patterns(pattern, &other, binding, occurs)?;
+                intersection.insert(key.clone(), unified);
+                Some((intersection, lhs_rest, binding))
+            }
+            None => {
+                lhs_rest.insert(key.clone(), pattern.clone());
+                Some((intersection, lhs_rest, binding))
+            }
+        },
+    )?;
+    Some((intersection, lhs_rest, rhs_rest, binding))
+}
+
 fn unify_prefix(
     lhs: &[Pattern],
     rhs: &[Pattern],
@@ -468,6 +604,10 @@ mod test {
         () => { Pattern::Record(Default::default(), None) }
     }
 
+    fn map(entries: &[(Pattern, Pattern)], tail: Option<Pattern>) -> Pattern {
+        Pattern::Map(entries.iter().cloned().collect(), tail.map(Box::new))
+    }
+
     fn unbound(pattern: Pattern) -> Pattern {
         Pattern::Unbound(Box::new(pattern))
     }
@@ -576,6 +716,37 @@ mod test {
         );
     }
 
+    #[test]
+    fn unify_map() {
+        yes!(map(&[], None), map(&[], None));
+        yes!(
+            map(&[(int(1), atom("a")), (int(2), atom("b"))], None),
+            map(&[(int(2), atom("b")), (int(1), atom("a"))], None)
+        );
+        yes!(
+            map(&[(int(1), atom("a")), (string("2"), atom("b"))], None),
+            map(&[(int(1), WILD)], Some(WILD))
+        );
+        yes!(
+            map(&[(int(1), atom("a"))], Some(WILD)),
+            map(&[(int(2), atom("b"))], Some(WILD))
+        );
+    }
+
+    #[test]
+    fn no_unify_map() {
+        no!(map(&[], None), map(&[(int(1), atom("a"))], None));
+        no!(
+            map(&[(int(1), atom("a"))], None),
+            map(&[(rat(1), atom("a"))], None)
+        );
+        no!(
+            map(&[(int(1), atom("a"))], None),
+            map(&[(int(1), atom("b"))], Some(WILD))
+        );
+        no!(map(&[], None), record! {});
+    }
+
     #[test]
     fn unify_wildcard() {
         yes!(WILD, WILD);
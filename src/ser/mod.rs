@@ -1,4 +1,4 @@
-use crate::{Struct, Value};
+use crate::{Map, Struct, Value};
 use serde::{ser, Serialize};
 use std::collections::HashMap;
 
@@ -32,7 +32,7 @@ impl<'a, 'p> ser::Serializer for &'a mut Serializer<'p> {
     type SerializeStructVariant = Self;
 
     fn serialize_bool(self, value: bool) -> crate::Result<()> {
-        *self.output = Some(Value::atom(if value { "true" } else { "false" }));
+        *self.output = Some(Value::bool(value));
         Ok(())
     }
 
@@ -87,12 +87,12 @@ impl<'a, 'p> ser::Serializer for &'a mut Serializer<'p> {
     }
 
     fn serialize_f32(self, value: f32) -> crate::Result<()> {
-        *self.output = Some(Value::rational(value));
+        *self.output = Some(Value::float(value));
         Ok(())
     }
 
     fn serialize_f64(self, value: f64) -> crate::Result<()> {
-        *self.output = Some(Value::rational(value));
+        *self.output = Some(Value::float(value));
         Ok(())
     }
 
@@ -382,6 +382,27 @@ impl<'a, 'p> ser::SerializeTupleVariant for &'a mut Serializer<'p> {
     }
 }
 
+impl Serializer<'_> {
+    /// Adds an entry to the map being serialized. Maps with only string keys are serialized as
+    /// records, but as soon as any other key is found, the record is converted to a map.
+    fn insert_entry(&mut self, key: Value, value: Option<Value>) {
+        let output = self.output.as_mut().unwrap();
+        if let (Value::Record(record), Value::String(key)) = (&mut *output, &key) {
+            record.set(key, value);
+            return;
+        }
+        if let Value::Record(record) = output {
+            let entries = record
+                .fields
+                .drain()
+                .map(|(key, value)| (Value::string(key.as_ref()), value))
+                .collect();
+            *output = Value::Map(Map::new(entries, true));
+        }
+        output.as_map_mut().unwrap().set(key, value);
+    }
+}
+
 impl<'a, 'p> ser::SerializeMap for &'a mut Serializer<'p> {
     type Ok = ();
     type Error = crate::Error;
@@ -395,18 +416,7 @@ impl<'a, 'p> ser::SerializeMap for &'a mut Serializer<'p> {
             output: &mut output,
         };
         key.serialize(&mut serializer)?;
-        // TODO: we could add some conversions to string from other types here...
-        let key_string = output
-            .unwrap()
-            .as_string()
-            .ok_or_else(|| crate::Error::ser("map keys must be strings"))?
-            .to_owned();
-        self.output
-            .as_mut()
-            .unwrap()
-            .as_record_mut()
-            .unwrap()
-            .set(key_string, None);
+        self.insert_entry(output.unwrap(), None);
         Ok(())
     }
 
@@ -419,17 +429,18 @@ impl<'a, 'p> ser::SerializeMap for &'a mut Serializer<'p> {
             output: &mut output,
         };
         value.serialize(&mut serializer)?;
-        *self
-            .output
-            .as_mut()
-            .unwrap()
-            .as_record_mut()
-            .unwrap()
-            .iter_mut()
-            .filter(|(_, value)| value.is_none())
-            .last()
-            .unwrap()
-            .1 = output;
+        match self.output.as_mut().unwrap() {
+            Value::Record(record) => {
+                *record
+                    .iter_mut()
+                    .filter(|(_, value)| value.is_none())
+                    .last()
+                    .unwrap()
+                    .1 = output
+            }
+            Value::Map(map) => map.entries.last_mut().unwrap().1 = output,
+            _ => unreachable!(),
+        }
         Ok(())
     }
 
@@ -443,23 +454,12 @@ impl<'a, 'p> ser::SerializeMap for &'a mut Serializer<'p> {
             output: &mut key_output,
         };
         key.serialize(&mut serializer)?;
-        // TODO: we could add some conversions to string from other types here...
-        let key_string = key_output
-            .unwrap()
-            .as_string()
-            .ok_or_else(|| crate::Error::ser("map keys must be strings"))?
-            .to_owned();
         let mut value_output = None;
         let mut serializer = Serializer {
             output: &mut value_output,
         };
         value.serialize(&mut serializer)?;
-        self.output
-            .as_mut()
-            .unwrap()
-            .as_record_mut()
-            .unwrap()
-            .set(key_string, value_output);
+        self.insert_entry(key_output.unwrap(), value_output);
         Ok(())
     }
 
@@ -568,8 +568,9 @@ mod test {
 
     #[test]
     fn serialize_float() {
-        assert_eq!(to_value(&3.5f32).unwrap(), Value::rational(3.5));
-        assert_eq!(to_value(&3.5f64).unwrap(), Value::rational(3.5));
+        assert_eq!(to_value(&3.5f32).unwrap(), Value::float(3.5));
+        assert_eq!(to_value(&3.5f64).unwrap(), Value::float(3.5));
+        assert_eq!(to_value(&0.1f64).unwrap(), Value::float(0.1));
     }
 
     #[test]
@@ -620,6 +621,30 @@ mod test {
         assert_eq!(to_value(&&map).unwrap(), Value::Record(record));
     }
 
+    #[test]
+    fn serialize_map_non_string_keys() {
+        let mut map = HashMap::new();
+        map.insert(1, "admin");
+        map.insert(2, "user");
+        assert_eq!(
+            to_value(&map).unwrap(),
+            Value::map(vec![
+                (Value::integer(1), "admin"),
+                (Value::integer(2), "user"),
+            ]),
+        );
+
+        let mut map = HashMap::new();
+        map.insert((1, 2), true);
+        assert_eq!(
+            to_value(&map).unwrap(),
+            Value::map(vec![(
+                Value::list(vec![Value::integer(1), Value::integer(2)]),
+                true,
+            )]),
+        );
+    }
+
     #[test]
     fn serialize_vec() {
         assert_eq!(
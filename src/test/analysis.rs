@@ -0,0 +1,107 @@
+use super::*;
+use std::path::Path;
+
+fn lines(locations: &[Location], file: &Path) -> Vec<usize> {
+    locations
+        .iter()
+        .filter(|location| location.file() == Some(file))
+        .map(Location::line)
+        .collect()
+}
+
+#[test]
+fn analysis_diagnostics() {
+    let (_, analysis) = analyze("analysis");
+    let diagnostics = analysis
+        .diagnostics()
+        .iter()
+        .map(|error| (error.kind(), error.code().unwrap()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        diagnostics,
+        vec![(ErrorKind::Parse, "L0002"), (ErrorKind::Warning, "L0001")],
+    );
+}
+
+#[test]
+fn analysis_handle_at() {
+    let (here, analysis) = analyze("analysis");
+    let handle = analysis.handle_at(here.join("main.lumber"), 3, 10).unwrap();
+    assert_eq!(handle.to_string(), "a::foo/1");
+    assert_eq!(handle.name(), "foo");
+    assert_eq!(handle.arity(), 1);
+    assert!(analysis.is_exported(&handle));
+    let handle = analysis.handle_at(here.join("a.lumber"), 4, 1).unwrap();
+    assert_eq!(handle.to_string(), "a::baz/0");
+    assert!(!analysis.is_exported(&handle));
+    assert_eq!(analysis.handle_at(here.join("main.lumber"), 3, 7), None);
+}
+
+#[test]
+fn analysis_definitions() {
+    let (here, analysis) = analyze("analysis");
+    let handle = analysis.handle_at(here.join("main.lumber"), 2, 10).unwrap();
+    let definitions = analysis.definitions(&handle);
+    assert_eq!(definitions.len(), 2);
+    assert_eq!(lines(&definitions, &here.join("a.lumber")), vec![2, 3]);
+}
+
+#[test]
+fn analysis_references() {
+    let (here, analysis) = analyze("analysis");
+    let handle = analysis.handle_at(here.join("a.lumber"), 2, 1).unwrap();
+    let references = analysis.references(&handle);
+    assert_eq!(references.len(), 5);
+    assert_eq!(lines(&references, &here.join("a.lumber")), vec![1, 3, 4]);
+    assert_eq!(lines(&references, &here.join("main.lumber")), vec![2, 3]);
+}
+
+#[test]
+fn analysis_module_at() {
+    let (here, analysis) = analyze("analysis");
+    assert_eq!(
+        analysis.module_at(here.join("main.lumber"), 1, 5),
+        Some(here.join("a.lumber").as_path()),
+    );
+    assert_eq!(analysis.module_at(here.join("main.lumber"), 3, 1), None);
+}
+
+#[test]
+fn analysis_completions() {
+    let (here, analysis) = analyze("analysis");
+    let completions = analysis
+        .completions(here.join("main.lumber"))
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>();
+    assert_eq!(completions, vec!["foo/1", "test/0"]);
+}
+
+#[test]
+fn analysis_checks_modes() {
+    let here = PathBuf::from(file!()).parent().unwrap().to_owned();
+    let analysis = Lumber::builder()
+        .analyze(
+            here.join("analysis_checks_modes"),
+            ":- mode(double(!, _)).\ndouble!(A) <- A * 2.\nbad(B) :- double(C, B), C <- 3.\n",
+        )
+        .unwrap();
+    let codes = analysis
+        .diagnostics()
+        .iter()
+        .map(|error| error.code().unwrap())
+        .collect::<Vec<_>>();
+    assert!(codes.contains(&"L0051"), "{:?}", codes);
+}
+
+#[test]
+fn analysis_native_modes() {
+    let here = PathBuf::from(file!()).parent().unwrap().to_owned();
+    let analysis = Lumber::builder()
+        .analyze(
+            here.join("analysis_native_modes"),
+            ":- nat(print/1).\n:- pub(test/0).\ntest :- print(1).\n",
+        )
+        .unwrap();
+    assert!(analysis.diagnostics().is_empty());
+}
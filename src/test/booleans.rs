@@ -0,0 +1,39 @@
+use super::*;
+
+test! {
+    booleans_operators => ""
+    ?- "@core::and(true, false, A)"
+        A = Value::bool(false);
+    ?- "@core::and(true, true, A)"
+        A = Value::bool(true);
+    ?- "@core::or(false, true, A)"
+        A = Value::bool(true);
+    ?- "@core::or(false, false, A)"
+        A = Value::bool(false);
+    ?- "@core::not(false, A)"
+        A = Value::bool(true);
+    ?- "@core::not(true, A)"
+        A = Value::bool(false);
+    ?- "@core::not(a, A)"
+    ?- "@core::and(true, 1, A)"
+}
+
+#[test]
+#[cfg(feature = "serde")]
+fn booleans_deserialize() {
+    let program = Lumber::builder()
+        .build_from_str(
+            r#"
+            :- pub(adult/2).
+            adult(Age, A) :- @core::geq(Age, 18, A).
+            "#,
+        )
+        .unwrap();
+    for (age, expected) in &[(20, true), (12, false)] {
+        let question = Question::try_from(format!("adult({}, A)", age).as_str()).unwrap();
+        let mut answers = program.ask(&question);
+        let answer = question.answer(&answers.next().unwrap()).unwrap();
+        let adult: bool = answer["A"].as_ref().unwrap().deserialize().unwrap();
+        assert_eq!(adult, *expected);
+    }
+}
@@ -11,9 +11,9 @@ test! {
     ?- "test(a, B)"
         B = Value::atom("a");
     ?- "@core::equal({ a: 1, b: 2, c: 3 }, { a: 1, ..Rest })"
-        Rest = Value::Record(Record::default().with("b", Some(Value::integer(2))).with("c", Some(Value::integer(3))));
+        Rest = record(&[("b", Value::integer(2)), ("c", Value::integer(3))]);
     ?- "@core::equal({ a: 1, ..Rest }, { a: 1, b: 2, c: 3 })"
-        Rest = Value::Record(Record::default().with("b", Some(Value::integer(2))).with("c", Some(Value::integer(3))));
+        Rest = record(&[("b", Value::integer(2)), ("c", Value::integer(3))]);
 }
 
 test! {
@@ -22,3 +22,122 @@ test! {
     ?- "@core::list::contains(d, [])"
     // ?- "@core::list::contains(d, [a, b, c])"
 }
+
+test! {
+    core_var => ""
+    ?- "@core::var(_)";
+    ?- "@core::var(A)";
+    ?- "@core::var(a)"
+    ?- "@core::var([_])"
+}
+
+test! {
+    core_ground => ""
+    ?- "@core::ground(a)";
+    ?- "@core::ground(a([1, { b: \"c\" }]))";
+    ?- "@core::ground(_)"
+    ?- "@core::ground(a(_))"
+    ?- "@core::ground([1, _])"
+    ?- "@core::ground([1, ..])"
+    ?- "@core::ground({ a: 1, .. })"
+}
+
+test! {
+    core_copy_term => ""
+    ?- "@core::copy_term(a(1), B)"
+        B = Value::Struct(Struct::new("a", Some(Value::integer(1))));
+    ?- "@core::copy_term(a(A), B)"
+        B = Value::Struct(Struct::new("a", None));
+    ?- "@core::copy_term(a(1), a(B))"
+        B = Value::integer(1);
+    ?- "@core::copy_term(a(1), b(_))"
+    ?- "@core::copy_term([A, A], [1, B])"
+        B = Value::integer(1);
+    ?- "@core::copy_term([A, A], [1, 2])"
+    ?- "@core::copy_term([A, B], [1, 2])";
+    ?- "@core::copy_term(A, 1), @core::copy_term(A, 2)";
+}
+
+test! {
+    core_struct_name => ""
+    ?- "@core::struct_name(a, N)"
+        N = Value::atom("a");
+    ?- "@core::struct_name(a(1), N)"
+        N = Value::atom("a");
+    ?- "@core::struct_name(a(1), b)"
+    ?- "@core::struct_name(1, N)"
+}
+
+test! {
+    core_struct => ""
+    ?- "@core::struct(a(1), N, C)"
+        N = Value::atom("a"), C = Value::integer(1);
+    ?- "@core::struct(S, a, [1, 2])"
+        S = Value::Struct(Struct::new("a", Some(ints(&[1, 2]))));
+    ?- "@core::struct(a, N, C)"
+    ?- "@core::struct(S, 1, C)"
+}
+
+test! {
+    core_clause => r#"
+    :- pub(hello/1).
+    :- pub(greet/2).
+    hello(a).
+    hello(b).
+    greet(A, B) :- hello(A), hello(B).
+    hidden(c).
+    "#
+    ?- "@core::clause(hello([A]), B)"
+        A = Value::atom("a"), B = Value::atom("true");
+        A = Value::atom("b"), B = Value::atom("true");
+    ?- "@core::clause(greet([a, b]), and([hello([A]), hello([B])]))"
+        A = Value::atom("a"), B = Value::atom("b");
+    ?- "@core::clause(hidden([A]), B)"
+    ?- "@core::clause(missing([A]), B)"
+    ?- "@core::clause('@core::add'([A, B, C]), D)"
+}
+
+test! {
+    core_clause_private => r#"
+    :- pub(peek/2).
+    :- mut(counter/1).
+    peek(A, B) :- @core::clause(hidden([A]), B).
+    peek(A, B) :- @core::clause(counter([A]), B).
+    hidden(c).
+    "#
+    ?- "peek(A, B)"
+        A = Value::atom("c"), B = Value::atom("true");
+}
+
+test! {
+    core_clause_other_module => r#"
+    :- mod(a).
+    :- mod(b).
+    root(r).
+    "#
+    ?- "b::peek(A, B)"
+        A = Value::atom("a"), B = Value::atom("true");
+    ?- "b::peek_root(A, B)"
+        A = Value::atom("r"), B = Value::atom("true");
+}
+
+test! {
+    core_clause_interpreter => r#"
+    :- pub(solve/1).
+    :- pub(parent/2).
+    :- pub(grandparent/2).
+    parent(a, b).
+    parent(b, c).
+    parent(b, d).
+    grandparent(A, C) :- parent(A, B), parent(B, C).
+
+    solve(true).
+    solve(and([])).
+    solve(and([Goal, ..Goals])) :- solve(Goal), solve(and(Goals)).
+    solve(Goal) :- @core::clause(Goal, Body), solve(Body).
+    "#
+    ?- "solve(grandparent([a, C]))"
+        C = Value::atom("c");
+        C = Value::atom("d");
+    ?- "solve(grandparent([b, C]))"
+}
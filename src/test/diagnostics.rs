@@ -0,0 +1,61 @@
+use super::*;
+use crate::ast::AsHandle;
+
+build_error! { singleton_variable_code => r#"
+test :- foo(A).
+foo(_).
+"#, "error[L0001]: Singleton variable A in predicate test/0." }
+
+build_error! { singleton_variable_help => r#"
+test :- foo(A).
+foo(_).
+"#, "= help: if the value is not needed, use `_` instead" }
+
+build_error! { unresolved_predicate_label => r#"
+test :- bar.
+"#, "  |         ^^^ not found in this scope" }
+
+build_error! { unbound_native_code => r#"
+:- nat(foo/0).
+"#, "error[L0033]: Native function foo/0 is not bound." }
+
+#[test]
+fn syntax_error_code() {
+    let error = Lumber::from_str("test(.").expect_err("program should not build");
+    assert_eq!(error.code(), Some("L0052"));
+    assert_eq!(error.label(), Some("unexpected input"));
+    assert!(error
+        .to_string()
+        .starts_with("error[L0052]: Syntax error: "));
+}
+
+#[test]
+fn question_lambda_code() {
+    let error = Question::try_from("test([A] :- foo(A))").expect_err("question should not parse");
+    assert!(error.to_string().contains("error[L0053]"), "{}", error);
+}
+
+#[test]
+fn render_multiline_span() {
+    let source = ast::Source::new(None, "test :-\n    foo.\n".to_owned());
+    let span = pest::Span::new(&source.code, 5, 14).unwrap();
+    let error = Error::compile(CompileError::UnsatisfiableMode {
+        handle: "foo/2".as_handle().unwrap(),
+        mode: "(?, ?)".to_owned(),
+        modes: vec!["(!, _)".to_owned(), "(_, !)".to_owned()],
+    })
+    .at(&ast::SourceSpan::new(&source, span));
+    assert_eq!(
+        error.to_string(),
+        "error[L0051]: Call to foo/2 as (?, ?) can never satisfy any of its declared modes.\n --> 1:6\n  |\n1 | test :-\n  |      ^^ no declared mode admits this call\n  |\n  = note: foo/2 is declared with the modes (!, _), (_, !)",
+    );
+    assert_eq!(
+        error.notes(),
+        &["foo/2 is declared with the modes (!, _), (_, !)".to_owned()][..],
+    );
+    assert_eq!(error.help(), None);
+}
+
+build_error! { unsatisfiable_mode_note => r#"
+bad(A, C) :- C <- A + B, B <- 1.
+"#, "= note: @core::add/3 is declared with the modes (!, !, _)" }
@@ -0,0 +1,75 @@
+use super::*;
+
+#[test]
+fn docs_modules() {
+    let (_, analysis) = analyze("docs");
+    assert!(analysis.diagnostics().is_empty());
+    let modules = analysis.modules();
+    let paths = modules.iter().map(ModuleInfo::path).collect::<Vec<_>>();
+    assert_eq!(paths, vec![vec![], vec!["util".to_owned()]]);
+    assert_eq!(modules[0].to_string(), "~");
+    assert_eq!(modules[1].to_string(), "util");
+    assert_eq!(modules[0].doc(), "The main module.\nIt has a second line.");
+    assert_eq!(
+        modules[1].doc(),
+        "Utilities for tests.\n\nHelpers for greeting."
+    );
+}
+
+#[test]
+fn docs_predicates() {
+    let (_, analysis) = analyze("docs");
+    let modules = analysis.modules();
+    let run = &modules[0].exports()[0];
+    assert_eq!(run.to_string(), "run/0");
+    assert_eq!(analysis.doc(run), Some("Runs the program."));
+
+    let greet = &modules[1].exports()[0];
+    assert_eq!(greet.to_string(), "util::greet/1:to");
+    assert_eq!(greet.positional_arity(), 1);
+    assert_eq!(greet.fields(), vec![("to".to_owned(), 1)]);
+    assert_eq!(
+        analysis.doc(greet),
+        Some("Greets someone.\n\nSays `hello`.\n\nAnother paragraph."),
+    );
+}
+
+#[test]
+fn docs_undocumented() {
+    let (here, analysis) = analyze("docs");
+    let helper = analysis.handle_at(here.join("util.lumber"), 12, 1).unwrap();
+    assert_eq!(helper.to_string(), "util::helper/0");
+    assert_eq!(analysis.doc(&helper), None);
+}
+
+#[test]
+fn docs_imported() {
+    let (here, analysis) = analyze("docs");
+    let greet = analysis.handle_at(here.join("main.lumber"), 10, 9).unwrap();
+    assert_eq!(greet.to_string(), "util::greet/1:to");
+    assert!(analysis.doc(&greet).unwrap().starts_with("Greets someone."));
+}
+
+#[test]
+fn docs_markdown() {
+    let (_, analysis) = analyze("docs");
+    let modules = analysis.modules();
+    assert_eq!(
+        analysis.document(&modules[0], DocFormat::Markdown),
+        "# Module `~`\n\nThe main module.\nIt has a second line.\n\n## Modules\n\n- [`util`](util/index.md): Utilities for tests.\n\n## Predicates\n\n### `run/0`\n\n```lumber\nrun\n```\n\nRuns the program.\n",
+    );
+    assert_eq!(
+        analysis.document(&modules[1], DocFormat::Markdown),
+        "# Module `util`\n\nUtilities for tests.\n\nHelpers for greeting.\n\n## Predicates\n\n### `greet/1:to`\n\n```lumber\ngreet(_, to: _)\n```\n\nGreets someone.\n\nSays `hello`.\n\nAnother paragraph.\n",
+    );
+}
+
+#[test]
+fn docs_html() {
+    let (_, analysis) = analyze("docs");
+    let modules = analysis.modules();
+    assert_eq!(
+        analysis.document(&modules[0], DocFormat::Html),
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>~</title>\n</head>\n<body>\n<h1>Module <code>~</code></h1>\n<p>The main module.\nIt has a second line.</p>\n<h2>Modules</h2>\n<ul>\n<li><a href=\"util/index.html\"><code>util</code></a>: Utilities for tests.</li>\n</ul>\n<h2>Predicates</h2>\n<h3><code>run/0</code></h3>\n<pre><code class=\"language-lumber\">run\n</code></pre>\n<p>Runs the program.</p>\n</body>\n</html>\n",
+    );
+}
@@ -0,0 +1,120 @@
+use super::*;
+
+#[test]
+fn duplicate_export() {
+    let errors = compile_errors(
+        "duplicate_export",
+        r#"
+        :- pub(test/0).
+        :- pub(test/0).
+        test.
+        "#,
+    );
+    match errors.as_slice() {
+        [CompileError::DuplicateExport { handle }] => {
+            assert_eq!(handle.to_string(), "test/0")
+        }
+        errors => panic!("unexpected errors {:?}", errors),
+    }
+}
+
+#[test]
+fn unbound_native() {
+    let errors = compile_errors("unbound_native", ":- nat(print/1).");
+    match errors.as_slice() {
+        [CompileError::UnboundNative { handle }] => assert_eq!(handle.to_string(), "print/1"),
+        errors => panic!("unexpected errors {:?}", errors),
+    }
+}
+
+#[test]
+fn alias_arity_mismatch() {
+    let errors = compile_errors(
+        "alias_arity_mismatch",
+        r#"
+        :- mod(a).
+        :- use(a(alias(test/0, as: hello/1))).
+        "#,
+    );
+    match errors.as_slice() {
+        [CompileError::AliasArityMismatch { input, output }] => {
+            assert_eq!(input.to_string(), "a::test/0");
+            assert_eq!(output.to_string(), "hello/1");
+        }
+        errors => panic!("unexpected errors {:?}", errors),
+    }
+}
+
+#[test]
+fn ambiguous_glob_import() {
+    let errors = compile_errors(
+        "ambiguous_glob_import",
+        r#"
+        :- mod(a).
+        :- mod(b).
+        :- use(a).
+        :- use(b).
+        main :- test.
+        "#,
+    );
+    match errors.as_slice() {
+        [CompileError::AmbiguousReference { handle, candidates }] => {
+            assert_eq!(handle.to_string(), "test/0");
+            let mut candidates = candidates
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>();
+            candidates.sort();
+            assert_eq!(candidates, vec!["a::test/0", "b::test/0"]);
+        }
+        errors => panic!("unexpected errors {:?}", errors),
+    }
+}
+
+#[test]
+fn errors_grouped_by_module() {
+    let error = build_error("submodule_location", ":- mod(a).\nfoo :- bar.\n");
+    let modules = error
+        .errors()
+        .iter()
+        .map(|error| error.module().unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(modules, vec!["a", "~"]);
+    assert!(error.to_string().starts_with("-- 1 error in module a --"));
+}
+
+#[test]
+fn syntax_errors_recovered() {
+    let error = build_error(
+        "syntax_errors_recovered",
+        "test(a).\ntest(.\ntest(b).\ntest :- .\ntest(\"c. d\").\ntest(c d).\n",
+    );
+    let lines = error
+        .errors()
+        .iter()
+        .map(|error| {
+            assert_eq!(error.code(), Some("L0052"));
+            error.location().unwrap().line()
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(lines, vec![2, 4, 6]);
+}
+
+#[test]
+fn syntax_errors_with_semantic_errors() {
+    let errors = compile_errors(
+        "syntax_errors_with_semantic_errors",
+        r#"
+        :- pub(test/0).
+        :- pub(test/0).
+        test.
+        foo(.
+        "#,
+    );
+    match errors.as_slice() {
+        [CompileError::Syntax { .. }, CompileError::DuplicateExport { handle }] => {
+            assert_eq!(handle.to_string(), "test/0")
+        }
+        errors => panic!("unexpected errors {:?}", errors),
+    }
+}
@@ -0,0 +1,89 @@
+use super::*;
+
+test! {
+    floats_literal => r#"
+    :- pub(float/2).
+    float(a, 1.5f).
+    float(b, 1e3).
+    float(c, 2.5e-1f).
+    float(d, -1.5f).
+    float(e, -2e-2).
+    "#
+    ?- "float(a, A)"
+        A = Value::float(1.5);
+    ?- "float(b, A)"
+        A = Value::float(1000.0);
+    ?- "float(c, A)"
+        A = Value::float(0.25);
+    ?- "float(d, A)"
+        A = Value::float(-1.5);
+    ?- "float(e, A)"
+        A = Value::float(-0.02);
+    ?- "float(a, 1.5)"
+}
+
+test! {
+    floats_arithmetic => r#"
+    :- pub(add/3).
+    :- pub(div/3).
+    :- pub(mul/3).
+    :- pub(pow/3).
+    add!(A, B) <- A + B.
+    div!(A, B) <- A / B.
+    mul!(A, B) <- A * B.
+    pow!(A, B) <- A ** B.
+    "#
+    ?- "add(1.5f, 1, A)"
+        A = Value::float(2.5);
+    ?- "div(1, 4f, A)"
+        A = Value::float(0.25);
+    ?- "mul(0.5, 3f, A)"
+        A = Value::float(1.5);
+    ?- "pow(2f, 0.5, A)"
+        A = Value::float(2f64.sqrt());
+    ?- "add(-1.5f, 1, A)"
+        A = Value::float(-0.5);
+    ?- "add(1, 1, A)"
+        A = Value::integer(2);
+    ?- "add(1f, a, A)"
+}
+
+test! {
+    floats_compare => ""
+    ?- "@core::eq(1.5f, 1.5, A)"
+        A = Value::atom("true");
+    ?- "@core::lt(1, 1.5f, A)"
+        A = Value::atom("true");
+    ?- "@core::max(2f, 1, A)"
+        A = Value::float(2.0);
+}
+
+test! {
+    floats_conversion => ""
+    ?- "@core::float(1.5, A)"
+        A = Value::float(1.5);
+    ?- "@core::integer(3f, A)"
+        A = Value::integer(3);
+    ?- "@core::integer(3.5f, A)"
+    ?- "@core::floor(2.5f, A)"
+        A = Value::integer(2);
+    ?- "@core::floor(2.5f, 2)";
+    ?- "@core::floor(2.5, 2)";
+    ?- "@core::round(2.5f, A)"
+        A = Value::integer(3);
+    ?- "@core::abs(2.5f, A)"
+        A = Value::float(2.5);
+}
+
+#[test]
+fn floats_display_round_trip() {
+    for float in &[0.1, 1.0, 1e100, 2.5e-8, -1.5, -3e-20] {
+        let value = Value::float(*float);
+        let source = format!(":- pub(test/1).\ntest({}).", value);
+        let program = Lumber::builder().build_from_str(source).unwrap();
+        let question = Question::try_from("test(A)").unwrap();
+        let mut answers = program.ask(&question);
+        let answer = question.answer(&answers.next().unwrap()).unwrap();
+        assert_eq!(answer["A"], Some(value));
+    }
+}
@@ -0,0 +1,100 @@
+use super::*;
+
+test! {
+    lambda_call => r#"
+    :- pub(test/1).
+    test(A) :- @core::call([X] :- @core::equal(X, a), A).
+    "#
+    ?- "test(a)";
+    ?- "test(b)"
+    ?- "test(A)"
+        A = Value::atom("a");
+}
+
+test! {
+    lambda_call_passed => r#"
+    :- pub(test/1).
+    hello(a).
+    hello(b).
+    apply(P, X) :- @core::call(P, X).
+    test(A) :- apply([X] :- hello(X), A).
+    "#
+    ?- "test(a)";
+    ?- "test(c)"
+    ?- "test(A)"
+        A = Value::atom("a");
+        A = Value::atom("b");
+}
+
+test! {
+    lambda_capture => r#"
+    :- pub(test/2).
+    apply(P, X) :- @core::call(P, X).
+    test(A, B) :- apply([X] :- @core::equal(X, A), B).
+    "#
+    ?- "test(a, a)";
+    ?- "test(a, b)"
+    ?- "test(a, B)"
+        B = Value::atom("a");
+}
+
+test! {
+    lambda_capture_later => r#"
+    :- pub(test/1).
+    test(B) :- @core::call([] :- @core::equal(A, a)), @core::equal(A, B).
+    "#
+    ?- "test(a)";
+    ?- "test(b)"
+    ?- "test(B)"
+        B = Value::atom("a");
+}
+
+test! {
+    lambda_nested_capture_later => r#"
+    :- pub(test/1).
+    test(B) :- @core::call([X] :- (@core::call([] :- @core::equal(Y, a)), @core::equal(X, Y)), B).
+    "#
+    ?- "test(B)"
+        B = Value::atom("a");
+}
+
+test! {
+    lambda_multiple_params => r#"
+    :- pub(test/2).
+    test(A, B) :- @core::call([X, Y] :- (@core::equal(X, a), @core::equal(Y, b)), A, B).
+    "#
+    ?- "test(A, B)"
+        A = Value::atom("a"), B = Value::atom("b");
+}
+
+test! {
+    lambda_shadowed_param => r#"
+    :- pub(test/2).
+    test(X, Y) :- @core::equal(X, 1) -> @core::call([X] :- @core::equal(X, 2), Y).
+    "#
+    ?- "test(X, Y)"
+        X = Value::integer(1), Y = Value::integer(2);
+}
+
+test! {
+    lambda_nested => r#"
+    :- pub(test/2).
+    test(A, B) :- @core::call([X] :- @core::call([Y] :- @core::equal(Y, X), A), B).
+    "#
+    ?- "test(a, B)"
+        B = Value::atom("a");
+    ?- "test(a, b)"
+}
+
+test! {
+    lambda_in_list => r#"
+    :- pub(test/1).
+    hello(a).
+    hello(b).
+    hello(c).
+    filter(P, L, O) :- O <- [X : @core::list::contains(X, L), @core::call(P, X)].
+    test(A) :- filter([X] :- hello(X), [a, d, c, e], A).
+    "#
+    ?- "test(A)"
+        A = Value::list(vec![Value::atom("a"), Value::atom("c")]);
+}
@@ -0,0 +1,167 @@
+use super::*;
+
+#[test]
+fn singleton_variable_denied_by_default() {
+    let error = build_error(
+        "singleton_variable_denied_by_default",
+        "test :- foo(A).\nfoo(_).\n",
+    );
+    assert_eq!(error.errors()[0].code(), Some("L0001"));
+}
+
+#[test]
+fn singleton_variable_warns() {
+    let warnings = warnings(
+        Lumber::builder().lint(Lint::SingletonVariable, LintLevel::Warn),
+        "singleton_variable_warns",
+        "test :- foo(A).\nfoo(_).\n",
+    );
+    match warnings.as_slice() {
+        [CompileError::SingletonVariable { variable, .. }] => assert_eq!(variable, "A"),
+        warnings => panic!("unexpected warnings {:?}", warnings),
+    }
+}
+
+#[test]
+fn warning_rendering() {
+    let program = build(
+        Lumber::builder().lint(Lint::SingletonVariable, LintLevel::Warn),
+        "warning_rendering",
+        "test :- foo(A).\nfoo(_).\n",
+    );
+    let warning = program.warnings()[0].to_string();
+    assert!(
+        warning.starts_with("warning[L0001]: Singleton variable A in predicate test/0."),
+        "{}",
+        warning
+    );
+    assert_eq!(program.warnings()[0].kind(), ErrorKind::Warning);
+    assert_eq!(program.warnings()[0].lint(), Some(Lint::SingletonVariable));
+}
+
+#[test]
+fn lint_allowed_by_builder() {
+    let warnings = warnings(
+        Lumber::builder().lint(Lint::SingletonVariable, LintLevel::Allow),
+        "lint_allowed_by_builder",
+        "test :- foo(A).\nfoo(_).\n",
+    );
+    assert!(warnings.is_empty(), "unexpected warnings {:?}", warnings);
+}
+
+#[test]
+fn lint_warned_by_directive() {
+    let program = build(
+        Lumber::builder(),
+        "lint_warned_by_directive",
+        ":- warn(singleton_variable).\ntest :- foo(A).\nfoo(_).\n",
+    );
+    assert_eq!(program.warnings()[0].code(), Some("L0001"));
+}
+
+#[test]
+fn lint_directive_overrides_builder() {
+    let warnings = warnings(
+        Lumber::builder().lint(Lint::SingletonVariable, LintLevel::Warn),
+        "lint_directive_overrides_builder",
+        ":- allow(singleton_variable).\ntest :- foo(A).\nfoo(_).\n",
+    );
+    assert!(warnings.is_empty(), "unexpected warnings {:?}", warnings);
+}
+
+#[test]
+fn lint_level_inherited() {
+    let error = build_error(
+        "lint_level_inherited",
+        ":- deny(unused_predicate).\n:- mod(a).\n",
+    );
+    match error.errors() {
+        [error] => {
+            assert_eq!(error.code(), Some("L0055"));
+            assert_eq!(error.module(), Some("a"));
+        }
+        errors => panic!("unexpected errors {:?}", errors),
+    }
+}
+
+build_error! { unknown_lint => r#"
+:- deny(singleton).
+"#, "error[L0058]: Unknown lint singleton." }
+
+#[test]
+fn unused_import() {
+    let warnings = warnings(
+        Lumber::builder(),
+        "unused_import",
+        ":- mod(a).\n:- mod(b).\n",
+    );
+    match warnings.as_slice() {
+        [CompileError::UnusedImport { handle, .. }] => {
+            assert_eq!(handle.to_string(), "foo/0")
+        }
+        warnings => panic!("unexpected warnings {:?}", warnings),
+    }
+}
+
+#[test]
+fn unused_predicate() {
+    let warnings = warnings(Lumber::builder(), "unused_predicate", ":- mod(a).\n");
+    match warnings.as_slice() {
+        [CompileError::UnusedPredicate { handle }] => {
+            assert_eq!(handle.to_string(), "unused/0")
+        }
+        warnings => panic!("unexpected warnings {:?}", warnings),
+    }
+}
+
+#[test]
+fn shadowed_glob() {
+    let warnings = warnings(
+        Lumber::builder(),
+        "shadowed_glob",
+        ":- mod(a).\n:- use(a).\ntest.\n",
+    );
+    match warnings.as_slice() {
+        [CompileError::ShadowedGlob { handle, module }] => {
+            assert_eq!(handle.to_string(), "test/0");
+            assert_eq!(module, "a");
+        }
+        warnings => panic!("unexpected warnings {:?}", warnings),
+    }
+}
+
+#[test]
+fn unreachable_clause() {
+    let warnings = warnings(
+        Lumber::builder(),
+        "unreachable_clause",
+        r#"
+        len([], 0).
+        len([_, ..T], N) :- len(T, M), N <- M + 1.
+        first([a, b, c]).
+        test(N) :- len([a, b], N), first([a, b]).
+        "#,
+    );
+    match warnings.as_slice() {
+        [CompileError::UnreachableClause { handle, call }] => {
+            assert_eq!(handle.to_string(), "test/1");
+            assert_eq!(call.to_string(), "first/1");
+        }
+        warnings => panic!("unexpected warnings {:?}", warnings),
+    }
+}
+
+#[test]
+fn reachable_clauses() {
+    let warnings = warnings(
+        Lumber::builder(),
+        "reachable_clauses",
+        r#"
+        color(red).
+        color(rgb[_, _, _]).
+        test :- color(red), color(rgb[1, 2, 3]), color(_).
+        test :- color(blue); color(red).
+        "#,
+    );
+    assert!(warnings.is_empty(), "unexpected warnings {:?}", warnings);
+}
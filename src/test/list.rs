@@ -0,0 +1,152 @@
+use super::*;
+
+test! {
+    list_length => ""
+    ?- "@core::list::length([a, b], A)"
+        A = Value::integer(2);
+    ?- "@core::list::length([a, b], 2)";
+    ?- "@core::list::length([a, b], 3)"
+}
+
+test! {
+    list_append => ""
+    ?- "@core::list::append([1, 2], [3], A)"
+        A = ints(&[1, 2, 3]);
+    ?- "@core::list::append(A, [3], [1, 2, 3])"
+        A = ints(&[1, 2]);
+    ?- "@core::list::append([1], A, [1, 2, 3])"
+        A = ints(&[2, 3]);
+    ?- "@core::list::append(A, B, [1, 2])"
+        A = ints(&[]), B = ints(&[1, 2]);
+        A = ints(&[1]), B = ints(&[2]);
+        A = ints(&[1, 2]), B = ints(&[]);
+    ?- "@core::list::append([2], A, [1, 2, 3])"
+}
+
+test! {
+    list_reverse => ""
+    ?- "@core::list::reverse([1, 2, 3], A)"
+        A = ints(&[3, 2, 1]);
+    ?- "@core::list::reverse([], A)"
+        A = ints(&[]);
+}
+
+test! {
+    list_nth => ""
+    ?- "@core::list::nth0(1, [a, b, c], A)"
+        A = Value::atom("b");
+    ?- "@core::list::nth1(1, [a, b, c], A)"
+        A = Value::atom("a");
+    ?- "@core::list::nth0(3, [a, b, c], A)"
+    ?- "@core::list::nth0(-1, [a, b, c], A)"
+    ?- "@core::list::nth1(0, [a, b, c], A)"
+    ?- "@core::list::nth0(1, [a, b, .._], A)"
+        A = Value::atom("b");
+    ?- "@core::list::nth1(2, [a, b, .._], A)"
+        A = Value::atom("b");
+    ?- "@core::list::nth0(A, [a, b, a], a)"
+        A = Value::integer(0);
+        A = Value::integer(2);
+    ?- "@core::list::nth1(A, [a, b], B)"
+        A = Value::integer(1), B = Value::atom("a");
+        A = Value::integer(2), B = Value::atom("b");
+}
+
+test! {
+    list_last => ""
+    ?- "@core::list::last([1, 2, 3], A)"
+        A = Value::integer(3);
+    ?- "@core::list::last([], A)"
+}
+
+test! {
+    list_take_drop => ""
+    ?- "@core::list::take(2, [1, 2, 3], A)"
+        A = ints(&[1, 2]);
+    ?- "@core::list::take(0, [1, 2, 3], A)"
+        A = ints(&[]);
+    ?- "@core::list::take(4, [1, 2, 3], A)"
+    ?- "@core::list::drop(2, [1, 2, 3], A)"
+        A = ints(&[3]);
+    ?- "@core::list::drop(3, [1, 2, 3], A)"
+        A = ints(&[]);
+    ?- "@core::list::drop(4, [1, 2, 3], A)"
+}
+
+test! {
+    list_zip => ""
+    ?- "@core::list::zip([1, 2], [3, 4], A)"
+        A = Value::list(vec![
+            ints(&[1, 3]),
+            ints(&[2, 4]),
+        ]);
+    ?- "@core::list::zip(A, B, [[1, 3], [2, 4]])"
+        A = ints(&[1, 2]), B = ints(&[3, 4]);
+    ?- "@core::list::zip([1], [3, 4], A)"
+}
+
+test! {
+    list_flatten => ""
+    ?- "@core::list::flatten([[1, 2], [], [3]], A)"
+        A = ints(&[1, 2, 3]);
+}
+
+test! {
+    list_sum_max_min => ""
+    ?- "@core::list::sum([1, 2, 3], A)"
+        A = Value::integer(6);
+    ?- "@core::list::sum([], A)"
+        A = Value::integer(0);
+    ?- "@core::list::max([3, 1, 4, 1, 5], A)"
+        A = Value::integer(5);
+    ?- "@core::list::min([3, 1, 4, 1, 5], A)"
+        A = Value::integer(1);
+    ?- "@core::list::max([], A)"
+    ?- "@core::list::min([\"b\", \"a\"], A)"
+        A = Value::string("a");
+}
+
+test! {
+    list_sort => ""
+    ?- "@core::list::msort([3, 1, 2, 1], A)"
+        A = ints(&[1, 1, 2, 3]);
+    ?- "@core::list::sort([3, 1, 2, 1], A)"
+        A = ints(&[1, 2, 3]);
+    ?- "@core::list::sort([], A)"
+        A = ints(&[]);
+    ?- "@core::list::msort([\"b\", \"c\", \"a\"], A)"
+        A = Value::list(vec![Value::string("a"), Value::string("b"), Value::string("c")]);
+}
+
+test! {
+    list_dedupe => ""
+    ?- "@core::list::dedupe([b, a, b, c, a], A)"
+        A = Value::list(vec![Value::atom("b"), Value::atom("a"), Value::atom("c")]);
+}
+
+test! {
+    list_select_permutation => ""
+    ?- "@core::list::select(A, [1, 2, 3], B)"
+        A = Value::integer(1), B = ints(&[2, 3]);
+        A = Value::integer(2), B = ints(&[1, 3]);
+        A = Value::integer(3), B = ints(&[1, 2]);
+    ?- "@core::list::select(4, [1, 2, 3], B)"
+    ?- "@core::list::permutation([1, 2, 3], A)"
+        A = ints(&[1, 2, 3]);
+        A = ints(&[1, 3, 2]);
+        A = ints(&[2, 1, 3]);
+        A = ints(&[2, 3, 1]);
+        A = ints(&[3, 1, 2]);
+        A = ints(&[3, 2, 1]);
+}
+
+test! {
+    list_between => ""
+    ?- "@core::list::between(1, 3, A)"
+        A = Value::integer(1);
+        A = Value::integer(2);
+        A = Value::integer(3);
+    ?- "@core::list::between(3, 1, A)"
+    ?- "@core::list::between(1, 3, 2)";
+    ?- "@core::list::between(1, 3, 4)"
+}
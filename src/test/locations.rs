@@ -0,0 +1,36 @@
+use super::*;
+
+#[test]
+fn syntax_error_location() {
+    let error = build_error("syntax_error_location", "test.\ntest(.\ntest.\n");
+    let location = error
+        .location()
+        .expect("syntax errors should have a location");
+    assert_eq!(location.line(), 2);
+    assert_eq!(location.file(), None);
+}
+
+build_error! { singleton_variable_location => r#"
+test :-
+    foo(A).
+foo(_).
+"#, "Singleton variable A in predicate test/0.\n --> 3:9\n  |\n3 |     foo(A)." }
+
+build_error! { unresolved_predicate_location => r#"
+test(A) :- foo(A),
+    bar(A).
+foo(_).
+"#, " --> 3:5\n  |\n3 |     bar(A)." }
+
+build_error! { operator_location => r#"
+bad(A, C) :- C <- A + B, B <- 1.
+"#, "can never satisfy any of its declared modes.\n --> 2:21" }
+
+build_error! { directive_location => r#"
+test.
+:- nat(foo/0).
+"#, "Native function foo/0 is not bound.\n --> 3:8" }
+
+build_error! { submodule_location => r#"
+:- mod(a).
+"#, "a.lumber:3:5\n  |\n3 |     undefined(A)." }
@@ -0,0 +1,64 @@
+use super::*;
+
+test! {
+    map_unify => r#"
+    :- pub(roles/1).
+    roles({ 1 => admin, 2 => user, "guest" => none }).
+    "#
+    ?- "roles({ 2 => A, .. })"
+        A = Value::atom("user");
+    ?- "roles({ \"guest\" => A, ..Rest })"
+        A = Value::atom("none"),
+        Rest = Value::map(vec![(Value::integer(1), Value::atom("admin")), (Value::integer(2), Value::atom("user"))]);
+    ?- "roles({ 3 => A, .. })"
+    ?- "roles({ 1 => A })"
+    ?- "roles({ 1.0 => A, .. })"
+}
+
+test! {
+    map_get => ""
+    ?- "@core::map::get({ 1 => a, [2] => b }, [2], A)"
+        A = Value::atom("b");
+    ?- "@core::map::get({ 1 => a }, 2, A)"
+    ?- "@core::map::get({ 1 => a, .. }, 2, A)"
+    ?- "@core::map::get({ 1 => a }, K, V)"
+        K = Value::integer(1), V = Value::atom("a");
+}
+
+test! {
+    map_put => ""
+    ?- "@core::map::put({ 1 => a }, 2, b, A)"
+        A = Value::map(vec![(Value::integer(1), Value::atom("a")), (Value::integer(2), Value::atom("b"))]);
+    ?- "@core::map::put({ 1 => a }, 1, b, A)"
+        A = Value::map(vec![(Value::integer(1), Value::atom("b"))]);
+    ?- "@core::map::put({=>}, K, b, A)"
+}
+
+test! {
+    map_delete => ""
+    ?- "@core::map::delete({ 1 => a, 2 => b }, 1, A)"
+        A = Value::map(vec![(Value::integer(2), Value::atom("b"))]);
+    ?- "@core::map::delete({ 1 => a }, 2, A)"
+        A = Value::map(vec![(Value::integer(1), Value::atom("a"))]);
+    ?- "@core::map::delete({ 1 => a, .. }, 2, A)"
+}
+
+test! {
+    map_pairs => ""
+    ?- "@core::map::pairs({ \"a\" => 1 }, A)"
+        A = Value::list(vec![Value::list(vec![Value::string("a"), Value::integer(1)])]);
+    ?- "@core::map::pairs(A, [[1, a], [2, b], [1, c]])"
+        A = Value::map(vec![(Value::integer(1), Value::atom("c")), (Value::integer(2), Value::atom("b"))]);
+    ?- "@core::map::keys({ 1 => a }, A)"
+        A = ints(&[1]);
+    ?- "@core::map::values({ 1 => a }, A)"
+        A = Value::list(vec![Value::atom("a")]);
+    ?- "@core::map::merge({ 1 => a, 2 => b }, { 2 => c }, A)"
+        A = Value::map(vec![(Value::integer(1), Value::atom("a")), (Value::integer(2), Value::atom("c"))]);
+}
+
+#[test]
+fn map_unbound_key() {
+    assert!(Lumber::from_str("test(K, { K => 1 }).").is_err());
+    assert!(Lumber::from_str("test({ 1 => a, 1 => b }).").is_err());
+}
@@ -0,0 +1,104 @@
+use super::*;
+
+test! {
+    math_abs_neg => r#"
+    :- pub(abs_neg/2).
+    abs_neg!(X) <- N <- 0 - X, @core::abs!(N).
+    "#
+    ?- "abs_neg(3, A)"
+        A = Value::integer(3);
+    ?- "@core::abs(1.5, A)"
+        A = Value::rational(1.5);
+    ?- "@core::neg(3, A)"
+        A = Value::integer(-3);
+    ?- "@core::neg(1.5, A)"
+        A = Value::rational(-1.5);
+    ?- "@core::abs(a, A)"
+}
+
+test! {
+    math_min_max => ""
+    ?- "@core::min(1, 2, A)"
+        A = Value::integer(1);
+    ?- "@core::max(1, 2.5, A)"
+        A = Value::rational(2.5);
+    ?- "@core::min(\"b\", \"a\", A)"
+        A = Value::string("a");
+    ?- "@core::max(1, a, A)"
+}
+
+test! {
+    math_gcd_lcm => r#"
+    :- pub(gcd_neg/3).
+    gcd_neg!(X, Y) <- N <- 0 - X, @core::gcd!(N, Y).
+    "#
+    ?- "@core::gcd(12, 18, A)"
+        A = Value::integer(6);
+    ?- "gcd_neg(12, 18, A)"
+        A = Value::integer(6);
+    ?- "@core::lcm(4, 6, A)"
+        A = Value::integer(12);
+    ?- "@core::lcm(0, 6, A)"
+        A = Value::integer(0);
+}
+
+test! {
+    math_rounding => r#"
+    :- pub(floor_neg/2).
+    :- pub(ceil_neg/2).
+    :- pub(round_neg/2).
+    :- pub(truncate_neg/2).
+    floor_neg!(X) <- N <- 0 - X, @core::floor!(N).
+    ceil_neg!(X) <- N <- 0 - X, @core::ceil!(N).
+    round_neg!(X) <- N <- 0 - X, @core::round!(N).
+    truncate_neg!(X) <- N <- 0 - X, @core::truncate!(N).
+    "#
+    ?- "@core::floor(2.5, A)"
+        A = Value::integer(2);
+    ?- "floor_neg(2.5, A)"
+        A = Value::integer(-3);
+    ?- "@core::ceil(2.5, A)"
+        A = Value::integer(3);
+    ?- "ceil_neg(2.5, A)"
+        A = Value::integer(-2);
+    ?- "@core::round(2.5, A)"
+        A = Value::integer(3);
+    ?- "round_neg(2.5, A)"
+        A = Value::integer(-3);
+    ?- "@core::round(2.4, A)"
+        A = Value::integer(2);
+    ?- "@core::truncate(2.5, A)"
+        A = Value::integer(2);
+    ?- "truncate_neg(2.5, A)"
+        A = Value::integer(-2);
+    ?- "@core::floor(4, A)"
+        A = Value::integer(4);
+}
+
+test! {
+    math_rational_parts => ""
+    ?- "@core::numerator(0.75, A)"
+        A = Value::integer(3);
+    ?- "@core::denominator(0.75, A)"
+        A = Value::integer(4);
+    ?- "@core::denominator(5, A)"
+        A = Value::integer(1);
+    ?- "@core::integer(4.0, A)"
+        A = Value::integer(4);
+    ?- "@core::integer(4.5, A)"
+    ?- "@core::rational(4, A)"
+        A = Value::rational(4);
+}
+
+test! {
+    math_functions => r#"
+    :- pub(distance/3).
+    :- pub(mean/2).
+    distance!(A, B) <- D <- A - B, @core::abs!(D).
+    mean!(A) <- M <- @core::list::sum!(A) / @core::list::length!(A), @core::round!(M).
+    "#
+    ?- "distance(2, 5, A)"
+        A = Value::integer(3);
+    ?- "mean([1, 2, 4], A)"
+        A = Value::integer(2);
+}
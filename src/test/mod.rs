@@ -43,11 +43,115 @@ macro_rules! test {
     };
 }
 
+/// Builds a program which is expected to fail, from a source string in the directory of the test.
+fn build_error(name: &str, src: &str) -> Error {
+    let here = PathBuf::from(file!()).parent().unwrap().to_owned();
+    Lumber::builder()
+        .build(here.join(name), src)
+        .expect_err("program should not build")
+}
+
+/// Builds a program which is expected to compile, from a source string in the directory of the
+/// test.
+fn build<'p>(builder: LumberBuilder<'p>, name: &str, src: &str) -> Lumber<'p> {
+    let here = PathBuf::from(file!()).parent().unwrap().to_owned();
+    builder
+        .build(here.join(name), src)
+        .expect("program should build")
+}
+
+/// The problems reported as warnings by a program which is expected to compile.
+fn warnings(builder: LumberBuilder, name: &str, src: &str) -> Vec<CompileError> {
+    build(builder, name, src)
+        .warnings()
+        .iter()
+        .map(|warning| {
+            warning
+                .compile_error()
+                .expect("warning should have a detail")
+                .clone()
+        })
+        .collect()
+}
+
+/// The problems which prevent a program from compiling.
+fn compile_errors(name: &str, src: &str) -> Vec<CompileError> {
+    build_error(name, src)
+        .errors()
+        .iter()
+        .map(|error| {
+            error
+                .compile_error()
+                .expect("error should have a detail")
+                .clone()
+        })
+        .collect()
+}
+
+/// A list of integers.
+fn ints(values: &[i64]) -> Value {
+    Value::list(values.iter().map(|&value| Value::integer(value)))
+}
+
+/// A record with the given fields, all of which are set.
+fn record(fields: &[(&str, Value)]) -> Value {
+    Value::Record(
+        fields
+            .iter()
+            .fold(Record::default(), |record, (key, value)| {
+                record.with(key, Some(value.clone()))
+            }),
+    )
+}
+
+/// Analyzes the program in a directory beside the tests, allowing singleton variables to only warn.
+fn analyze(name: &str) -> (PathBuf, Analysis) {
+    let here = PathBuf::from(file!()).parent().unwrap().join(name);
+    let analysis = Lumber::builder()
+        .lint(Lint::SingletonVariable, LintLevel::Warn)
+        .analyze_file(here.join("main.lumber"))
+        .expect("program should be analyzed");
+    (here, analysis)
+}
+
+macro_rules! build_error {
+    ($name:ident => $src:literal, $message:literal) => {
+        #[test]
+        fn $name() {
+            let here = PathBuf::from(file!()).parent().unwrap().to_owned();
+            let path = here.join(stringify!($name));
+            let error = Lumber::builder()
+                .build(path, $src)
+                .expect_err("program should not build")
+                .to_string();
+            assert!(error.contains($message), "{}", error);
+        }
+    };
+}
+
 mod accessibility;
+mod analysis;
 mod assumption;
 mod bindings;
+mod booleans;
 mod conjunction;
 mod core;
+mod diagnostics;
 mod disjunction;
+mod docs;
+mod errors;
+mod floats;
+mod lambdas;
+mod lints;
+mod list;
+mod locations;
+mod map;
+mod math;
+mod modes;
 mod operators;
+mod pretty;
 mod procession;
+mod record;
+mod strings;
+mod testing;
+mod types;
@@ -0,0 +1,174 @@
+use super::*;
+
+test! {
+    moded_call => r#"
+    :- pub(quad/2).
+    :- mode(double(!, _)).
+    double!(A) <- A * 2.
+    quad!(A) <- double!(A) * 2.
+    "#
+    ?- "quad(2, B)"
+        B = Value::integer(8);
+}
+
+test! {
+    moded_alternatives => r#"
+    :- pub(test/1).
+    :- mode(conv(!, _)).
+    :- mode(conv(_, !)).
+    conv(A, A).
+    test(A) :- conv(B, A), conv(B, _).
+    "#
+    ?- "test(1)";
+}
+
+test! {
+    moded_bound_result => ""
+    ?- "@core::add(1, 2, 3)";
+    ?- "@core::add(1, 2, 4)"
+}
+
+build_error! {
+    unbound_argument => r#"
+    :- mode(double(!, _)).
+    double!(A) <- A * 2.
+    bad(B) :- double(C, B), C <- 3.
+    "#, "Call to double/2 as (?, _) can never satisfy"
+}
+
+build_error! {
+    unbound_in_other_case => r#"
+    :- mode(double(!, _)).
+    double!(A) <- A * 2.
+    bad(B) :- C <- 3 ; double(C, B).
+    "#, "Call to double/2 as (?, _) can never satisfy"
+}
+
+build_error! {
+    bound_argument => r#"
+    :- mode(fresh(?)).
+    fresh(_).
+    bad :- fresh(1).
+    "#, "Call to fresh/1 as (!) can never satisfy"
+}
+
+build_error! {
+    unbound_operand => r#"
+    bad(A, C) :- C <- A + B, B <- 1.
+    "#, "can never satisfy any of its declared modes"
+}
+
+build_error! {
+    invalid_mode => r#"
+    :- mode(fresh(X)).
+    fresh(_).
+    "#, "Invalid mode for fresh/1"
+}
+
+build_error! {
+    undefined_mode => r#"
+    :- mode(fresh(?)).
+    "#, "Mode or determinism is declared for fresh/1"
+}
+
+build_error! {
+    duplicate_determinism => r#"
+    :- det(fresh/1).
+    :- det(fresh/1, semidet).
+    fresh(_).
+    "#, "Determinism of fresh/1 declared multiple times"
+}
+
+#[test]
+fn det_single_answer() {
+    let program = build(
+        Lumber::builder().debug(true),
+        "det_single_answer",
+        ":- pub(one/1).\n:- det(one/1).\none(1).",
+    );
+    let question = Question::try_from("one(A)").unwrap();
+    let answers = program.ask_checked(&question).collect::<Vec<_>>();
+    assert_eq!(answers.len(), 1);
+    assert!(answers[0].is_ok());
+}
+
+#[test]
+fn det_many_answers() {
+    let program = build(
+        Lumber::builder().debug(true),
+        "det_many_answers",
+        ":- pub(two/1).\n:- det(two/1).\ntwo(1).\ntwo(2).",
+    );
+    let question = Question::try_from("two(A)").unwrap();
+    let mut answers = program.ask_checked(&question);
+    assert!(answers.next().unwrap().is_ok());
+    let error = answers.next().unwrap().unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::Determinism);
+    assert_eq!(
+        error.message(),
+        "two/1 is declared det, but produced more than 1 answer."
+    );
+    assert!(answers.next().is_none());
+}
+
+#[test]
+fn multi_no_answers() {
+    let program = build(
+        Lumber::builder().debug(true),
+        "multi_no_answers",
+        ":- pub(one/1).\n:- det(one/1, multi).\none(1).",
+    );
+    let question = Question::try_from("one(2)").unwrap();
+    let mut answers = program.ask_checked(&question);
+    let error = answers.next().unwrap().unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::Determinism);
+    assert_eq!(
+        error.message(),
+        "one/1 is declared multi, but produced no answers."
+    );
+    assert!(answers.next().is_none());
+}
+
+#[test]
+fn det_violation_ends_answers() {
+    let program = build(
+        Lumber::builder().debug(true),
+        "det_violation_ends_answers",
+        ":- pub(two/1).\n:- det(two/1).\ntwo(1).\ntwo(2).",
+    );
+    let question = Question::try_from("two(A)").unwrap();
+    assert_eq!(program.ask(&question).count(), 1);
+}
+
+#[test]
+fn semidet_no_answers() {
+    let program = build(
+        Lumber::builder().debug(true),
+        "semidet_no_answers",
+        ":- pub(one/1).\n:- det(one/1, semidet).\none(1).",
+    );
+    let question = Question::try_from("one(2)").unwrap();
+    assert!(program.ask_checked(&question).next().is_none());
+}
+
+#[test]
+fn det_other_mode() {
+    let program = build(
+        Lumber::builder().debug(true),
+        "det_other_mode",
+        ":- pub(one/1).\n:- mode(one(?)).\n:- det(one/1).\none(1).",
+    );
+    let question = Question::try_from("one(2)").unwrap();
+    assert!(program.ask_checked(&question).next().is_none());
+}
+
+#[test]
+fn det_without_debug() {
+    let program = build(
+        Lumber::builder(),
+        "det_without_debug",
+        ":- pub(two/1).\n:- det(two/1).\ntwo(1).\ntwo(2).",
+    );
+    let question = Question::try_from("two(A)").unwrap();
+    assert_eq!(program.ask_checked(&question).count(), 2);
+}
@@ -73,3 +73,129 @@ test! {
     ?- "rem5(4.0, A)"
     ?- "rem5(A, 5)"
 }
+
+test! {
+    op_pow => r#"
+    :- pub(pow/3).
+    :- pub(inverse_square/2).
+    pow!(A, B) <- A ** B.
+    inverse_square!(A) <- A ** (0 - 2).
+    "#
+    ?- "pow(2, 10, A)"
+        A = Value::integer(1024);
+    ?- "pow(1.5, 2, A)"
+        A = Value::rational(2.25);
+    ?- "pow(2, 0.5, A)"
+    ?- "inverse_square(2, A)"
+        A = Value::rational(0.25);
+    ?- "inverse_square(0.5, A)"
+        A = Value::rational(4);
+    ?- "inverse_square(0, A)"
+    ?- "pow(2, 32768, A)"
+        A = Value::integer(ramp::Int::from(2).pow(32768));
+    ?- "pow(2, 32769, A)"
+    ?- "pow(2, 4294967295, A)"
+    ?- "pow(0.5, 100000, A)"
+}
+
+test! {
+    op_pow_right_assoc => r#"
+    :- pub(test/1).
+    test! <- 2 ** 3 ** 2.
+    "#
+    ?- "test(A)"
+        A = Value::integer(512);
+}
+
+test! {
+    op_comparison => r#"
+    :- pub(lt/3).
+    :- pub(leq/3).
+    :- pub(eq/3).
+    :- pub(neq/3).
+    lt!(A, B) <- A < B.
+    leq!(A, B) <- A <= B.
+    eq!(A, B) <- A == B.
+    neq!(A, B) <- A != B.
+    "#
+    ?- "lt(1, 2, A)"
+        A = Value::atom("true");
+    ?- "lt(2, 1.5, A)"
+        A = Value::atom("false");
+    ?- "lt(\"a\", \"b\", A)"
+        A = Value::atom("true");
+    ?- "lt(a, b, A)"
+    ?- "leq(2, 2.0, A)"
+        A = Value::atom("true");
+    ?- "eq(2, 2.0, A)"
+        A = Value::atom("true");
+    ?- "eq(a(1), a(1), A)"
+        A = Value::atom("true");
+    ?- "neq(a, b, A)"
+        A = Value::atom("true");
+    ?- "eq(_, a, A)"
+}
+
+test! {
+    op_boolean => r#"
+    :- pub(test/4).
+    test!(A, B, C) <- A < B && B < C || A == C.
+    "#
+    ?- "test(1, 2, 3, A)"
+        A = Value::atom("true");
+    ?- "test(1, 3, 2, A)"
+        A = Value::atom("false");
+    ?- "test(2, 1, 2, A)"
+        A = Value::atom("true");
+}
+
+test! {
+    op_condition => r#"
+    :- pub(big/1).
+    :- pub(between/3).
+    :- pub(test/1).
+    big(A) :- A > 10.
+    between(A, B, C) :- A <= B && B <= C.
+    num(5).
+    num(15).
+    num(25).
+    test(A) :- num(A), (A < 10 || A > 20).
+    "#
+    ?- "big(11)";
+    ?- "big(10)"
+    ?- "between(1, 2, 3)";
+    ?- "between(1, 4, 3)"
+    ?- "test(A)"
+        A = Value::integer(5);
+        A = Value::integer(25);
+}
+
+test! {
+    op_user_defined => r#"
+    :- op(<>, 8, left, concat/3).
+    :- op(=>, 1, right, implies/3).
+    :- pub(test/3).
+    :- pub(imp/3).
+    concat!(A, B) <- A + B.
+    implies!(true, B) <- B.
+    implies!(false, _) <- true.
+    test!(A, B) <- A <> ", " <> B.
+    imp!(A, B) <- A => B => false.
+    "#
+    ?- "test(\"a\", \"b\", C)"
+        C = Value::string("a, b");
+    ?- "imp(true, true, C)"
+        C = Value::atom("false");
+    ?- "imp(false, true, C)"
+        C = Value::atom("true");
+}
+
+build_error! { op_precedence_too_high => r#"
+:- op(<>, 11, left, concat/3).
+concat!(A, B) <- A + B.
+"#, "error[L0026]: Operator `<>` has invalid precedence 11." }
+
+build_error! { op_precedence_zero => r#"
+:- op(<>, 0, left, concat/3).
+concat!(A, B) <- A + B.
+"#, "error[L0026]: Operator `<>` has invalid precedence 0." }
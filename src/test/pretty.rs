@@ -0,0 +1,250 @@
+use super::*;
+use crate::ast::{AsHandle, Body, Definition, Identifier, Pattern, Query};
+use crate::program::DatabaseDefinition;
+use ramp::rational::Rational;
+use std::collections::HashMap;
+
+#[test]
+fn pretty_atoms() {
+    assert_eq!(Value::atom("hello").to_string(), "hello");
+    assert_eq!(Value::atom("snake_case2").to_string(), "snake_case2");
+    assert_eq!(Value::atom("Hello").to_string(), "'Hello'");
+    assert_eq!(Value::atom("two words").to_string(), "'two words'");
+    assert_eq!(Value::atom("it's").to_string(), "#'it's'#");
+    assert_eq!(Value::atom("'#").to_string(), "##''#'##");
+}
+
+#[test]
+fn pretty_record_order() {
+    let fields: HashMap<_, _> = vec!["d", "b", "a", "e", "c"]
+        .into_iter()
+        .enumerate()
+        .map(|(i, key)| (key.to_owned(), Some(Value::integer(i as i64))))
+        .collect();
+    assert_eq!(
+        Value::record(fields).to_string(),
+        "{a: 2, b: 1, c: 4, d: 0, e: 3}",
+    );
+}
+
+#[test]
+fn pretty_numbers() {
+    assert_eq!(Value::integer(-3).to_string(), "-3");
+    assert_eq!(
+        Value::rational(Rational::new(21.into(), 20.into())).to_string(),
+        "1.05"
+    );
+    assert_eq!(
+        Value::rational(Rational::new((-1).into(), 8.into())).to_string(),
+        "-0.125"
+    );
+    assert_eq!(
+        Value::rational(Rational::new(2.into(), 1.into())).to_string(),
+        "2.0"
+    );
+    assert_eq!(Value::float(1500.0).to_string(), "1500.0f");
+    assert_eq!(
+        Value::rational(Rational::new(1.into(), 3.into())).to_string(),
+        "1/3",
+    );
+}
+
+#[test]
+fn pretty_strings() {
+    assert_eq!(
+        Value::from("say \"hi\"\n\\").to_string(),
+        r#""say \"hi\"\n\\""#,
+    );
+}
+
+#[test]
+fn pretty_unknown() {
+    assert_eq!(Value::list(vec![Value::any(3)]).to_string(), "[_]");
+}
+
+#[test]
+fn pretty_incomplete() {
+    assert_eq!(
+        Value::List(List::new(vec![Some(Value::integer(1))], false)).to_string(),
+        "[1, ..]",
+    );
+    let record = Record::default()
+        .with("b", Some(Value::integer(2)))
+        .with("a", None);
+    assert_eq!(
+        Value::Record(Record {
+            complete: false,
+            ..record
+        })
+        .to_string(),
+        "{a: _, b: 2, ..}",
+    );
+    assert_eq!(
+        Value::Map(Map::new(
+            vec![(Value::integer(1), Some(Value::integer(2)))],
+            false
+        ))
+        .to_string(),
+        "{1 => 2, ..}",
+    );
+}
+
+#[test]
+fn pretty_structs() {
+    let point = Record::default()
+        .with("x", Some(Value::integer(1)))
+        .with("y", Some(Value::integer(2)));
+    assert_eq!(
+        Struct::new("point", Some(Value::Record(point))).to_string(),
+        "point{x: 1, y: 2}"
+    );
+    assert_eq!(
+        Struct::new("pair", Some(ints(&[1, 2]))).to_string(),
+        "pair[1, 2]"
+    );
+    assert_eq!(
+        Struct::new("some", Some(Value::string("a"))).to_string(),
+        "some(\"a\")"
+    );
+    assert_eq!(Struct::new("Some", None).to_string(), "'Some'(_)");
+}
+
+#[test]
+fn pretty_width() {
+    let value = Value::list(vec![
+        ints(&[1, 2, 3]),
+        record(&[
+            ("name", Value::string("lumber")),
+            (
+                "tags",
+                Value::list(vec![Value::atom("logic"), Value::atom("rust")]),
+            ),
+        ]),
+        Value::string("end"),
+    ]);
+    assert_eq!(
+        value.pretty(80),
+        r#"[[1, 2, 3], {name: "lumber", tags: [logic, rust]}, "end"]"#,
+    );
+    assert_eq!(
+        value.pretty(45),
+        "[\n    [1, 2, 3],\n    {name: \"lumber\", tags: [logic, rust]},\n    \"end\",\n]",
+    );
+    assert_eq!(
+        value.pretty(30),
+        "[\n    [1, 2, 3],\n    {\n        name: \"lumber\",\n        tags: [logic, rust],\n    },\n    \"end\",\n]",
+    );
+    assert_eq!(format!("{:#}", value), value.pretty(80));
+}
+
+#[test]
+fn pretty_round_trip() {
+    let question = Question::try_from("test(A)").unwrap();
+    for source in &[
+        "[1, -2, 3.25, 4.5f, \"five\"]",
+        "{'Key': #'it's'#, key: {1 => [], \"b\" => {:}}, other: {=>}}",
+        "node('Leaf'[1, 2])",
+        "[{a: 1, ..}, [1, .._], _]",
+    ] {
+        let program = Lumber::from_str(format!(":- pub(test/1).\ntest({}).\n", source)).unwrap();
+        let binding = program.ask(&question).next().unwrap();
+        let value = question.answer(&binding).unwrap().remove("A").unwrap();
+        let value = value.unwrap();
+        let printed = format!(
+            ":- pub(test/1).\ntest({}).\ntest({}).\n",
+            value,
+            value.pretty(10)
+        );
+        let program = Lumber::from_str(&printed).unwrap();
+        let answers = program
+            .ask(&question)
+            .map(|binding| question.answer(&binding).unwrap().remove("A").unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            answers,
+            vec![Some(value.clone()), Some(value)],
+            "{}",
+            printed
+        );
+    }
+}
+
+#[test]
+fn pretty_body() {
+    let question = Question::try_from("foo(A, b: B), (bar; baz) -> qux(A), C <- A + 1").unwrap();
+    let printed = question.as_ref().to_string();
+    assert_eq!(
+        printed,
+        "foo(A, b: B), (bar; baz) -> qux(A), @core::add(A, 1, Tmp3), C <- Tmp3",
+    );
+    let reparsed = Question::try_from(printed.as_str()).unwrap();
+    assert_eq!(reparsed.as_ref().to_string(), printed);
+}
+
+#[test]
+fn pretty_body_renamed_clash() {
+    let question = Question::try_from("foo(Tmp3, Tmp3_), C <- Tmp3 + 1").unwrap();
+    let printed = question.as_ref().to_string();
+    assert_eq!(
+        printed,
+        "foo(Tmp3, Tmp3_), @core::add(Tmp3, 1, Tmp3__), C <- Tmp3__",
+    );
+    let reparsed = Question::try_from(printed.as_str()).unwrap();
+    assert_eq!(reparsed.as_ref().to_string(), printed);
+}
+
+#[test]
+fn pretty_definition() {
+    let head = |name: &str| {
+        Query::new(
+            "test/1".as_handle().unwrap(),
+            vec![Pattern::Variable(Identifier::new(name.to_owned()))],
+        )
+    };
+    let mut definition = Definition::default();
+    definition.insert(head("_"), Body::default());
+    let question = Question::try_from("first(A), second(A); third(A, [1, 2, 3])").unwrap();
+    definition.insert(head("A"), question.as_ref().clone());
+    assert_eq!(
+        definition.to_string(),
+        "test(_).\ntest(A) :- first(A), second(A); third(A, [1, 2, 3]).",
+    );
+
+    let question = Question::try_from(
+        "a_rather_long_goal(A, B, C), another_long_goal(C), (a_goal(A); some_other_goal(B))",
+    )
+    .unwrap();
+    let mut definition = Definition::default();
+    definition.insert(head("A"), question.as_ref().clone());
+    assert_eq!(
+        format!("{:#}", definition),
+        "test(A) :-\n    a_rather_long_goal(A, B, C),\n    another_long_goal(C),\n    (a_goal(A); some_other_goal(B)).",
+    );
+}
+
+#[test]
+fn pretty_lambda() {
+    let source = ":- pub(test/2).\ntwice(A, B) :- B <- A.\ntest(A, B) :- @core::call([X] :- @core::equal(X, A), B).\ntest(A, B) :- @core::call([] :- C <- twice!(A)), B <- twice!(C).\n";
+    let print = |source: &str| {
+        let program = Lumber::from_str(source).unwrap();
+        match program
+            .database
+            .lookup(&"test/2".as_handle().unwrap(), true)
+        {
+            Some(DatabaseDefinition::Static(definition)) => definition.to_string(),
+            _ => unreachable!(),
+        }
+    };
+    let printed = print(source);
+    assert_eq!(
+        printed,
+        "test(A, B) :- @core::call([X] :- @core::equal(X, A), B).\ntest(A, B) :- @core::call([] :- (twice(A, Tmp2_0), C <- Tmp2_0)), twice(C, Tmp3), B <- Tmp3.",
+    );
+    assert_eq!(
+        print(&format!(
+            ":- pub(test/2).\ntwice(A, B) :- B <- A.\n{}\n",
+            printed
+        )),
+        printed
+    );
+}
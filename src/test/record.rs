@@ -0,0 +1,64 @@
+use super::*;
+
+test! {
+    record_get => ""
+    ?- "@core::record::get({ a: 1, b: 2 }, b, A)"
+        A = Value::integer(2);
+    ?- "@core::record::get({ a: 1, b: 2 }, \"a\", A)"
+        A = Value::integer(1);
+    ?- "@core::record::get({ a: 1, b: 2 }, c, A)"
+    ?- "@core::record::get({ a: 1, .. }, c, A)"
+    ?- "@core::record::get({ b: 2, a: 1 }, K, V)"
+        K = Value::atom("a"), V = Value::integer(1);
+        K = Value::atom("b"), V = Value::integer(2);
+}
+
+test! {
+    record_put => ""
+    ?- "@core::record::put({ a: 1 }, b, 2, A)"
+        A = record(&[("a", Value::integer(1)), ("b", Value::integer(2))]);
+    ?- "@core::record::put({ a: 1 }, a, 2, A)"
+        A = record(&[("a", Value::integer(2))]);
+    ?- "@core::record::put({ a: 1 }, 3, 2, A)"
+}
+
+test! {
+    record_delete => ""
+    ?- "@core::record::delete({ a: 1, b: 2 }, a, A)"
+        A = record(&[("b", Value::integer(2))]);
+    ?- "@core::record::delete({ a: 1 }, b, A)"
+        A = record(&[("a", Value::integer(1))]);
+    ?- "@core::record::delete({ a: 1, .. }, b, A)"
+}
+
+test! {
+    record_keys_values => ""
+    ?- "@core::record::keys({ b: 2, a: 1 }, A)"
+        A = Value::list(vec![Value::atom("a"), Value::atom("b")]);
+    ?- "@core::record::values({ b: 2, a: 1 }, A)"
+        A = ints(&[1, 2]);
+    ?- "@core::record::keys({:}, A)"
+        A = Value::list(Vec::<Value>::new());
+    ?- "@core::record::keys({ a: 1 }, [a, b])"
+    ?- "@core::record::keys({ a: 1, .. }, [a, b])";
+}
+
+test! {
+    record_pairs => ""
+    ?- "@core::record::pairs({ b: 2, a: 1 }, A)"
+        A = Value::list(vec![
+            Value::list(vec![Value::atom("a"), Value::integer(1)]),
+            Value::list(vec![Value::atom("b"), Value::integer(2)]),
+        ]);
+    ?- "@core::record::pairs(A, [[a, 1]])"
+        A = record(&[("a", Value::integer(1))]);
+    ?- "@core::record::pairs(A, [[1, 1]])"
+    ?- "@core::record::pairs(A, [a, 1])"
+}
+
+test! {
+    record_merge => ""
+    ?- "@core::record::merge({ a: 1, b: 2 }, { b: 3, c: 4 }, A)"
+        A = record(&[("a", Value::integer(1)), ("b", Value::integer(3)), ("c", Value::integer(4))]);
+    ?- "@core::record::merge({ a: 1 }, a, A)"
+}
@@ -0,0 +1,143 @@
+use super::*;
+
+test! {
+    string_escapes => r##"
+    :- pub(escaped/1).
+    :- pub(raw/1).
+    escaped("quote \" backslash \\ newline \n tab \t return \r nul \0 tree \u{1F332}").
+    raw(#"quote " backslash \\ \n"#).
+    "##
+    ?- "escaped(A)"
+        A = Value::string("quote \" backslash \\ newline \n tab \t return \r nul \0 tree \u{1F332}");
+    ?- "raw(A)"
+        A = Value::string("quote \" backslash \\\\ \\n");
+    ?- "escaped(\"\\u{0}\")"
+}
+
+#[test]
+fn string_display_round_trip() {
+    let value =
+        Value::string("quote \" backslash \\ newline \n bell \u{7} delete \u{7f} tree \u{1F332}");
+    let source = format!(":- pub(test/1).\ntest({}).", value);
+    let program = Lumber::builder().build_from_str(source).unwrap();
+    let question = Question::try_from("test(A)").unwrap();
+    let mut answers = program.ask(&question);
+    let answer = question.answer(&answers.next().unwrap()).unwrap();
+    assert_eq!(answer["A"], Some(value));
+}
+
+test! {
+    string_length => r#""#
+    ?- "@core::string::length(\"hello\", A)"
+        A = Value::integer(5);
+    ?- "@core::string::length(\"\u{1F332}\u{1F332}\", A)"
+        A = Value::integer(2);
+    ?- "@core::string::length(\"\", 0)";
+    ?- "@core::string::length(A, 3)"
+}
+
+test! {
+    string_concat => r#""#
+    ?- "@core::string::concat(\"ab\", \"cd\", A)"
+        A = Value::string("abcd");
+    ?- "@core::string::concat(A, B, \"ab\")"
+        A = Value::string(""), B = Value::string("ab");
+        A = Value::string("a"), B = Value::string("b");
+        A = Value::string("ab"), B = Value::string("");
+    ?- "@core::string::concat(\"a\", B, \"abc\")"
+        B = Value::string("bc");
+    ?- "@core::string::concat(A, \"c\", \"abc\")"
+        A = Value::string("ab");
+    ?- "@core::string::concat(\"x\", B, \"abc\")"
+}
+
+test! {
+    string_substring => r#""#
+    ?- "@core::string::substring(\"hello\", 1, 3, A)"
+        A = Value::string("ell");
+    ?- "@core::string::substring(\"hello\", 3, 3, A)"
+    ?- "@core::string::substring(\"hello\", A, B, \"l\")"
+        A = Value::integer(2), B = Value::integer(1);
+        A = Value::integer(3), B = Value::integer(1);
+    ?- "@core::string::substring(\"ab\", 1, A, B)"
+        A = Value::integer(0), B = Value::string("");
+        A = Value::integer(1), B = Value::string("b");
+}
+
+test! {
+    string_index_of => r#""#
+    ?- "@core::string::index_of(\"banana\", \"an\", A)"
+        A = Value::integer(1);
+        A = Value::integer(3);
+    ?- "@core::string::index_of(\"banana\", \"x\", A)"
+}
+
+test! {
+    string_split_join => r#""#
+    ?- "@core::string::split(\"a,b,,c\", \",\", A)"
+        A = Value::list(vec![Value::string("a"), Value::string("b"), Value::string(""), Value::string("c")]);
+    ?- "@core::string::split(A, \", \", [\"a\", \"b\"])"
+        A = Value::string("a, b");
+    ?- "@core::string::join([\"a\", \"b\", \"c\"], \"-\", A)"
+        A = Value::string("a-b-c");
+    ?- "@core::string::join(A, \"-\", \"a-b\")"
+        A = Value::list(vec![Value::string("a"), Value::string("b")]);
+    ?- "@core::string::join([\"a\", 1], \"-\", A)"
+}
+
+test! {
+    string_case_trim => r#""#
+    ?- "@core::string::upper(\"Hello\", A)"
+        A = Value::string("HELLO");
+    ?- "@core::string::upper(\"a\", \"A\")";
+    ?- "@core::string::lower(\"Hello\", A)"
+        A = Value::string("hello");
+    ?- "@core::string::trim(\"  hi \\n\", A)"
+        A = Value::string("hi");
+    ?- "@core::string::trim_start(\"  hi  \", A)"
+        A = Value::string("hi  ");
+    ?- "@core::string::trim_end(\"  hi  \", A)"
+        A = Value::string("  hi");
+}
+
+test! {
+    string_affixes => r#""#
+    ?- "@core::string::starts_with(\"hello\", \"he\")";
+    ?- "@core::string::starts_with(\"hello\", \"lo\")"
+    ?- "@core::string::ends_with(\"hello\", \"lo\")";
+    ?- "@core::string::ends_with(\"hello\", \"he\")"
+}
+
+test! {
+    string_chars_codes => r#""#
+    ?- "@core::string::char_code(\"a\", A)"
+        A = Value::integer(97);
+    ?- "@core::string::char_code(A, 97)"
+        A = Value::string("a");
+    ?- "@core::string::char_code(\"ab\", A)"
+    ?- "@core::string::chars(\"ab\", A)"
+        A = Value::list(vec![Value::string("a"), Value::string("b")]);
+    ?- "@core::string::chars(A, [\"a\", \"b\"])"
+        A = Value::string("ab");
+    ?- "@core::string::codes(\"ab\", A)"
+        A = ints(&[97, 98]);
+    ?- "@core::string::codes(A, [97, 98])"
+        A = Value::string("ab");
+}
+
+test! {
+    string_number => r#""#
+    ?- "@core::string::number(\"42\", A)"
+        A = Value::integer(42);
+    ?- "@core::string::number(\"-42\", A)"
+        A = Value::integer(-42);
+    ?- "@core::string::number(\"1.5\", A)"
+        A = Value::rational(1.5);
+    ?- "@core::string::number(\"3/4\", A)"
+        A = Value::rational(0.75);
+    ?- "@core::string::number(\"4x\", A)"
+    ?- "@core::string::number(\"\", A)"
+    ?- "@core::string::number(\"1.\", A)"
+    ?- "@core::string::number(A, 42)"
+        A = Value::string("42");
+}
@@ -0,0 +1,66 @@
+use super::*;
+
+#[test]
+fn testing_discovery() {
+    let here = PathBuf::from(file!()).parent().unwrap().join("testing");
+    let program = Lumber::from_file(here.join("main.lumber")).expect("program should compile");
+    let tests = program
+        .tests()
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>();
+    assert_eq!(
+        tests,
+        vec![
+            "math::any_clause/0",
+            "math::doubles/0",
+            "math::fails/0",
+            "root_passes/0",
+        ],
+    );
+    assert!(program.warnings().is_empty());
+}
+
+#[test]
+fn testing_pass() {
+    let here = PathBuf::from(file!()).parent().unwrap().join("testing");
+    let program = Lumber::from_file(here.join("main.lumber")).expect("program should compile");
+    let tests = program.tests();
+    assert!(program.run_test(&tests[0]).is_ok(), "{}", tests[0]);
+    assert!(program.run_test(&tests[1]).is_ok(), "{}", tests[1]);
+    assert!(program.run_test(&tests[3]).is_ok(), "{}", tests[3]);
+}
+
+#[test]
+fn testing_failure() {
+    let here = PathBuf::from(file!()).parent().unwrap().join("testing");
+    let program = Lumber::from_file(here.join("main.lumber")).expect("program should compile");
+    let failure = program.run_test(&program.tests()[2]).unwrap_err();
+    assert_eq!(failure.goal(), "@core::equal(B, 7)");
+    assert_eq!(failure.location().unwrap().line(), 12);
+    assert_eq!(
+        failure.bindings(),
+        &[("B".to_owned(), Value::integer(8))][..],
+    );
+}
+
+#[test]
+fn testing_disjunction() {
+    let program = Lumber::from_str(":- test(either).\neither :- a(1); a(3).\na(2).\n").unwrap();
+    let failure = program.run_test(&program.tests()[0]).unwrap_err();
+    assert_eq!(failure.goal(), "a(1); a(3)");
+    assert!(failure.bindings().is_empty());
+}
+
+#[test]
+fn testing_other_program() {
+    let program = Lumber::from_str(":- test(t).\nt.\nu.\n").unwrap();
+    let other = Lumber::from_str(":- test(u).\nu.\n:- test(v).\nv.\n").unwrap();
+    let tests = other.tests();
+    assert_eq!(program.run_test(&tests[0]).unwrap_err().goal(), "u");
+    assert_eq!(program.run_test(&tests[1]).unwrap_err().goal(), "v");
+}
+
+build_error!(testing_undefined => ":- test(missing).", "Test missing/0 is declared, but it is not defined");
+
+build_error!(testing_mutable => ":- mut(t/0).\n:- test(t).", "Test t/0 cannot be mutable.");
@@ -0,0 +1,107 @@
+use super::*;
+
+test! {
+    typed_record => r#"
+    :- pub(name/2).
+    :- type(person, { name: string, age: integer }).
+    :- type(name/2, [person, string]).
+    name({ name: N, age: _ }, N).
+    "#
+    ?- "name({ name: \"Alice\", age: 30 }, N)"
+        N = Value::from("Alice");
+}
+
+test! {
+    typed_alternatives => r#"
+    :- pub(area/2).
+    :- type(shape, circle(number)).
+    :- type(shape, square(number)).
+    :- type(area/2, [shape, number]).
+    area!(circle(R)) <- R * R * 3.
+    area!(square(S)) <- S * S.
+    "#
+    ?- "area(square(2), A)"
+        A = Value::integer(4);
+}
+
+test! {
+    typed_recursive => r#"
+    :- pub(length/2).
+    :- type(nat, atom(z)).
+    :- type(nat, s(nat)).
+    :- type(length/2, [nat, integer]).
+    length(z, 0).
+    length!(s(N)) <- length!(N) + 1.
+    "#
+    ?- "length(s(s(z)), L)"
+        L = Value::integer(2);
+}
+
+test! {
+    imported_type => r#"
+    :- pub(age/2).
+    :- mod(people).
+    :- use(people(person)).
+    :- type(age/2, [person, integer]).
+    age({ age: A, .. }, A).
+    "#
+    ?- "age({ name: \"Bob\", age: 40 }, A)"
+        A = Value::integer(40);
+}
+
+build_error! {
+    misspelled_field => r#"
+    :- type(person, { name: string, age: integer }).
+    :- type(name/2, [person, string]).
+    name({ nmae: N, age: _ }, N).
+    "#, "argument 1 is not of type person"
+}
+
+build_error! {
+    misspelled_struct => r#"
+    :- type(shape, circle(number)).
+    :- type(area/2, [shape, number]).
+    area(cirlce(R), R).
+    "#, "argument 1 is not of type shape"
+}
+
+build_error! {
+    ill_typed_call => r#"
+    :- type(double/2, [integer, integer]).
+    double!(A) <- A * 2.
+    quad!(A) <- double!("two") * A.
+    "#, "Call to double/2 does not match"
+}
+
+build_error! {
+    unknown_type => r#"
+    :- type(name/2, [person, string]).
+    name(_, "").
+    "#, "person"
+}
+
+build_error! {
+    signature_arity => r#"
+    :- type(name/2, [string]).
+    name(_, "").
+    "#, "name/2"
+}
+
+build_error! {
+    undefined_signature => r#"
+    :- type(name/2, [string, string]).
+    "#, "name/2"
+}
+
+build_error! {
+    builtin_type => r#"
+    :- type(integer, string).
+    "#, "Type integer is built in"
+}
+
+build_error! {
+    non_exported_imported_type => r#"
+    :- mod(people).
+    :- use(people(person)).
+    "#, "person"
+}